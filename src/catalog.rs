@@ -0,0 +1,215 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::normalization::{
+    canonical_type_to_move_string, resolve_type_param_names, rpc_visibility_to_string,
+    type_to_canonical_json_with,
+};
+use crate::types::{SchemaSource, UnknownTypePolicy};
+
+/// One function a transaction can call, with its signature rendered as Move type strings.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CallableEntry {
+    pub module: String,
+    pub name: String,
+    pub type_params: usize,
+    pub params: Vec<String>,
+    pub returns: Vec<String>,
+    pub is_entry: bool,
+}
+
+/// Every `public` or `entry` function in `interface`, sorted by module then name. Types the
+/// canonicalizer does not know are rendered from their raw JSON rather than dropped.
+pub fn public_call_catalog(interface: &Value, source: SchemaSource) -> Vec<CallableEntry> {
+    let (functions_key, tps_key, params_key, returns_key, entry_key) = match source {
+        SchemaSource::Rpc => (
+            "exposedFunctions",
+            "typeParameters",
+            "parameters",
+            "return",
+            "isEntry",
+        ),
+        SchemaSource::Bytecode => ("functions", "type_params", "params", "returns", "is_entry"),
+    };
+    let empty = Vec::new();
+    let mut out = Vec::new();
+    let Some(modules) = interface.get("modules").and_then(Value::as_object) else {
+        return out;
+    };
+    for (module_name, module) in modules {
+        let Some(functions) = module.get(functions_key).and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, fun) in functions {
+            let visibility = match source {
+                SchemaSource::Rpc => fun.get("visibility").and_then(rpc_visibility_to_string),
+                SchemaSource::Bytecode => fun
+                    .get("visibility")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            };
+            let is_entry = fun.get(entry_key).and_then(Value::as_bool).unwrap_or(false);
+            if visibility.as_deref() != Some("public") && !is_entry {
+                continue;
+            }
+            let tps = fun.get(tps_key).and_then(Value::as_array).unwrap_or(&empty);
+            let render = |key: &str| -> Vec<String> {
+                fun.get(key)
+                    .and_then(Value::as_array)
+                    .unwrap_or(&empty)
+                    .iter()
+                    .map(|t| {
+                        let t = resolve_type_param_names(t, tps);
+                        match type_to_canonical_json_with(
+                            source,
+                            &t,
+                            UnknownTypePolicy::PassThrough,
+                        ) {
+                            Ok(canon) => canonical_type_to_move_string(&canon),
+                            Err(_) => t.to_string(),
+                        }
+                    })
+                    .collect()
+            };
+            out.push(CallableEntry {
+                module: module_name.clone(),
+                name: name.clone(),
+                type_params: tps.len(),
+                params: render(params_key),
+                returns: render(returns_key),
+                is_entry,
+            });
+        }
+    }
+    out.sort_by(|a, b| (&a.module, &a.name).cmp(&(&b.module, &b.name)));
+    out
+}
+
+/// Entry functions (`module::name`) of two interfaces, bucketed by where they appear.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct EntrySurfaceDiff {
+    /// Entry on both sides, whether or not the signature matches.
+    pub both: Vec<String>,
+    pub rpc_only: Vec<String>,
+    pub bytecode_only: Vec<String>,
+    /// Entry on both sides with differing type-param count, params or returns.
+    pub signature_changed: Vec<String>,
+}
+
+/// Compare only the `entry` surface (what a PTB can call as a transaction entry) of two
+/// interfaces, using the rendered signatures of `public_call_catalog`.
+pub fn entry_surface_diff(rpc: &Value, bytecode: &Value) -> EntrySurfaceDiff {
+    let entries = |interface, source| -> BTreeMap<String, CallableEntry> {
+        public_call_catalog(interface, source)
+            .into_iter()
+            .filter(|e| e.is_entry)
+            .map(|e| (format!("{}::{}", e.module, e.name), e))
+            .collect()
+    };
+    let rpc_entries = entries(rpc, SchemaSource::Rpc);
+    let byte_entries = entries(bytecode, SchemaSource::Bytecode);
+
+    let mut diff = EntrySurfaceDiff::default();
+    for (name, r) in &rpc_entries {
+        match byte_entries.get(name) {
+            Some(b) => {
+                diff.both.push(name.clone());
+                if (r.type_params, &r.params, &r.returns) != (b.type_params, &b.params, &b.returns)
+                {
+                    diff.signature_changed.push(name.clone());
+                }
+            }
+            None => diff.rpc_only.push(name.clone()),
+        }
+    }
+    diff.bytecode_only = byte_entries
+        .keys()
+        .filter(|name| !rpc_entries.contains_key(*name))
+        .cloned()
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_call_catalog_lists_public_and_entry_functions() {
+        let coin = serde_json::json!({"kind": "datatype", "address": "0x2", "module": "coin", "name": "Coin", "type_args": [{"kind": "type_param", "index": 0}]});
+        let fun = |visibility: &str, is_entry: bool, params: Value, returns: Value| {
+            serde_json::json!({
+                "visibility": visibility,
+                "is_entry": is_entry,
+                "type_params": [{"constraints": []}],
+                "params": params,
+                "returns": returns
+            })
+        };
+        let interface = serde_json::json!({
+            "modules": {
+                "pool": {"functions": {
+                    "swap": fun(
+                        "public",
+                        false,
+                        serde_json::json!([{"kind": "ref", "mutable": true, "to": coin}, {"kind": "u64"}]),
+                        serde_json::json!([coin])
+                    ),
+                    "claim": fun("private", true, serde_json::json!([{"kind": "vector", "type": {"kind": "u8"}}]), serde_json::json!([])),
+                    "settle": fun("friend", false, serde_json::json!([]), serde_json::json!([])),
+                    "helper": fun("private", false, serde_json::json!([]), serde_json::json!([]))
+                }}
+            }
+        });
+        let catalog = public_call_catalog(&interface, SchemaSource::Bytecode);
+        let coin_t0 =
+            "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<T0>";
+        assert_eq!(
+            catalog,
+            vec![
+                CallableEntry {
+                    module: "pool".to_string(),
+                    name: "claim".to_string(),
+                    type_params: 1,
+                    params: vec!["vector<u8>".to_string()],
+                    returns: vec![],
+                    is_entry: true,
+                },
+                CallableEntry {
+                    module: "pool".to_string(),
+                    name: "swap".to_string(),
+                    type_params: 1,
+                    params: vec![format!("&mut {coin_t0}"), "u64".to_string()],
+                    returns: vec![coin_t0.to_string()],
+                    is_entry: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_surface_diff_buckets() {
+        let rpc_fun = |is_entry: bool, params: Value| serde_json::json!({"visibility": "Private", "isEntry": is_entry, "typeParameters": [], "parameters": params, "return": []});
+        let byte_fun = |is_entry: bool, params: Value| serde_json::json!({"visibility": "private", "is_entry": is_entry, "type_params": [], "params": params, "returns": []});
+        let rpc = serde_json::json!({"modules": {"pool": {"exposedFunctions": {
+            "deposit": rpc_fun(true, serde_json::json!(["U64"])),
+            "withdraw": rpc_fun(true, serde_json::json!(["U64"])),
+            "claim": rpc_fun(false, serde_json::json!([]))
+        }}}});
+        let bytecode = serde_json::json!({"modules": {"pool": {"functions": {
+            "deposit": byte_fun(true, serde_json::json!([{"kind": "u64"}])),
+            "withdraw": byte_fun(true, serde_json::json!([{"kind": "u128"}])),
+            "claim": byte_fun(true, serde_json::json!([]))
+        }}}});
+        assert_eq!(
+            entry_surface_diff(&rpc, &bytecode),
+            EntrySurfaceDiff {
+                both: vec!["pool::deposit".to_string(), "pool::withdraw".to_string()],
+                rpc_only: vec![],
+                bytecode_only: vec!["pool::claim".to_string()],
+                signature_changed: vec!["pool::withdraw".to_string()],
+            }
+        );
+    }
+}
@@ -1,726 +1,6379 @@
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crate::bytecode::get_object;
+use move_binary_format::file_format::{Ability, AbilitySet};
+
+use crate::bytecode::{extract_sanity_counts, get_object, struct_has_key};
 use crate::normalization::{
-    abilities_from_value, bytecode_type_to_canonical_json, rpc_type_to_canonical_json,
-    rpc_visibility_to_string,
+    abilities_from_value, ability_set_from_value, bare_struct_name, effective_field_abilities,
+    estimate_struct_size, is_canonical_datatype, normalize_address_str, resolve_type_param_names,
+    rpc_interface_to_bytecode_schema, rpc_visibility_to_string, type_to_canonical_json,
+    type_to_canonical_json_with,
 };
 use crate::types::{
-    BytecodeModuleCheck, InterfaceCompareMismatch, InterfaceCompareSummary, ModuleSetDiff,
+    BytecodeModuleCheck, BytecodeVsRpcSummary, FunctionCallability, FunctionChange, FunctionStatus,
+    FunctionVerdict, InterfaceCompareMismatch, InterfaceCompareReport, InterfaceCompareSummary,
+    MismatchKind, MismatchSeverity, ModuleBreakdown, ModuleCompareStats, ModuleSetDiff,
+    PassingMode, SchemaSource, ShapeCheck, SizeDiff, UnknownTypePolicy, ValidationIssue,
 };
-use crate::utils::canonicalize_json_value;
+use crate::utils::{bytes_to_hex, canonicalize_json_value, path_matches_pattern, sha256_32};
 
+#[derive(Debug, Clone, Default)]
 pub struct InterfaceCompareOptions {
     pub max_mismatches: usize,
     pub include_values: bool,
+    /// Path patterns (`*` within a segment, `**` across segments, or a plain prefix) whose
+    /// captured `rpc`/`bytecode` values are replaced with `"<redacted>"`. Detection is unaffected.
+    pub redact_paths: Vec<String>,
+    /// Checked once per intersecting module; when set, the walk stops early and the summary is
+    /// returned with `complete: false`.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Emit a `Warning` when a struct's set of field names differs between sources, since
+    /// generated field accessors in SDKs are keyed by name.
+    pub check_accessor_fields: bool,
+    /// Pair struct fields by name instead of position: report added, removed and retyped fields
+    /// individually, and a reordering once (`FieldOrderChanged`), rather than a cascade of
+    /// positional name/type mismatches.
+    pub match_fields_by_name: bool,
+    /// Path patterns (same syntax as `redact_paths`) for known, intentional tolerances. Matching
+    /// mismatches are kept in the output with `accepted: true` and `Info` severity, so they are
+    /// documented in reports without counting as errors.
+    pub accept_paths: Vec<String>,
+    /// Path patterns (same syntax as `redact_paths`) for differences to suppress outright, e.g. a
+    /// baseline of known deviations. Matching mismatches are counted in `ignored_mismatches`
+    /// only (not in `mismatches_total`, the severity or item counts, or `type_sites_mismatched`),
+    /// and are never sampled.
+    pub ignore_paths: Vec<String>,
+    /// Glob (`*`, `?`) over bare function names, e.g. `swap*`. Non-matching functions are
+    /// skipped entirely and not counted in `functions_compared`.
+    pub function_name_filter: Option<String>,
+    /// Emit a `Warning` when a function gains or loses a `0x2::tx_context::TxContext`
+    /// parameter, since SDKs inject it when building PTBs.
+    pub check_tx_context: bool,
+    /// `validate_interface` only: flag fields whose effective abilities (resolved against the
+    /// package's own struct declarations) cannot satisfy the struct's declared abilities, e.g. a
+    /// `key` struct storing a non-`store` value.
+    pub check_field_abilities: bool,
+    /// Report bytecode functions the RPC side does not list (`"extra function in bytecode"`).
+    /// Only functions RPC would have exposed are considered; see `rpc_would_expose`.
+    pub report_extra_functions: bool,
+    /// With `report_extra_functions`, also report the bytecode functions RPC never lists
+    /// (private non-entry ones, or non-public ones against a public-only endpoint), as `Info`
+    /// since their absence from RPC is expected. Gives a full inventory of bytecode-only code.
+    pub report_private_extra_functions: bool,
+    /// How type kinds unknown to the canonicalizers are handled (default: parse-error `Error`).
+    pub unknown_type_policy: UnknownTypePolicy,
+    /// Emit a `Warning` per differing parameter name when both sides carry names (RPC
+    /// `parameterNames`, bytecode `param_names`); skipped when either side lacks them.
+    pub compare_param_names: bool,
+    /// Match module, struct and function names that differ only in ASCII case, emitting a
+    /// `Warning` (`"name casing differs: Pool vs pool"`) instead of missing/extra mismatches.
+    pub case_insensitive_names: bool,
+    /// Emit `Info` diagnostics for module `uses`/`imports` entries present on only one side;
+    /// skipped when either side lacks the list.
+    pub compare_imports: bool,
+    /// For type mismatches, also keep both full canonical types (`rpc_canonical`,
+    /// `bytecode_canonical`) to show what survived canonicalization.
+    pub include_canonical: bool,
+    /// Key modules by `<address>::<name>` when they carry an `address`, so same-named modules
+    /// of different packages are not cross-matched; modules without one keep their bare key.
+    pub key_modules_by_address: bool,
+    /// For functions flagged `entry` on a side, check that side's parameters are types Sui
+    /// accepts in entry functions; disagreements are reported as `Warning`s per parameter.
+    pub check_entry_params: bool,
+    /// Treat a non-entry RPC function without `visibility` as `public` (it is listed in
+    /// `exposedFunctions`) instead of `<unknown>`. Entry functions stay `<unknown>`, since
+    /// private entry functions are exposed too.
+    pub infer_missing_visibility: bool,
+    /// Downgrade a function type-param arity mismatch to `Info` when the extra type params on
+    /// the longer side appear in none of its param or return types (a no-op at the ABI level).
+    pub ignore_unused_type_params: bool,
+    /// Emit a `Warning` when a struct's estimated minimum serialized size differs between sources
+    /// (see `estimate_struct_size`); layouts that cannot be estimated are skipped.
+    pub compare_struct_sizes: bool,
+    /// Treat `0x1::string::String`, `0x1::ascii::String` and `vector<u8>` as the same type, for
+    /// schemas that lower strings to their byte representation. Nominally they differ.
+    pub unify_string_types: bool,
+    /// Treat `0x1::option::Option<T>` and `vector<T>` as the same type, for schemas that lower
+    /// `Option` to its underlying vector (at most one element). Nominally they differ.
+    pub unify_option_types: bool,
+    /// Emit a `Warning` when a function is marked deprecated on one side only, so SDKs know
+    /// when to stop surfacing it.
+    pub check_deprecation: bool,
+    /// Restrict the comparison to one item path (e.g. `modules/pool/functions/swap` or
+    /// `modules/pool`): modules, structs and functions off that path are not walked.
+    pub focus_path: Option<String>,
+    /// Check that each `acquires` entry (on whichever sides carry one) names a `key` struct
+    /// declared in the function's module; invalid entries are reported as `Warning`s.
+    pub check_acquires: bool,
+    /// Emit a `Warning` for `public` functions whose param or return types reference a struct
+    /// of this package with neither `store` nor `key`, which SDK callers cannot meaningfully
+    /// construct or hold.
+    pub check_internal_type_exposure: bool,
+    /// When both interfaces carry a top-level package `version` and they differ, report a single
+    /// `Error` and skip the walk (`complete: false`): the inputs are different upgrades.
+    pub check_package_version: bool,
+    /// Compare param and return types with their outer `&`/`&mut` removed, so `&T`, `&mut T`
+    /// and `T` unify. The reference difference is still reported, as `PassingModeChanged`.
+    pub value_shape_mode: bool,
+    /// Roll every finding (not just the kept samples) up into one `FunctionVerdict` per
+    /// function, returned by `compare_interface_rpc_vs_bytecode_with_verdicts` and
+    /// `CompareConfig::run`.
+    pub per_function_verdicts: bool,
 }
 
-pub fn compare_interface_rpc_vs_bytecode(
-    _package_id: &str,
-    rpc_interface_value: &Value,
-    bytecode_interface_value: &Value,
-    opts: InterfaceCompareOptions,
-) -> (InterfaceCompareSummary, Vec<InterfaceCompareMismatch>) {
-    let mut mismatches: Vec<InterfaceCompareMismatch> = Vec::new();
-    let mut mismatch_count_total: usize = 0;
-
-    let mut push_mismatch =
-        |path: String, reason: String, rpc: Option<Value>, bytecode: Option<Value>| {
-            mismatch_count_total += 1;
-            if mismatches.len() < opts.max_mismatches {
-                let (rpc, bytecode) = if opts.include_values {
-                    (rpc, bytecode)
-                } else {
-                    (None, None)
-                };
-                mismatches.push(InterfaceCompareMismatch {
-                    path,
-                    reason,
-                    rpc,
-                    bytecode,
-                });
-            }
-        };
-
-    let empty_modules = serde_json::Map::new();
-    let rpc_modules = rpc_interface_value
-        .get("modules")
-        .and_then(Value::as_object)
-        .unwrap_or(&empty_modules);
-    let byte_modules = bytecode_interface_value
-        .get("modules")
-        .and_then(Value::as_object)
-        .unwrap_or(&empty_modules);
-
-    let mut rpc_module_names: Vec<&String> = rpc_modules.keys().collect();
-    rpc_module_names.sort();
-    let mut byte_module_names: Vec<&String> = byte_modules.keys().collect();
-    byte_module_names.sort();
-
-    let rpc_set: HashSet<&str> = rpc_module_names.iter().map(|s| s.as_str()).collect();
-    let byte_set: HashSet<&str> = byte_module_names.iter().map(|s| s.as_str()).collect();
-
-    let modules_missing_in_bytecode: Vec<&str> = rpc_module_names
-        .iter()
-        .map(|s| s.as_str())
-        .filter(|m| !byte_set.contains(m))
-        .collect();
-    for m in &modules_missing_in_bytecode {
-        push_mismatch(
-            format!("modules/{m}"),
-            "module missing in bytecode".to_string(),
-            rpc_modules.get(*m).cloned(),
-            None,
-        );
+pub fn function_callability(visibility: &str, is_entry: bool) -> FunctionCallability {
+    match (visibility == "public", is_entry) {
+        (true, true) => FunctionCallability::Entrypoint,
+        (true, false) => FunctionCallability::Public,
+        (false, true) => FunctionCallability::EntryOnly,
+        (false, false) => FunctionCallability::Internal,
     }
+}
 
-    let modules_extra_in_bytecode: Vec<&str> = byte_module_names
-        .iter()
-        .map(|s| s.as_str())
-        .filter(|m| !rpc_set.contains(m))
-        .collect();
-    for m in &modules_extra_in_bytecode {
-        push_mismatch(
-            format!("modules/{m}"),
-            "extra module in bytecode".to_string(),
-            None,
-            byte_modules.get(*m).cloned(),
-        );
+/// Rewrite `0x1::string::String` and `0x1::ascii::String` nodes in a canonical type to
+/// `vector<u8>`, their BCS layout.
+fn lower_string_types(canon: &mut Value) {
+    if is_canonical_datatype(canon, "0x1", "string", "String")
+        || is_canonical_datatype(canon, "0x1", "ascii", "String")
+    {
+        *canon = serde_json::json!({"kind": "vector", "type": {"kind": "u8"}});
+        return;
     }
+    match canon {
+        Value::Object(obj) => obj.values_mut().for_each(lower_string_types),
+        Value::Array(items) => items.iter_mut().for_each(lower_string_types),
+        _ => {}
+    }
+}
 
-    let mut modules_compared = 0usize;
-    let mut structs_compared = 0usize;
-    let mut struct_mismatches = 0usize;
-    let mut functions_compared = 0usize;
-    let mut function_mismatches = 0usize;
-
-    let mut intersection: Vec<&str> = rpc_module_names
-        .iter()
-        .map(|s| s.as_str())
-        .filter(|m| byte_set.contains(*m))
-        .collect();
-    intersection.sort();
+/// Rewrite `0x1::option::Option<T>` nodes in a canonical type to `vector<T>`, their layout.
+fn lower_option_types(canon: &mut Value) {
+    if is_canonical_datatype(canon, "0x1", "option", "Option") {
+        let mut inner = canon
+            .get_mut("type_args")
+            .and_then(Value::as_array_mut)
+            .and_then(|args| args.pop())
+            .unwrap_or(Value::Null);
+        lower_option_types(&mut inner);
+        *canon = serde_json::json!({"kind": "vector", "type": inner});
+        return;
+    }
+    match canon {
+        Value::Object(obj) => obj.values_mut().for_each(lower_option_types),
+        Value::Array(items) => items.iter_mut().for_each(lower_option_types),
+        _ => {}
+    }
+}
 
-    for module_name in intersection {
-        modules_compared += 1;
+/// Param-count gap at which two same-named functions look like different functions merged under
+/// one name by the extractor rather than an upgrade of one function.
+const COLLISION_PARAM_GAP: usize = 3;
 
-        let rpc_mod = rpc_modules.get(module_name).unwrap_or(&Value::Null);
-        let byte_mod = byte_modules.get(module_name).unwrap_or(&Value::Null);
+/// Kind and reason for a type-param count difference. Going to or from zero type params is a
+/// redesign that breaks every existing reference, so it is reported as `GenericityChanged`.
+fn type_param_arity_mismatch(rpc_len: usize, byte_len: usize) -> (MismatchKind, String) {
+    if rpc_len == 0 || byte_len == 0 {
+        (
+            MismatchKind::GenericityChanged,
+            format!("genericity changed (rpc={rpc_len} bytecode={byte_len})"),
+        )
+    } else {
+        (
+            MismatchKind::TypeParamArityMismatch,
+            format!("type param arity mismatch (rpc={rpc_len} bytecode={byte_len})"),
+        )
+    }
+}
 
-        let rpc_structs = get_object(rpc_mod, &["structs"])
-            .cloned()
-            .unwrap_or_default();
-        let byte_structs = get_object(byte_mod, &["structs"])
-            .cloned()
-            .unwrap_or_default();
+/// Passing mode of a canonical parameter type.
+pub fn param_passing_mode(canon: &Value) -> PassingMode {
+    if canon.get("kind").and_then(Value::as_str) != Some("ref") {
+        return PassingMode::ByValue;
+    }
+    if canon.get("mutable").and_then(Value::as_bool) == Some(true) {
+        PassingMode::ByMutRef
+    } else {
+        PassingMode::ByRef
+    }
+}
 
-        let mut rpc_struct_names: Vec<String> = rpc_structs.keys().cloned().collect();
-        rpc_struct_names.sort();
-        let mut byte_struct_names: Vec<String> = byte_structs.keys().cloned().collect();
-        byte_struct_names.sort();
+/// Whether a canonical parameter type, behind any reference, is a datatype or type parameter,
+/// i.e. can be an object argument. Primitives and vectors are pure values to a PTB.
+fn may_be_object(canon: &Value) -> bool {
+    let inner = if canon.get("kind").and_then(Value::as_str) == Some("ref") {
+        canon.get("to").unwrap_or(&Value::Null)
+    } else {
+        canon
+    };
+    matches!(
+        inner.get("kind").and_then(Value::as_str),
+        Some("datatype" | "type_param")
+    )
+}
 
-        let byte_struct_set: HashSet<&str> = byte_struct_names.iter().map(|s| s.as_str()).collect();
-        for sname in &rpc_struct_names {
-            if !byte_struct_set.contains(sname.as_str()) {
-                struct_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/structs/{sname}"),
-                    "struct missing in bytecode".to_string(),
-                    rpc_structs.get(sname).cloned(),
-                    None,
-                );
+/// Field/param/return type sites whose datatypes live at an address that is neither one of the
+/// interface's own modules, `package_id`, the implicit framework packages (`0x1`-`0x3`), nor a
+/// declared dependency. Dependencies come from a top-level `dependencies` array of addresses or
+/// a `linkage_table` object keyed by address; without either the check cannot run (`None`).
+fn dangling_type_references(
+    source: SchemaSource,
+    interface_value: &Value,
+    modules: &serde_json::Map<String, Value>,
+    package_id: &str,
+) -> Option<Vec<(String, String)>> {
+    fn addresses(v: &Value, out: &mut BTreeSet<String>) {
+        match v {
+            Value::Object(obj) => {
+                if obj.get("kind").and_then(Value::as_str) == Some("datatype") {
+                    if let Some(a) = obj.get("address").and_then(Value::as_str) {
+                        out.insert(normalize_address_str(a).unwrap_or_else(|_| a.to_string()));
+                    }
+                }
+                obj.values().for_each(|v| addresses(v, out));
             }
+            Value::Array(items) => items.iter().for_each(|v| addresses(v, out)),
+            _ => {}
         }
+    }
 
-        for sname in &rpc_struct_names {
-            let Some(rpc_struct) = rpc_structs.get(sname) else {
-                continue;
-            };
-            let Some(byte_struct) = byte_structs.get(sname) else {
-                continue;
-            };
-            structs_compared += 1;
-
-            let rpc_abilities = rpc_struct
-                .get("abilities")
-                .map(abilities_from_value)
-                .unwrap_or_default();
-            let byte_abilities = byte_struct
-                .get("abilities")
-                .map(abilities_from_value)
-                .unwrap_or_default();
-            if rpc_abilities != byte_abilities {
-                struct_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/structs/{sname}/abilities"),
-                    "abilities mismatch".to_string(),
-                    rpc_struct.get("abilities").cloned(),
-                    byte_struct.get("abilities").cloned(),
-                );
-            }
+    let declared: Vec<String> = match (
+        interface_value.get("dependencies"),
+        interface_value.get("linkage_table"),
+    ) {
+        (Some(Value::Array(deps)), _) => deps
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        (_, Some(Value::Object(linkage))) => linkage.keys().cloned().collect(),
+        _ => return None,
+    };
+    let available: BTreeSet<String> = declared
+        .iter()
+        .map(String::as_str)
+        .chain(["0x1", "0x2", "0x3", package_id])
+        .chain(
+            modules
+                .values()
+                .filter_map(|m| m.get("address").and_then(Value::as_str)),
+        )
+        .filter_map(|a| normalize_address_str(a).ok())
+        .collect();
 
-            let rpc_tps = rpc_struct
-                .get("typeParameters")
+    let (function_key, params_key, returns_key, tps_key) = match source {
+        SchemaSource::Rpc => ("exposedFunctions", "parameters", "return", "typeParameters"),
+        SchemaSource::Bytecode => ("functions", "params", "returns", "type_params"),
+    };
+    let mut sites: Vec<(String, &Value, &[Value])> = Vec::new();
+    for (module_name, module) in modules {
+        let structs = module.get("structs").and_then(Value::as_object);
+        for (sname, decl) in structs.into_iter().flatten() {
+            let tps = decl
+                .get(tps_key)
                 .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_tps = byte_struct
-                .get("type_params")
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            for field in decl
+                .get("fields")
                 .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            if rpc_tps.len() != byte_tps.len() {
-                struct_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/structs/{sname}/type_params"),
-                    format!(
-                        "type param arity mismatch (rpc={} bytecode={})",
-                        rpc_tps.len(),
-                        byte_tps.len()
-                    ),
-                    rpc_struct.get("typeParameters").cloned(),
-                    byte_struct.get("type_params").cloned(),
-                );
-            } else {
-                for (i, (rtp, btp)) in rpc_tps.iter().zip(byte_tps.iter()).enumerate() {
-                    let rpc_constraints = rtp
-                        .get("constraints")
-                        .map(abilities_from_value)
-                        .unwrap_or_default();
-                    let rpc_is_phantom = rtp
-                        .get("isPhantom")
-                        .and_then(Value::as_bool)
-                        .unwrap_or(false);
-                    let byte_constraints = btp
-                        .get("constraints")
-                        .map(abilities_from_value)
-                        .unwrap_or_default();
-                    let byte_is_phantom = btp
-                        .get("is_phantom")
-                        .and_then(Value::as_bool)
-                        .unwrap_or(false);
-                    if rpc_constraints != byte_constraints || rpc_is_phantom != byte_is_phantom {
-                        struct_mismatches += 1;
-                        push_mismatch(
-                            format!("modules/{module_name}/structs/{sname}/type_params[{i}]"),
-                            "struct type param mismatch".to_string(),
-                            Some(
-                                serde_json::json!({"constraints": rpc_constraints, "is_phantom": rpc_is_phantom}),
-                            ),
-                            Some(
-                                serde_json::json!({"constraints": byte_constraints, "is_phantom": byte_is_phantom}),
-                            ),
-                        );
-                    }
+                .into_iter()
+                .flatten()
+            {
+                let fname = field.get("name").and_then(Value::as_str).unwrap_or("");
+                if let Some(ty) = field.get("type") {
+                    sites.push((
+                        format!("modules/{module_name}/structs/{sname}/fields/{fname}"),
+                        ty,
+                        tps,
+                    ));
                 }
             }
-
-            let rpc_fields = rpc_struct
-                .get("fields")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_fields = byte_struct
-                .get("fields")
+        }
+        let functions = module.get(function_key).and_then(Value::as_object);
+        for (fname, fun) in functions.into_iter().flatten() {
+            let tps = fun
+                .get(tps_key)
                 .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_is_native = byte_struct
-                .get("is_native")
-                .and_then(Value::as_bool)
-                .unwrap_or(false);
-            if byte_is_native && rpc_fields.is_empty() {
-            } else if rpc_fields.len() != byte_fields.len() {
-                struct_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/structs/{sname}/fields"),
-                    format!(
-                        "field count mismatch (rpc={} bytecode={})",
-                        rpc_fields.len(),
-                        byte_fields.len()
-                    ),
-                    rpc_struct.get("fields").cloned(),
-                    byte_struct.get("fields").cloned(),
-                );
-            } else {
-                for (i, (rf, bf)) in rpc_fields.iter().zip(byte_fields.iter()).enumerate() {
-                    let rname = rf.get("name").and_then(Value::as_str).unwrap_or("");
-                    let bname = bf.get("name").and_then(Value::as_str).unwrap_or("");
-                    if rname != bname {
-                        struct_mismatches += 1;
-                        push_mismatch(
-                            format!("modules/{module_name}/structs/{sname}/fields[{i}]/name"),
-                            "field name mismatch".to_string(),
-                            rf.get("name").cloned(),
-                            bf.get("name").cloned(),
-                        );
-                        continue;
-                    }
-                    let rty = rf.get("type").unwrap_or(&Value::Null);
-                    let bty = bf.get("type").unwrap_or(&Value::Null);
-                    let rcanon = rpc_type_to_canonical_json(rty);
-                    let bcanon = bytecode_type_to_canonical_json(bty);
-                    match (rcanon, bcanon) {
-                        (Ok(mut r), Ok(mut b)) => {
-                            canonicalize_json_value(&mut r);
-                            canonicalize_json_value(&mut b);
-                            if r != b {
-                                struct_mismatches += 1;
-                                push_mismatch(
-                                    format!(
-                                        "modules/{module_name}/structs/{sname}/fields[{i}]/type"
-                                    ),
-                                    "field type mismatch".to_string(),
-                                    Some(r),
-                                    Some(b),
-                                );
-                            }
-                        }
-                        (Err(e), _) => {
-                            struct_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/structs/{sname}/fields[{i}]/type"),
-                                format!("rpc type parse error: {:#}", e),
-                                Some(rty.clone()),
-                                None,
-                            );
-                        }
-                        (_, Err(e)) => {
-                            struct_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/structs/{sname}/fields[{i}]/type"),
-                                format!("bytecode type parse error: {:#}", e),
-                                None,
-                                Some(bty.clone()),
-                            );
-                        }
-                    }
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            for (key, label) in [(params_key, "params"), (returns_key, "returns")] {
+                let types = fun.get(key).and_then(Value::as_array);
+                for (i, ty) in types.into_iter().flatten().enumerate() {
+                    sites.push((
+                        format!("modules/{module_name}/functions/{fname}/{label}[{i}]"),
+                        ty,
+                        tps,
+                    ));
                 }
             }
         }
+    }
 
-        let rpc_funcs = get_object(rpc_mod, &["exposedFunctions", "exposed_functions"])
-            .cloned()
-            .unwrap_or_default();
-        let byte_funcs = get_object(byte_mod, &["functions"])
-            .cloned()
-            .unwrap_or_default();
+    let mut out = Vec::new();
+    for (path, ty, tps) in sites {
+        let Ok(canon) = type_to_canonical_json(source, &resolve_type_param_names(ty, tps)) else {
+            continue;
+        };
+        let mut referenced = BTreeSet::new();
+        addresses(&canon, &mut referenced);
+        for address in referenced.difference(&available) {
+            out.push((path.clone(), address.clone()));
+        }
+    }
+    Some(out)
+}
 
-        let mut rpc_func_names: Vec<String> = rpc_funcs.keys().cloned().collect();
-        rpc_func_names.sort();
+/// The referenced type of a canonical `ref`, or the type itself.
+fn strip_outer_ref(canon: Value) -> Value {
+    match canon {
+        Value::Object(mut obj) if obj.get("kind").and_then(Value::as_str) == Some("ref") => {
+            obj.remove("to").unwrap_or(Value::Null)
+        }
+        other => other,
+    }
+}
 
-        for fname in &rpc_func_names {
-            let Some(rpc_fun) = rpc_funcs.get(fname) else {
-                continue;
-            };
-            let Some(byte_fun) = byte_funcs.get(fname) else {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}"),
-                    "function missing in bytecode".to_string(),
-                    Some(rpc_fun.clone()),
-                    None,
-                );
-                continue;
-            };
-            functions_compared += 1;
+/// Module structs keyed by bare name, so an extractor that encodes generic arity in the name
+/// (`Table<2>`) still lines up with one that does not (`Table`). Arity is compared separately.
+fn structs_by_bare_name(module: &Value) -> serde_json::Map<String, Value> {
+    get_object(module, &["structs"])
+        .map(|structs| {
+            structs
+                .iter()
+                .map(|(name, def)| (bare_struct_name(name).to_string(), def.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-            let rpc_vis = rpc_fun
-                .get("visibility")
-                .and_then(rpc_visibility_to_string)
-                .unwrap_or_else(|| "<unknown>".to_string());
-            let byte_vis = byte_fun
-                .get("visibility")
-                .and_then(Value::as_str)
-                .unwrap_or("<missing>")
-                .to_string();
-            if rpc_vis != byte_vis {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}/visibility"),
-                    "visibility mismatch".to_string(),
-                    rpc_fun.get("visibility").cloned(),
-                    byte_fun.get("visibility").cloned(),
-                );
-            }
+/// Collects mismatches for one comparison: counts every mismatch not matched by `ignore_paths`,
+/// keeps up to `max_mismatches` samples, and applies the `include_values`/`redact_paths` options
+/// to captured values.
+struct MismatchSink<'a> {
+    opts: &'a InterfaceCompareOptions,
+    mismatches: Vec<InterfaceCompareMismatch>,
+    total: usize,
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+    accepted: usize,
+    ignored: usize,
+    modules_missing: usize,
+    modules_extra: usize,
+    struct_mismatches: usize,
+    function_mismatches: usize,
+    structs_missing: usize,
+    structs_extra: usize,
+    functions_missing: usize,
+    functions_extra: usize,
+    /// `modules/<m>/<structs|functions>/<name>` of compared items with an `Error` finding.
+    items_with_errors: BTreeSet<String>,
+    /// Keyed by (module, function); only with `per_function_verdicts`.
+    verdicts: Option<BTreeMap<(String, String), FunctionVerdict>>,
+    /// Counted mismatches by the module segment of their path (`module_breakdown`).
+    per_module: BTreeMap<String, usize>,
+}
 
-            let rpc_entry = rpc_fun
-                .get("isEntry")
-                .and_then(Value::as_bool)
-                .unwrap_or(false);
-            let byte_entry = byte_fun
-                .get("is_entry")
-                .and_then(Value::as_bool)
-                .unwrap_or(false);
-            if rpc_entry != byte_entry {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}/is_entry"),
-                    "entry mismatch".to_string(),
-                    rpc_fun.get("isEntry").cloned(),
-                    byte_fun.get("is_entry").cloned(),
-                );
-            }
+impl<'a> MismatchSink<'a> {
+    fn new(opts: &'a InterfaceCompareOptions) -> Self {
+        Self {
+            opts,
+            mismatches: Vec::new(),
+            total: 0,
+            errors: 0,
+            warnings: 0,
+            infos: 0,
+            accepted: 0,
+            ignored: 0,
+            modules_missing: 0,
+            modules_extra: 0,
+            struct_mismatches: 0,
+            function_mismatches: 0,
+            structs_missing: 0,
+            structs_extra: 0,
+            functions_missing: 0,
+            functions_extra: 0,
+            items_with_errors: BTreeSet::new(),
+            verdicts: opts.per_function_verdicts.then(BTreeMap::new),
+            per_module: BTreeMap::new(),
+        }
+    }
 
-            let rpc_tps = rpc_fun
-                .get("typeParameters")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_tps = byte_fun
-                .get("type_params")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            if rpc_tps.len() != byte_tps.len() {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}/type_params"),
-                    format!(
-                        "type param arity mismatch (rpc={} bytecode={})",
-                        rpc_tps.len(),
-                        byte_tps.len()
-                    ),
-                    rpc_fun.get("typeParameters").cloned(),
-                    byte_fun.get("type_params").cloned(),
-                );
-            } else {
-                for (i, (rtp, btp)) in rpc_tps.iter().zip(byte_tps.iter()).enumerate() {
-                    let rpc_constraints = abilities_from_value(rtp);
-                    let byte_constraints = btp
-                        .get("constraints")
-                        .map(abilities_from_value)
-                        .unwrap_or_default();
-                    if rpc_constraints != byte_constraints {
-                        function_mismatches += 1;
-                        push_mismatch(
-                            format!("modules/{module_name}/functions/{fname}/type_params[{i}]"),
-                            "function type param constraints mismatch".to_string(),
-                            Some(serde_json::json!({"constraints": rpc_constraints})),
-                            Some(serde_json::json!({"constraints": byte_constraints})),
-                        );
-                    }
-                }
-            }
+    /// The verdict of `module::name`, starting out `Compatible`.
+    fn verdict(&mut self, module: &str, name: &str) -> Option<&mut FunctionVerdict> {
+        let verdicts = self.verdicts.as_mut()?;
+        Some(
+            verdicts
+                .entry((module.to_string(), name.to_string()))
+                .or_insert_with(|| FunctionVerdict {
+                    module: module.to_string(),
+                    name: name.to_string(),
+                    status: FunctionStatus::Compatible,
+                    changes: Vec::new(),
+                }),
+        )
+    }
 
-            let rpc_params = rpc_fun
-                .get("parameters")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_params = byte_fun
-                .get("params")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            if rpc_params.len() != byte_params.len() {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}/params"),
-                    format!(
-                        "param count mismatch (rpc={} bytecode={})",
-                        rpc_params.len(),
-                        byte_params.len()
-                    ),
-                    rpc_fun.get("parameters").cloned(),
-                    byte_fun.get("params").cloned(),
-                );
-            } else {
-                for (i, (rp, bp)) in rpc_params.iter().zip(byte_params.iter()).enumerate() {
-                    let rcanon = rpc_type_to_canonical_json(rp);
-                    let bcanon = bytecode_type_to_canonical_json(bp);
-                    match (rcanon, bcanon) {
-                        (Ok(mut r), Ok(mut b)) => {
-                            canonicalize_json_value(&mut r);
-                            canonicalize_json_value(&mut b);
-                            if r != b {
-                                function_mismatches += 1;
-                                push_mismatch(
-                                    format!("modules/{module_name}/functions/{fname}/params[{i}]"),
-                                    "param type mismatch".to_string(),
-                                    Some(r),
-                                    Some(b),
-                                );
-                            }
-                        }
-                        (Err(e), _) => {
-                            function_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/functions/{fname}/params[{i}]"),
-                                format!("rpc type parse error: {:#}", e),
-                                Some(rp.clone()),
-                                None,
-                            );
-                        }
-                        (_, Err(e)) => {
-                            function_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/functions/{fname}/params[{i}]"),
-                                format!("bytecode type parse error: {:#}", e),
-                                None,
-                                Some(bp.clone()),
-                            );
-                        }
-                    }
-                }
-            }
+    fn record_verdict(
+        &mut self,
+        kind: MismatchKind,
+        severity: MismatchSeverity,
+        path: &str,
+        reason: &str,
+    ) {
+        let mut segments = path.split('/');
+        let (Some("modules"), Some(module), Some("functions"), Some(name)) = (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+        ) else {
+            return;
+        };
+        let Some(v) = self.verdict(module, name) else {
+            return;
+        };
+        v.status = match (kind, v.status) {
+            (MismatchKind::FunctionMissing, _) => FunctionStatus::Removed,
+            (MismatchKind::FunctionExtra, _) => FunctionStatus::Added,
+            (_, FunctionStatus::Compatible) => FunctionStatus::Changed,
+            (_, status) => status,
+        };
+        v.changes.push(FunctionChange {
+            path: path.to_string(),
+            reason: reason.to_string(),
+            kind,
+            severity,
+        });
+    }
 
-            let rpc_rets = rpc_fun
-                .get("return")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            let byte_rets = byte_fun
-                .get("returns")
-                .and_then(Value::as_array)
-                .cloned()
-                .unwrap_or_default();
-            if rpc_rets.len() != byte_rets.len() {
-                function_mismatches += 1;
-                push_mismatch(
-                    format!("modules/{module_name}/functions/{fname}/returns"),
-                    format!(
-                        "return count mismatch (rpc={} bytecode={})",
-                        rpc_rets.len(),
-                        byte_rets.len()
-                    ),
-                    rpc_fun.get("return").cloned(),
-                    byte_fun.get("returns").cloned(),
-                );
-            } else {
-                for (i, (rr, br)) in rpc_rets.iter().zip(byte_rets.iter()).enumerate() {
-                    let rcanon = rpc_type_to_canonical_json(rr);
-                    let bcanon = bytecode_type_to_canonical_json(br);
-                    match (rcanon, bcanon) {
-                        (Ok(mut r), Ok(mut b)) => {
-                            canonicalize_json_value(&mut r);
-                            canonicalize_json_value(&mut b);
-                            if r != b {
-                                function_mismatches += 1;
-                                push_mismatch(
-                                    format!("modules/{module_name}/functions/{fname}/returns[{i}]"),
-                                    "return type mismatch".to_string(),
-                                    Some(r),
-                                    Some(b),
-                                );
-                            }
-                        }
-                        (Err(e), _) => {
-                            function_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/functions/{fname}/returns[{i}]"),
-                                format!("rpc type parse error: {:#}", e),
-                                Some(rr.clone()),
-                                None,
-                            );
-                        }
-                        (_, Err(e)) => {
-                            function_mismatches += 1;
-                            push_mismatch(
-                                format!("modules/{module_name}/functions/{fname}/returns[{i}]"),
-                                format!("bytecode type parse error: {:#}", e),
-                                None,
-                                Some(br.clone()),
-                            );
-                        }
-                    }
-                }
+    fn push(
+        &mut self,
+        kind: MismatchKind,
+        severity: MismatchSeverity,
+        path: String,
+        reason: String,
+        rpc: Option<Value>,
+        bytecode: Option<Value>,
+    ) -> bool {
+        // Ignored mismatches are counted as such only, so they do not show up in any other
+        // count or in totals derived from them.
+        if self
+            .opts
+            .ignore_paths
+            .iter()
+            .any(|p| path_matches_pattern(p, &path))
+        {
+            self.ignored += 1;
+            return false;
+        }
+        self.total += 1;
+        if let Some(module) = path
+            .strip_prefix("modules/")
+            .and_then(|p| p.split('/').next())
+        {
+            *self.per_module.entry(module.to_string()).or_default() += 1;
+        }
+        match kind {
+            MismatchKind::ModuleMissing => self.modules_missing += 1,
+            MismatchKind::ModuleExtra => self.modules_extra += 1,
+            _ if !counts_against_item(kind) => {}
+            _ => match item_section(&path) {
+                Some("structs") => self.struct_mismatches += 1,
+                Some("functions") => self.function_mismatches += 1,
+                _ => {}
+            },
+        }
+        match kind {
+            MismatchKind::StructMissing => self.structs_missing += 1,
+            MismatchKind::StructExtra => self.structs_extra += 1,
+            MismatchKind::FunctionMissing => self.functions_missing += 1,
+            MismatchKind::FunctionExtra => self.functions_extra += 1,
+            _ => {}
+        }
+        let accepted = self
+            .opts
+            .accept_paths
+            .iter()
+            .any(|p| path_matches_pattern(p, &path));
+        let severity = if accepted {
+            self.accepted += 1;
+            MismatchSeverity::Info
+        } else {
+            severity
+        };
+        match severity {
+            MismatchSeverity::Error => self.errors += 1,
+            MismatchSeverity::Warning => self.warnings += 1,
+            MismatchSeverity::Info => self.infos += 1,
+        }
+        if severity == MismatchSeverity::Error && counts_against_item(kind) {
+            if let Some(item) = compared_item(&path, kind) {
+                self.items_with_errors.insert(item.to_string());
             }
         }
+        if self.verdicts.is_some() {
+            self.record_verdict(kind, severity, &path, &reason);
+        }
+        if self.mismatches.len() >= self.opts.max_mismatches {
+            return false;
+        }
+        let (rpc, bytecode) = if !self.opts.include_values {
+            (None, None)
+        } else if self
+            .opts
+            .redact_paths
+            .iter()
+            .any(|p| path_matches_pattern(p, &path))
+        {
+            (rpc.map(|_| redacted()), bytecode.map(|_| redacted()))
+        } else {
+            // Captured values keep the input's key order otherwise (`preserve_order`).
+            let sorted = |mut v: Value| {
+                canonicalize_json_value(&mut v);
+                v
+            };
+            (rpc.map(sorted), bytecode.map(sorted))
+        };
+        self.mismatches.push(InterfaceCompareMismatch {
+            path,
+            reason,
+            kind,
+            severity,
+            accepted,
+            rpc,
+            bytecode,
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        });
+        true
     }
 
-    (
-        InterfaceCompareSummary {
-            modules_compared,
-            modules_missing_in_bytecode: modules_missing_in_bytecode.len(),
-            modules_extra_in_bytecode: modules_extra_in_bytecode.len(),
-            structs_compared,
-            struct_mismatches,
-            functions_compared,
-            function_mismatches,
-            mismatches_total: mismatch_count_total,
-        },
-        mismatches,
+    /// Attach both canonical types to the sample just retained by `push`.
+    fn attach_canonical(&mut self, rpc: &Value, bytecode: &Value) {
+        if !self.opts.include_canonical {
+            return;
+        }
+        let Some(last) = self.mismatches.last_mut() else {
+            return;
+        };
+        let redact = self
+            .opts
+            .redact_paths
+            .iter()
+            .any(|p| path_matches_pattern(p, &last.path));
+        let (rpc, bytecode) = if redact {
+            (redacted(), redacted())
+        } else {
+            (rpc.clone(), bytecode.clone())
+        };
+        last.rpc_canonical = Some(rpc);
+        last.bytecode_canonical = Some(bytecode);
+    }
+}
+
+/// The item section (`structs`, `functions`, ...) of a `modules/<m>/<section>/...` path.
+fn item_section(path: &str) -> Option<&str> {
+    let (_, rest) = path.strip_prefix("modules/")?.split_once('/')?;
+    rest.split('/').next()
+}
+
+/// `modules/<m>/<structs|functions>/<name>` of a finding inside an item both sides declare.
+fn compared_item(path: &str, kind: MismatchKind) -> Option<&str> {
+    if matches!(
+        kind,
+        MismatchKind::StructMissing
+            | MismatchKind::StructExtra
+            | MismatchKind::FunctionMissing
+            | MismatchKind::FunctionExtra
+    ) || !matches!(item_section(path), Some("structs" | "functions"))
+    {
+        return None;
+    }
+    let end = path
+        .match_indices('/')
+        .nth(3)
+        .map_or(path.len(), |(i, _)| i);
+    Some(&path[..end])
+}
+
+/// Signals derived from a difference that is reported on its own as well, e.g. `copy` lost on
+/// top of the abilities diff. They show up in the severity counts only, so one change does not
+/// count its item twice.
+fn is_derived_signal(kind: MismatchKind) -> bool {
+    matches!(
+        kind,
+        MismatchKind::HotPotatoChanged
+            | MismatchKind::CopyabilityChanged
+            | MismatchKind::DynamicFieldStoreLost
+            | MismatchKind::PublicTransferGained
+            | MismatchKind::PublicTransferLost
+            | MismatchKind::HotPotatoReturnChanged
+            | MismatchKind::CallabilityChanged
+            | MismatchKind::PassingModeChanged
     )
 }
 
-pub fn bytecode_module_check(
-    normalized_module_names: &[String],
-    bcs_module_names: &[String],
-) -> BytecodeModuleCheck {
-    let normalized_set: HashSet<&str> =
-        normalized_module_names.iter().map(|s| s.as_str()).collect();
-    let bcs_set: HashSet<&str> = bcs_module_names.iter().map(|s| s.as_str()).collect();
+/// Whether a mismatch under a struct or function counts that item as mismatched. Derived
+/// signals and naming or extraction diagnostics are reported without doing so.
+fn counts_against_item(kind: MismatchKind) -> bool {
+    !is_derived_signal(kind)
+        && !matches!(
+            kind,
+            MismatchKind::NameCasing
+                | MismatchKind::PossibleCollision
+                | MismatchKind::UnavailableTypeReference
+                | MismatchKind::InitSignatureInvalid
+        )
+}
 
-    let mut missing_in_bcs: Vec<String> = normalized_module_names
-        .iter()
-        .filter(|name| !bcs_set.contains(name.as_str()))
-        .cloned()
-        .collect();
-    missing_in_bcs.sort();
+fn field_name(field: &Value) -> &str {
+    field.get("name").and_then(Value::as_str).unwrap_or("")
+}
 
-    let mut extra_in_bcs: Vec<String> = bcs_module_names
+fn field_name_set(fields: &[Value]) -> BTreeSet<&str> {
+    fields
         .iter()
-        .filter(|name| !normalized_set.contains(name.as_str()))
-        .cloned()
-        .collect();
-    extra_in_bcs.sort();
+        .filter_map(|f| f.get("name").and_then(Value::as_str))
+        .collect()
+}
 
-    BytecodeModuleCheck {
+/// Field names that occur more than once, in first-repeat order.
+fn duplicate_field_names(fields: &[Value]) -> Vec<&str> {
+    let mut seen = BTreeSet::new();
+    let mut dups = Vec::new();
+    for name in fields
+        .iter()
+        .filter_map(|f| f.get("name").and_then(Value::as_str))
+    {
+        if !seen.insert(name) && !dups.contains(&name) {
+            dups.push(name);
+        }
+    }
+    dups
+}
+
+/// Whether a function is marked deprecated, via an `is_deprecated`/`isDeprecated` flag or a
+/// `deprecated` entry in its `attributes` (a bare name or `{"name": ...}`).
+fn is_deprecated(fun: &Value) -> bool {
+    if let Some(flag) = fun
+        .get("is_deprecated")
+        .or_else(|| fun.get("isDeprecated"))
+        .and_then(Value::as_bool)
+    {
+        return flag;
+    }
+    fun.get("attributes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .any(|a| {
+            a.as_str()
+                .or_else(|| a.get("name").and_then(Value::as_str))
+                .is_some_and(|name| name.eq_ignore_ascii_case("deprecated"))
+        })
+}
+
+/// Whether any parameter is (a reference to) `0x2::tx_context::TxContext`.
+fn takes_tx_context(source: SchemaSource, params: &[Value]) -> bool {
+    params.iter().any(|p| {
+        let Ok(mut canon) = type_to_canonical_json(source, p) else {
+            return false;
+        };
+        while canon.get("kind").and_then(Value::as_str) == Some("ref") {
+            canon = canon.get("to").cloned().unwrap_or(Value::Null);
+        }
+        is_canonical_datatype(&canon, "0x2", "tx_context", "TxContext")
+    })
+}
+
+/// Whether a canonical type may be an entry function parameter: pure values, vectors and
+/// datatypes (objects, `String`, `ID`, `Option`) of such values, type params, and references
+/// only at the top level and only to datatypes or type params (objects, `TxContext`).
+fn is_valid_entry_param(canon: &Value, top_level: bool) -> bool {
+    match canon.get("kind").and_then(Value::as_str) {
+        Some("bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "u256" | "address") => true,
+        Some("type_param") => true,
+        Some("vector") => canon
+            .get("type")
+            .is_some_and(|inner| is_valid_entry_param(inner, false)),
+        Some("datatype") => canon
+            .get("type_args")
+            .and_then(Value::as_array)
+            .is_none_or(|args| args.iter().all(|a| is_valid_entry_param(a, false))),
+        Some("ref") => {
+            top_level
+                && canon.get("to").is_some_and(|to| {
+                    matches!(
+                        to.get("kind").and_then(Value::as_str),
+                        Some("datatype" | "type_param")
+                    )
+                })
+        }
+        _ => false,
+    }
+}
+
+/// `module::Name` and declared abilities of a canonical datatype node, when it resolves to a
+/// struct in `decls` (an interface `modules` object) at the same address.
+fn local_struct_abilities(canon: &Value, decls: &Value) -> Option<(String, Vec<String>)> {
+    if canon.get("kind").and_then(Value::as_str) != Some("datatype") {
+        return None;
+    }
+    let module = canon.get("module").and_then(Value::as_str).unwrap_or("");
+    let name = canon.get("name").and_then(Value::as_str).unwrap_or("");
+    if let (Some(Value::String(a)), Some(Value::String(b))) = (
+        decls.get(module).and_then(|m| m.get("address")),
+        canon.get("address"),
+    ) {
+        if normalize_address_str(a).ok() != normalize_address_str(b).ok() {
+            return None;
+        }
+    }
+    let decl = decls
+        .get(module)?
+        .get("structs")?
+        .as_object()?
+        .iter()
+        .find(|(k, _)| bare_struct_name(k) == name)
+        .map(|(_, v)| v)?;
+    let abilities = decl
+        .get("abilities")
+        .map(abilities_from_value)
+        .unwrap_or_default();
+    Some((format!("{module}::{name}"), abilities))
+}
+
+/// `module::Name` of every datatype in a function's param and return types that is declared in
+/// `decls` (an interface `modules` object) with neither `store` nor `key`.
+fn internal_types_referenced(source: SchemaSource, function: &Value, decls: &Value) -> Vec<String> {
+    fn collect(v: &Value, decls: &Value, out: &mut BTreeSet<String>) {
+        match v {
+            Value::Object(obj) => {
+                if let Some((name, abilities)) = local_struct_abilities(v, decls) {
+                    if !abilities.iter().any(|a| a == "store" || a == "key") {
+                        out.insert(name);
+                    }
+                }
+                obj.values().for_each(|v| collect(v, decls, out));
+            }
+            Value::Array(items) => items.iter().for_each(|v| collect(v, decls, out)),
+            _ => {}
+        }
+    }
+    let (params_key, returns_key) = match source {
+        SchemaSource::Rpc => ("parameters", "return"),
+        SchemaSource::Bytecode => ("params", "returns"),
+    };
+    let mut out = BTreeSet::new();
+    for key in [params_key, returns_key] {
+        for t in function
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Ok(canon) = type_to_canonical_json(source, t) {
+                collect(&canon, decls, &mut out);
+            }
+        }
+    }
+    out.into_iter().collect()
+}
+
+/// `module::Name` of each hot potato (a struct in `decls` with no abilities) a function returns by
+/// value. Each one is an obligation the caller must discharge later in the same transaction.
+fn hot_potato_returns(source: SchemaSource, returns: &[Value], decls: &Value) -> BTreeSet<String> {
+    returns
+        .iter()
+        .filter_map(|t| type_to_canonical_json(source, t).ok())
+        .filter_map(|canon| local_struct_abilities(&canon, decls))
+        .filter(|(_, abilities)| abilities.is_empty())
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Type-param indices referenced anywhere in a function's param and return types, or `None`
+/// when a type does not canonicalize (so usage is unknown).
+fn used_type_params(source: SchemaSource, function: &Value) -> Option<BTreeSet<u64>> {
+    fn collect(v: &Value, used: &mut BTreeSet<u64>) {
+        match v {
+            Value::Object(obj) => {
+                if obj.get("kind").and_then(Value::as_str) == Some("type_param") {
+                    if let Some(i) = obj.get("index").and_then(Value::as_u64) {
+                        used.insert(i);
+                    }
+                }
+                obj.values().for_each(|v| collect(v, used));
+            }
+            Value::Array(items) => items.iter().for_each(|v| collect(v, used)),
+            _ => {}
+        }
+    }
+    let (params_key, returns_key) = match source {
+        SchemaSource::Rpc => ("parameters", "return"),
+        SchemaSource::Bytecode => ("params", "returns"),
+    };
+    let mut used = BTreeSet::new();
+    for key in [params_key, returns_key] {
+        for t in function
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            collect(&type_to_canonical_json(source, t).ok()?, &mut used);
+        }
+    }
+    Some(used)
+}
+
+fn ability_name(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Copy => "copy",
+        Ability::Drop => "drop",
+        Ability::Store => "store",
+        Ability::Key => "key",
+    }
+}
+
+/// Whether the RPC side would list a bytecode function. RPC never reports private non-entry
+/// functions, and some endpoints report only `public` ones, which is assumed when every RPC
+/// entry in the module is public.
+pub(crate) fn rpc_would_expose(byte_fun: &Value, rpc_public_only: bool) -> bool {
+    let visibility = byte_fun
+        .get("visibility")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let is_entry = byte_fun
+        .get("is_entry")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if rpc_public_only {
+        visibility == "public"
+    } else {
+        visibility != "private" || is_entry
+    }
+}
+
+/// Differences between two canonical types, each at the most specific sub-path where they
+/// diverge (e.g. `.../type/type_args[1]/type/type_args[0]`). Empty when the types are equal.
+fn type_diffs(rpc: &Value, bytecode: &Value, path: &str) -> Vec<(String, Value, Value)> {
+    if rpc == bytecode {
+        return Vec::new();
+    }
+    let whole = || vec![(path.to_string(), rpc.clone(), bytecode.clone())];
+    let kind = rpc.get("kind").and_then(Value::as_str);
+    if kind != bytecode.get("kind").and_then(Value::as_str) {
+        return whole();
+    }
+    let nested = |key: &str, sub: String| match (rpc.get(key), bytecode.get(key)) {
+        (Some(r), Some(b)) => type_diffs(r, b, &sub),
+        _ => whole(),
+    };
+    match kind {
+        Some("vector") => nested("type", format!("{path}/type")),
+        Some("ref") if rpc.get("mutable") == bytecode.get("mutable") => {
+            nested("to", format!("{path}/to"))
+        }
+        Some("datatype")
+            if ["address", "module", "name"]
+                .iter()
+                .all(|k| rpc.get(*k) == bytecode.get(*k)) =>
+        {
+            let empty = Vec::new();
+            let rpc_args = rpc
+                .get("type_args")
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            let byte_args = bytecode
+                .get("type_args")
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            if rpc_args.len() != byte_args.len() {
+                return whole();
+            }
+            rpc_args
+                .iter()
+                .zip(byte_args)
+                .enumerate()
+                .flat_map(|(j, (r, b))| type_diffs(r, b, &format!("{path}/type_args[{j}]")))
+                .collect()
+        }
+        _ => whole(),
+    }
+}
+
+/// A module's declared type params (structs plus exposed functions) are flagged as `Info` when
+/// one side has more than `TYPE_PARAM_DISPARITY_RATIO` times the other's, and at least
+/// `TYPE_PARAM_DISPARITY_MIN`.
+const TYPE_PARAM_DISPARITY_RATIO: usize = 4;
+const TYPE_PARAM_DISPARITY_MIN: usize = 10;
+
+/// Some extractors report a multi-value return as one tuple type (`{"Tuple": [..]}` or
+/// `{"kind": "tuple", "types": [..]}`) rather than a list; unwrap it on either side.
+fn flatten_return_tuple(returns: Vec<Value>) -> Vec<Value> {
+    if let [only] = returns.as_slice() {
+        let elements = only.get("Tuple").or_else(|| {
+            (only.get("kind").and_then(Value::as_str) == Some("tuple"))
+                .then(|| only.get("types"))
+                .flatten()
+        });
+        if let Some(elements) = elements.and_then(Value::as_array) {
+            return elements.clone();
+        }
+    }
+    returns
+}
+
+/// Re-key a `modules` map by `<normalized address>::<name>` for modules that carry an address.
+fn modules_keyed_by_address(
+    modules: &serde_json::Map<String, Value>,
+) -> serde_json::Map<String, Value> {
+    modules
+        .iter()
+        .map(|(key, module)| {
+            let qualified = module
+                .get("address")
+                .and_then(Value::as_str)
+                .and_then(|addr| normalize_address_str(addr).ok())
+                .map(|addr| {
+                    let name = module
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or_else(|| key.rsplit("::").next().unwrap_or(key));
+                    format!("{addr}::{name}")
+                });
+            (qualified.unwrap_or_else(|| key.clone()), module.clone())
+        })
+        .collect()
+}
+
+/// Pair each RPC-only name with the single bytecode-only name equal to it ignoring ASCII case.
+fn casing_pairs<'a>(rpc_only: &[&'a str], byte_only: &[&'a str]) -> Vec<(&'a str, &'a str)> {
+    rpc_only
+        .iter()
+        .filter_map(|r| {
+            let mut candidates = byte_only.iter().filter(|b| b.eq_ignore_ascii_case(r));
+            match (candidates.next(), candidates.next()) {
+                (Some(b), None) => Some((*r, *b)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn redacted() -> Value {
+    Value::String("<redacted>".to_string())
+}
+
+/// Compare an RPC interface against a bytecode interface. Output is deterministic: modules,
+/// structs and functions are walked in sorted name order and captured values have
+/// their keys sorted, so the same logical input yields byte-identical mismatches whatever its
+/// JSON key order (with or without serde_json's `preserve_order`).
+pub fn compare_interface_rpc_vs_bytecode(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+) -> (InterfaceCompareSummary, Vec<InterfaceCompareMismatch>) {
+    let (summary, mismatches, _) = compare_interface_impl(
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+        opts,
+        SchemaSource::Rpc,
+        None,
+        &HashSet::new(),
+    );
+    (summary, mismatches)
+}
+
+/// Like `compare_interface_rpc_vs_bytecode`, also returning one `FunctionVerdict` per function
+/// when `opts.per_function_verdicts` is set. Verdicts see every finding, including those past
+/// `max_mismatches`.
+pub fn compare_interface_rpc_vs_bytecode_with_verdicts(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+) -> (
+    InterfaceCompareSummary,
+    Vec<InterfaceCompareMismatch>,
+    Option<Vec<FunctionVerdict>>,
+) {
+    compare_interface_impl(
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+        opts,
+        SchemaSource::Rpc,
+        None,
+        &HashSet::new(),
+    )
+}
+
+/// Like `compare_interface_rpc_vs_bytecode`, additionally appending one line per visited
+/// module, struct and function to `trace` (e.g. `function m::f: mismatch`), in visit order.
+pub fn compare_interface_rpc_vs_bytecode_traced(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+    trace: &mut Vec<String>,
+) -> (InterfaceCompareSummary, Vec<InterfaceCompareMismatch>) {
+    let (summary, mismatches, _) = compare_interface_impl(
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+        opts,
+        SchemaSource::Rpc,
+        Some(trace),
+        &HashSet::new(),
+    );
+    (summary, mismatches)
+}
+
+/// Compare with bytecode as the reference and RPC as the candidate, e.g. to check that a node
+/// serves the data a package was built from. Runs the regular comparison with bytecode as the
+/// old side and mirrors it: missing and extra kinds (and their reasons) swap, as do added/removed
+/// constraints. Every bytecode function RPC does not list is reported (`Info` where RPC never
+/// lists it), whatever `report_extra_functions` says. Paths are unchanged, the `rpc`/`bytecode`
+/// value slots still hold the value from that source, and detail reasons that name both sides
+/// (`rpc=... bytecode=...`) are kept verbatim.
+pub fn compare_interface_bytecode_vs_rpc(
+    package_id: &str,
+    bytecode_interface_value: &Value,
+    rpc_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+) -> (BytecodeVsRpcSummary, Vec<InterfaceCompareMismatch>) {
+    let (summary, mismatches, _) = compare_interface_impl(
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+        InterfaceCompareOptions {
+            report_extra_functions: true,
+            report_private_extra_functions: true,
+            ..opts
+        },
+        SchemaSource::Bytecode,
+        None,
+        &HashSet::new(),
+    );
+    let mismatches = mismatches.into_iter().map(mirror_mismatch).collect();
+    (summary.into(), mismatches)
+}
+
+fn mirror_mismatch(mut m: InterfaceCompareMismatch) -> InterfaceCompareMismatch {
+    m.kind = match m.kind {
+        MismatchKind::ModuleMissing => MismatchKind::ModuleExtra,
+        MismatchKind::ModuleExtra => MismatchKind::ModuleMissing,
+        MismatchKind::StructMissing => MismatchKind::StructExtra,
+        MismatchKind::StructExtra => MismatchKind::StructMissing,
+        MismatchKind::FunctionMissing => MismatchKind::FunctionExtra,
+        MismatchKind::FunctionExtra => MismatchKind::FunctionMissing,
+        MismatchKind::ConstantMissing => MismatchKind::ConstantExtra,
+        MismatchKind::ConstantExtra => MismatchKind::ConstantMissing,
+        MismatchKind::FieldMissing => MismatchKind::FieldExtra,
+        MismatchKind::FieldExtra => MismatchKind::FieldMissing,
+        MismatchKind::ConstraintAdded => MismatchKind::ConstraintRemoved,
+        MismatchKind::ConstraintRemoved => MismatchKind::ConstraintAdded,
+        kind => kind,
+    };
+    // "struct missing in bytecode" <-> "extra struct in rpc".
+    if let Some(item) = m.reason.strip_suffix(" missing in bytecode") {
+        m.reason = format!("extra {item} in rpc");
+    } else if let Some(item) = m
+        .reason
+        .strip_prefix("extra ")
+        .and_then(|r| r.strip_suffix(" in bytecode"))
+    {
+        m.reason = format!("{item} missing in rpc");
+    } else if m.reason.contains(" constraint added: ") {
+        m.reason = m
+            .reason
+            .replace(" constraint added: ", " constraint removed: ");
+    } else if m.reason.contains(" constraint removed: ") {
+        m.reason = m
+            .reason
+            .replace(" constraint removed: ", " constraint added: ");
+    }
+    m
+}
+
+/// Trace outcome for an item, from the sink total before and after visiting it.
+fn trace_outcome(before: usize, after: usize) -> &'static str {
+    if after > before {
+        "mismatch"
+    } else {
+        "match"
+    }
+}
+
+/// Append a line to the walk's trace. Formatting only happens when a trace was requested.
+macro_rules! trace {
+    ($walk:expr, $($arg:tt)*) => {
+        if let Some(t) = $walk.trace.as_deref_mut() {
+            t.push(format!($($arg)*));
+        }
+    };
+}
+
+fn compare_interface_impl(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+    reference: SchemaSource,
+    trace: Option<&mut Vec<String>>,
+    unchanged: &HashSet<String>,
+) -> (
+    InterfaceCompareSummary,
+    Vec<InterfaceCompareMismatch>,
+    Option<Vec<FunctionVerdict>>,
+) {
+    let mut walk = Walk::new(
+        &opts,
+        trace,
+        reference,
+        unchanged,
+        rpc_interface_value,
+        bytecode_interface_value,
+    );
+    let complete = run_walk(
+        &mut walk,
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+    );
+    walk.finish(complete)
+}
+
+/// Run `walk` over both interfaces. Returns whether it visited every module it meant to.
+fn run_walk(
+    walk: &mut Walk,
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+) -> bool {
+    let opts = walk.opts;
+    if opts.check_package_version
+        && walk.package_versions_differ(rpc_interface_value, bytecode_interface_value)
+    {
+        return false;
+    }
+
+    let empty_modules = serde_json::Map::new();
+    let mut rpc_modules = rpc_interface_value
+        .get("modules")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty_modules);
+    let mut byte_modules = bytecode_interface_value
+        .get("modules")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty_modules);
+    let rpc_by_address;
+    let byte_by_address;
+    if opts.key_modules_by_address {
+        rpc_by_address = modules_keyed_by_address(rpc_modules);
+        byte_by_address = modules_keyed_by_address(byte_modules);
+        rpc_modules = &rpc_by_address;
+        byte_modules = &byte_by_address;
+    }
+
+    let intersection = walk.compare_module_sets(rpc_modules, byte_modules);
+
+    // A type from a package that is neither this one nor a declared dependency cannot be linked.
+    for (source, interface_value, modules) in [
+        (SchemaSource::Rpc, rpc_interface_value, rpc_modules),
+        (
+            SchemaSource::Bytecode,
+            bytecode_interface_value,
+            byte_modules,
+        ),
+    ] {
+        walk.report_dangling_references(source, interface_value, modules, package_id);
+    }
+
+    let mut complete = true;
+    for (module_name, byte_module_name) in intersection {
+        if walk.unchanged.contains(module_name) {
+            trace!(walk, "module {module_name}: skip (unchanged)");
+            continue;
+        }
+        if opts
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            trace!(walk, "module {module_name}: skip (cancelled)");
+            complete = false;
+            break;
+        }
+        walk.compare_module(
+            (module_name, byte_module_name),
+            rpc_modules.get(module_name).unwrap_or(&Value::Null),
+            byte_modules.get(byte_module_name).unwrap_or(&Value::Null),
+        );
+        #[cfg(test)]
+        if let Some(hook) = walk.after_module {
+            hook(module_name);
+        }
+    }
+    complete
+}
+
+/// A module present on both sides, with its structs keyed by bare name.
+struct ModulePair<'v> {
+    name: &'v str,
+    rpc: &'v Value,
+    bytecode: &'v Value,
+    rpc_structs: serde_json::Map<String, Value>,
+    byte_structs: serde_json::Map<String, Value>,
+}
+
+/// Where a compared type sits, which decides the mismatch kind and the item it counts against.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TypeSite {
+    Field,
+    Param,
+    /// A parameter of `init`, which runs once at publish and consumes the one-time witness.
+    InitParam,
+    Return,
+}
+
+impl TypeSite {
+    fn mismatch(self) -> (MismatchKind, &'static str) {
+        match self {
+            TypeSite::Field => (MismatchKind::FieldTypeMismatch, "field type mismatch"),
+            TypeSite::Param => (MismatchKind::ParamTypeMismatch, "param type mismatch"),
+            TypeSite::InitParam => (MismatchKind::InitSignatureChanged, "init signature changed"),
+            TypeSite::Return => (MismatchKind::ReturnTypeMismatch, "return type mismatch"),
+        }
+    }
+}
+
+/// The elements of an array-valued key, or none.
+fn array_at<'v>(value: &'v Value, key: &str) -> &'v [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn bool_at(value: &Value, key: &str) -> bool {
+    value.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// State of one comparison: the options, the sink, the item counters, and both sides' module
+/// declarations for resolving nested datatypes.
+struct Walk<'a> {
+    opts: &'a InterfaceCompareOptions,
+    sink: MismatchSink<'a>,
+    trace: Option<&'a mut Vec<String>>,
+    /// The old side of directional signals (abilities gained or lost).
+    reference: SchemaSource,
+    /// Modules whose findings the caller already has (`compare_interface_incremental`).
+    unchanged: &'a HashSet<String>,
+    rpc_decls: &'a Value,
+    byte_decls: &'a Value,
+    parse_error_severity: MismatchSeverity,
+    modules_compared: usize,
+    structs_compared: usize,
+    functions_compared: usize,
+    type_sites_compared: usize,
+    type_sites_mismatched: usize,
+    /// (structs, functions) compared per compared module.
+    module_items: BTreeMap<String, (usize, usize)>,
+    /// Called with each module's name once it has been compared.
+    #[cfg(test)]
+    after_module: Option<&'a dyn Fn(&str)>,
+}
+
+impl<'a> Walk<'a> {
+    fn new(
+        opts: &'a InterfaceCompareOptions,
+        trace: Option<&'a mut Vec<String>>,
+        reference: SchemaSource,
+        unchanged: &'a HashSet<String>,
+        rpc_interface_value: &'a Value,
+        bytecode_interface_value: &'a Value,
+    ) -> Self {
+        Self {
+            opts,
+            sink: MismatchSink::new(opts),
+            trace,
+            reference,
+            unchanged,
+            // Declarations as the extractors keyed them, for resolving nested datatypes.
+            rpc_decls: rpc_interface_value.get("modules").unwrap_or(&Value::Null),
+            byte_decls: bytecode_interface_value
+                .get("modules")
+                .unwrap_or(&Value::Null),
+            parse_error_severity: match opts.unknown_type_policy {
+                UnknownTypePolicy::Warn => MismatchSeverity::Warning,
+                _ => MismatchSeverity::Error,
+            },
+            modules_compared: 0,
+            structs_compared: 0,
+            functions_compared: 0,
+            type_sites_compared: 0,
+            type_sites_mismatched: 0,
+            module_items: BTreeMap::new(),
+            #[cfg(test)]
+            after_module: None,
+        }
+    }
+
+    /// `(old, new)` by the walk's reference side.
+    fn oriented<T>(&self, rpc: T, bytecode: T) -> (T, T) {
+        match self.reference {
+            SchemaSource::Rpc => (rpc, bytecode),
+            SchemaSource::Bytecode => (bytecode, rpc),
+        }
+    }
+
+    fn finish(
+        self,
+        complete: bool,
+    ) -> (
+        InterfaceCompareSummary,
+        Vec<InterfaceCompareMismatch>,
+        Option<Vec<FunctionVerdict>>,
+    ) {
+        let sink = self.sink;
+        let module_set_mismatches = sink.modules_missing + sink.modules_extra;
+        let items_with_errors = |section: &str| {
+            sink.items_with_errors
+                .iter()
+                .filter(|item| item_section(item) == Some(section))
+                .count()
+        };
+        let structs_with_errors = items_with_errors("structs");
+        let functions_with_errors = items_with_errors("functions");
+        (
+            InterfaceCompareSummary {
+                modules_compared: self.modules_compared,
+                modules_missing_in_bytecode: sink.modules_missing,
+                modules_extra_in_bytecode: sink.modules_extra,
+                structs_compared: self.structs_compared,
+                struct_mismatches: sink.struct_mismatches,
+                functions_compared: self.functions_compared,
+                function_mismatches: sink.function_mismatches,
+                mismatches_total: sink.total,
+                module_set_mismatches,
+                item_mismatches: sink.total - module_set_mismatches,
+                error_mismatches: sink.errors,
+                warning_mismatches: sink.warnings,
+                info_mismatches: sink.infos,
+                accepted_mismatches: sink.accepted,
+                ignored_mismatches: sink.ignored,
+                complete,
+                type_sites_compared: self.type_sites_compared,
+                type_sites_mismatched: self.type_sites_mismatched,
+                structs_missing_in_bytecode: sink.structs_missing,
+                structs_extra_in_bytecode: sink.structs_extra,
+                functions_missing_in_bytecode: sink.functions_missing,
+                functions_extra_in_bytecode: sink.functions_extra,
+                structs_with_errors,
+                functions_with_errors,
+            },
+            sink.mismatches,
+            sink.verdicts.map(|v| v.into_values().collect()),
+        )
+    }
+
+    /// Whether `path` is on the way to, or inside, `focus_path`.
+    fn in_focus(&self, path: &str) -> bool {
+        self.opts
+            .focus_path
+            .as_deref()
+            .is_none_or(|f| path_matches_pattern(f, path) || path_matches_pattern(path, f))
+    }
+
+    /// Report differing top-level versions; the walk is skipped when they do.
+    fn package_versions_differ(&mut self, rpc: &Value, bytecode: &Value) -> bool {
+        // RPC reports versions as strings, local builds as numbers.
+        let version = |v: &Value| match v.get("version") {
+            Some(Value::String(s)) => Some(s.trim().to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        let (Some(rpc_version), Some(byte_version)) = (version(rpc), version(bytecode)) else {
+            return false;
+        };
+        if rpc_version == byte_version {
+            return false;
+        }
+        trace!(self, "package version: mismatch (skipping walk)");
+        self.sink.push(
+            MismatchKind::PackageVersionMismatch,
+            MismatchSeverity::Error,
+            "version".to_string(),
+            format!(
+                "package version mismatch (rpc={rpc_version} bytecode={byte_version}); \
+                 align versions before trusting this comparison"
+            ),
+            Some(Value::String(rpc_version)),
+            Some(Value::String(byte_version)),
+        );
+        true
+    }
+
+    /// Report modules found on one side only and module names that disagree with their key.
+    /// Returns the (RPC name, bytecode name) pairs to compare in depth, sorted by RPC name.
+    fn compare_module_sets<'m>(
+        &mut self,
+        rpc_modules: &'m serde_json::Map<String, Value>,
+        byte_modules: &'m serde_json::Map<String, Value>,
+    ) -> Vec<(&'m str, &'m str)> {
+        let mut rpc_module_names: Vec<&String> = rpc_modules
+            .keys()
+            .filter(|m| self.in_focus(&format!("modules/{m}")))
+            .collect();
+        rpc_module_names.sort();
+        let mut byte_module_names: Vec<&String> = byte_modules
+            .keys()
+            .filter(|m| self.in_focus(&format!("modules/{m}")))
+            .collect();
+        byte_module_names.sort();
+
+        let rpc_set: HashSet<&str> = rpc_module_names.iter().map(|s| s.as_str()).collect();
+        let byte_set: HashSet<&str> = byte_module_names.iter().map(|s| s.as_str()).collect();
+
+        let mut missing_in_bytecode: Vec<&str> = rpc_module_names
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|m| !byte_set.contains(m))
+            .collect();
+        let mut extra_in_bytecode: Vec<&str> = byte_module_names
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|m| !rpc_set.contains(m))
+            .collect();
+        let module_casing = if self.opts.case_insensitive_names {
+            casing_pairs(&missing_in_bytecode, &extra_in_bytecode)
+        } else {
+            Vec::new()
+        };
+        missing_in_bytecode.retain(|m| !module_casing.iter().any(|(r, _)| r == m));
+        extra_in_bytecode.retain(|m| !module_casing.iter().any(|(_, b)| b == m));
+
+        for m in &missing_in_bytecode {
+            trace!(self, "module {m}: mismatch (missing in bytecode)");
+            self.sink.push(
+                MismatchKind::ModuleMissing,
+                MismatchSeverity::Error,
+                format!("modules/{m}"),
+                "module missing in bytecode".to_string(),
+                rpc_modules.get(*m).cloned(),
+                None,
+            );
+        }
+        for m in &extra_in_bytecode {
+            trace!(self, "module {m}: mismatch (extra in bytecode)");
+            self.sink.push(
+                MismatchKind::ModuleExtra,
+                MismatchSeverity::Error,
+                format!("modules/{m}"),
+                "extra module in bytecode".to_string(),
+                None,
+                byte_modules.get(*m).cloned(),
+            );
+        }
+
+        // A self-reported `name` that disagrees with the map key means the document was
+        // assembled wrongly upstream. Address-qualified keys are checked by their name part.
+        for m in &byte_module_names {
+            let Some(name) = byte_modules
+                .get(m.as_str())
+                .and_then(|v| v.get("name"))
+                .and_then(Value::as_str)
+            else {
+                continue;
+            };
+            let key_name = if self.opts.key_modules_by_address {
+                m.rsplit("::").next().unwrap_or(m.as_str())
+            } else {
+                m.as_str()
+            };
+            if name != key_name {
+                self.sink.push(
+                    MismatchKind::ModuleNameMismatch,
+                    MismatchSeverity::Error,
+                    format!("modules/{m}/name"),
+                    "module name/key mismatch".to_string(),
+                    None,
+                    Some(Value::String(name.to_string())),
+                );
+            }
+        }
+
+        let mut intersection: Vec<(&str, &str)> = rpc_module_names
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|m| byte_set.contains(*m))
+            .map(|m| (m, m))
+            .chain(module_casing)
+            .collect();
+        intersection.sort();
+        intersection
+    }
+
+    fn report_dangling_references(
+        &mut self,
+        source: SchemaSource,
+        interface_value: &Value,
+        modules: &serde_json::Map<String, Value>,
+        package_id: &str,
+    ) {
+        let dangling = dangling_type_references(source, interface_value, modules, package_id);
+        for (path, address) in dangling.into_iter().flatten() {
+            let module = path
+                .strip_prefix("modules/")
+                .and_then(|p| p.split('/').next());
+            if !self.in_focus(&path) || module.is_some_and(|m| self.unchanged.contains(m)) {
+                continue;
+            }
+            let address = Some(Value::String(address));
+            let (rpc, bytecode) = match source {
+                SchemaSource::Rpc => (address, None),
+                SchemaSource::Bytecode => (None, address),
+            };
+            self.sink.push(
+                MismatchKind::UnavailableTypeReference,
+                MismatchSeverity::Error,
+                path,
+                "type references unavailable package".to_string(),
+                rpc,
+                bytecode,
+            );
+        }
+    }
+
+    /// Compare one module present on both sides, given its (RPC, bytecode) names.
+    fn compare_module(&mut self, names: (&str, &str), rpc_mod: &Value, byte_mod: &Value) {
+        let (module_name, byte_module_name) = names;
+        self.modules_compared += 1;
+        let module_before = self.sink.total;
+        if module_name != byte_module_name {
+            self.sink.push(
+                MismatchKind::NameCasing,
+                MismatchSeverity::Warning,
+                format!("modules/{module_name}"),
+                format!("name casing differs: {module_name} vs {byte_module_name}"),
+                None,
+                None,
+            );
+        }
+        let module = ModulePair {
+            name: module_name,
+            rpc: rpc_mod,
+            bytecode: byte_mod,
+            rpc_structs: structs_by_bare_name(rpc_mod),
+            byte_structs: structs_by_bare_name(byte_mod),
+        };
+        let items_before = (self.structs_compared, self.functions_compared);
+        self.compare_structs(&module);
+        if self.opts.compare_imports && self.in_focus(&format!("modules/{module_name}/imports")) {
+            self.compare_imports(&module);
+        }
+        self.compare_functions(&module);
+        self.module_items.insert(
+            module_name.to_string(),
+            (
+                self.structs_compared - items_before.0,
+                self.functions_compared - items_before.1,
+            ),
+        );
+        trace!(
+            self,
+            "module {module_name}: {}",
+            trace_outcome(module_before, self.sink.total)
+        );
+    }
+
+    fn compare_structs(&mut self, module: &ModulePair) {
+        let module_name = module.name;
+        let (rpc_structs, byte_structs) = (&module.rpc_structs, &module.byte_structs);
+        let struct_in_focus =
+            |s: &&String| self.in_focus(&format!("modules/{module_name}/structs/{s}"));
+        let mut rpc_struct_names: Vec<&String> =
+            rpc_structs.keys().filter(struct_in_focus).collect();
+        rpc_struct_names.sort();
+        let mut byte_struct_names: Vec<&String> =
+            byte_structs.keys().filter(struct_in_focus).collect();
+        byte_struct_names.sort();
+
+        let byte_struct_set: HashSet<&str> = byte_struct_names.iter().map(|s| s.as_str()).collect();
+        let struct_casing: HashMap<&str, &str> = if self.opts.case_insensitive_names {
+            let rpc_only: Vec<&str> = rpc_struct_names
+                .iter()
+                .map(|s| s.as_str())
+                .filter(|s| !byte_struct_set.contains(s))
+                .collect();
+            let byte_only: Vec<&str> = byte_struct_names
+                .iter()
+                .map(|s| s.as_str())
+                .filter(|s| !rpc_structs.contains_key(*s))
+                .collect();
+            casing_pairs(&rpc_only, &byte_only).into_iter().collect()
+        } else {
+            HashMap::new()
+        };
+        for sname in &rpc_struct_names {
+            if !byte_struct_set.contains(sname.as_str())
+                && !struct_casing.contains_key(sname.as_str())
+            {
+                trace!(
+                    self,
+                    "struct {module_name}::{sname}: mismatch (missing in bytecode)"
+                );
+                self.sink.push(
+                    MismatchKind::StructMissing,
+                    MismatchSeverity::Error,
+                    format!("modules/{module_name}/structs/{sname}"),
+                    "struct missing in bytecode".to_string(),
+                    rpc_structs.get(sname.as_str()).cloned(),
+                    None,
+                );
+            }
+        }
+        for sname in &byte_struct_names {
+            if !rpc_structs.contains_key(sname.as_str())
+                && !struct_casing.values().any(|b| b == sname)
+            {
+                trace!(
+                    self,
+                    "struct {module_name}::{sname}: mismatch (extra in bytecode)"
+                );
+                self.sink.push(
+                    MismatchKind::StructExtra,
+                    MismatchSeverity::Error,
+                    format!("modules/{module_name}/structs/{sname}"),
+                    "extra struct in bytecode".to_string(),
+                    None,
+                    byte_structs.get(sname.as_str()).cloned(),
+                );
+            }
+        }
+
+        for sname in rpc_struct_names {
+            let Some(rpc_struct) = rpc_structs.get(sname) else {
+                continue;
+            };
+            let byte_sname = struct_casing
+                .get(sname.as_str())
+                .copied()
+                .unwrap_or(sname.as_str());
+            let Some(byte_struct) = byte_structs.get(byte_sname) else {
+                continue;
+            };
+            self.compare_struct(module_name, (sname, byte_sname), rpc_struct, byte_struct);
+        }
+    }
+
+    /// Compare one struct present on both sides, given its (RPC, bytecode) names.
+    fn compare_struct(
+        &mut self,
+        module_name: &str,
+        names: (&str, &str),
+        rpc_struct: &Value,
+        byte_struct: &Value,
+    ) {
+        let (sname, byte_sname) = names;
+        self.structs_compared += 1;
+        let struct_before = self.sink.total;
+        let spath = format!("modules/{module_name}/structs/{sname}");
+        if byte_sname != sname {
+            self.sink.push(
+                MismatchKind::NameCasing,
+                MismatchSeverity::Warning,
+                spath.clone(),
+                format!("name casing differs: {sname} vs {byte_sname}"),
+                None,
+                None,
+            );
+        }
+        self.compare_struct_abilities(&spath, rpc_struct, byte_struct);
+        self.compare_struct_type_params(&spath, rpc_struct, byte_struct);
+        if !self.compare_fields(&spath, rpc_struct, byte_struct) {
+            trace!(
+                self,
+                "struct {module_name}::{sname}: mismatch (duplicate fields)"
+            );
+            return;
+        }
+        if self.opts.compare_struct_sizes {
+            self.compare_struct_sizes(&spath, rpc_struct, byte_struct);
+        }
+        trace!(
+            self,
+            "struct {module_name}::{sname}: {}",
+            trace_outcome(struct_before, self.sink.total)
+        );
+    }
+
+    /// The abilities diff, plus the derived signals callers filter for separately.
+    fn compare_struct_abilities(&mut self, spath: &str, rpc_struct: &Value, byte_struct: &Value) {
+        let rpc_abilities = rpc_struct
+            .get("abilities")
+            .map(abilities_from_value)
+            .unwrap_or_default();
+        let byte_abilities = byte_struct
+            .get("abilities")
+            .map(abilities_from_value)
+            .unwrap_or_default();
+        if rpc_abilities != byte_abilities {
+            self.sink.push(
+                MismatchKind::AbilitiesMismatch,
+                MismatchSeverity::Error,
+                format!("{spath}/abilities"),
+                "abilities mismatch".to_string(),
+                rpc_struct.get("abilities").cloned(),
+                byte_struct.get("abilities").cloned(),
+            );
+        }
+        let has = |abilities: &[String], a: &str| abilities.iter().any(|x| x == a);
+        let (old, new) = self.oriented(&rpc_abilities, &byte_abilities);
+        // Hot potatoes must be consumed in the transaction that created them; letting one be
+        // dropped or stored removes the guarantee protocols build on (flash loan receipts).
+        if old.is_empty() {
+            let gained: Vec<&str> = new
+                .iter()
+                .map(String::as_str)
+                .filter(|a| matches!(*a, "drop" | "store" | "key"))
+                .collect();
+            if !gained.is_empty() {
+                self.sink.push(
+                    MismatchKind::HotPotatoChanged,
+                    MismatchSeverity::Error,
+                    format!("{spath}/hot_potato"),
+                    format!("hot potato gained {}", gained.join(", ")),
+                    Some(Value::Bool(rpc_abilities.is_empty())),
+                    Some(Value::Bool(byte_abilities.is_empty())),
+                );
+            }
+        }
+        // Separate from the abilities diff so SDKs that copy values can filter for it.
+        if has(old, "copy") != has(new, "copy") {
+            self.sink.push(
+                MismatchKind::CopyabilityChanged,
+                MismatchSeverity::Warning,
+                format!("{spath}/copyable"),
+                if has(new, "copy") {
+                    "copy gained".to_string()
+                } else {
+                    "copy lost".to_string()
+                },
+                Some(Value::Bool(has(&rpc_abilities, "copy"))),
+                Some(Value::Bool(has(&byte_abilities, "copy"))),
+            );
+        }
+        // Dynamic field values (and `Bag`/`Table` entries) must have `store`; losing it breaks
+        // every caller that stores this type that way, which a plain abilities diff hides.
+        if has(old, "store") && !has(new, "store") {
+            self.sink.push(
+                MismatchKind::DynamicFieldStoreLost,
+                MismatchSeverity::Error,
+                format!("{spath}/store"),
+                "store lost: no longer usable as a dynamic field value".to_string(),
+                Some(Value::Bool(has(&rpc_abilities, "store"))),
+                Some(Value::Bool(has(&byte_abilities, "store"))),
+            );
+        }
+        // `key + store` objects can be moved with `public_transfer` by anyone; calling out
+        // that change separately keeps it from hiding in a generic abilities diff.
+        if has(old, "key") && has(new, "key") && has(old, "store") != has(new, "store") {
+            let (kind, reason) = if has(new, "store") {
+                (MismatchKind::PublicTransferGained, "public transfer gained")
+            } else {
+                (MismatchKind::PublicTransferLost, "public transfer lost")
+            };
+            self.sink.push(
+                kind,
+                MismatchSeverity::Error,
+                format!("{spath}/public_transfer"),
+                reason.to_string(),
+                Some(Value::Bool(has(&rpc_abilities, "store"))),
+                Some(Value::Bool(has(&byte_abilities, "store"))),
+            );
+        }
+    }
+
+    fn compare_struct_type_params(&mut self, spath: &str, rpc_struct: &Value, byte_struct: &Value) {
+        let rpc_tps = array_at(rpc_struct, "typeParameters");
+        let byte_tps = array_at(byte_struct, "type_params");
+        if rpc_tps.len() != byte_tps.len() {
+            let (kind, reason) = type_param_arity_mismatch(rpc_tps.len(), byte_tps.len());
+            self.sink.push(
+                kind,
+                MismatchSeverity::Error,
+                format!("{spath}/type_params"),
+                reason,
+                rpc_struct.get("typeParameters").cloned(),
+                byte_struct.get("type_params").cloned(),
+            );
+            return;
+        }
+        for (i, (rtp, btp)) in rpc_tps.iter().zip(byte_tps).enumerate() {
+            let rpc_constraints = rtp
+                .get("constraints")
+                .map(abilities_from_value)
+                .unwrap_or_default();
+            let byte_constraints = btp
+                .get("constraints")
+                .map(abilities_from_value)
+                .unwrap_or_default();
+            let path = format!("{spath}/type_params[{i}]");
+            self.compare_constraints(&path, i, &rpc_constraints, &byte_constraints);
+            let rpc_is_phantom = bool_at(rtp, "isPhantom");
+            let byte_is_phantom = bool_at(btp, "is_phantom");
+            if rpc_is_phantom != byte_is_phantom {
+                self.sink.push(
+                    MismatchKind::PhantomChanged,
+                    MismatchSeverity::Error,
+                    format!("{path}/is_phantom"),
+                    format!(
+                        "phantom flag of type param {i} changed (rpc={rpc_is_phantom} bytecode={byte_is_phantom})"
+                    ),
+                    Some(Value::Bool(rpc_is_phantom)),
+                    Some(Value::Bool(byte_is_phantom)),
+                );
+            }
+        }
+    }
+
+    /// Report each ability constraint of type param `i` (at `path`) added or removed.
+    fn compare_constraints(
+        &mut self,
+        path: &str,
+        i: usize,
+        rpc_constraints: &[String],
+        byte_constraints: &[String],
+    ) {
+        for ability in byte_constraints
+            .iter()
+            .filter(|a| !rpc_constraints.contains(a))
+        {
+            self.sink.push(
+                MismatchKind::ConstraintAdded,
+                MismatchSeverity::Error,
+                format!("{path}/constraints/{ability}"),
+                format!("type param {i} constraint added: {ability}"),
+                None,
+                Some(Value::String(ability.clone())),
+            );
+        }
+        for ability in rpc_constraints
+            .iter()
+            .filter(|a| !byte_constraints.contains(a))
+        {
+            self.sink.push(
+                MismatchKind::ConstraintRemoved,
+                MismatchSeverity::Error,
+                format!("{path}/constraints/{ability}"),
+                format!("type param {i} constraint removed: {ability}"),
+                Some(Value::String(ability.clone())),
+                None,
+            );
+        }
+    }
+
+    /// Compare field names, order and types. Returns false when duplicate field names make the
+    /// struct incomparable.
+    fn compare_fields(&mut self, spath: &str, rpc_struct: &Value, byte_struct: &Value) -> bool {
+        let rpc_fields = array_at(rpc_struct, "fields");
+        let byte_fields = array_at(byte_struct, "fields");
+        let byte_is_native = bool_at(byte_struct, "is_native");
+        let fields_path = format!("{spath}/fields");
+        // Index-based field comparison misaligns on duplicates; report them alone.
+        let rpc_dups = duplicate_field_names(rpc_fields);
+        let byte_dups = duplicate_field_names(byte_fields);
+        if !rpc_dups.is_empty() || !byte_dups.is_empty() {
+            self.sink.push(
+                MismatchKind::DuplicateFieldName,
+                MismatchSeverity::Error,
+                fields_path,
+                "duplicate field name".to_string(),
+                (!rpc_dups.is_empty()).then(|| serde_json::json!(rpc_dups)),
+                (!byte_dups.is_empty()).then(|| serde_json::json!(byte_dups)),
+            );
+            return false;
+        }
+        // Native structs have no fields on the RPC side.
+        if byte_is_native && rpc_fields.is_empty() {
+            return true;
+        }
+        if self.opts.check_accessor_fields {
+            let rpc_names = field_name_set(rpc_fields);
+            let byte_names = field_name_set(byte_fields);
+            if rpc_names != byte_names {
+                self.sink.push(
+                    MismatchKind::AccessorFieldsChanged,
+                    MismatchSeverity::Warning,
+                    format!("{spath}/accessors"),
+                    "accessor-affecting field change".to_string(),
+                    Some(serde_json::json!(rpc_names)),
+                    Some(serde_json::json!(byte_names)),
+                );
+            }
+        }
+        let field_pairs = if self.opts.match_fields_by_name {
+            self.pair_fields_by_name(&fields_path, rpc_fields, byte_fields)
+        } else if rpc_fields.len() != byte_fields.len() {
+            self.sink.push(
+                MismatchKind::FieldCountMismatch,
+                MismatchSeverity::Error,
+                fields_path,
+                format!(
+                    "field count mismatch (rpc={} bytecode={})",
+                    rpc_fields.len(),
+                    byte_fields.len()
+                ),
+                rpc_struct.get("fields").cloned(),
+                byte_struct.get("fields").cloned(),
+            );
+            Vec::new()
+        } else {
+            self.pair_fields_by_position(&fields_path, rpc_fields, byte_fields)
+        };
+        let rpc_tps = array_at(rpc_struct, "typeParameters");
+        let byte_tps = array_at(byte_struct, "type_params");
+        for (type_path, rf, bf) in field_pairs {
+            self.compare_type_site(
+                TypeSite::Field,
+                &type_path,
+                (rf.get("type").unwrap_or(&Value::Null), rpc_tps),
+                (bf.get("type").unwrap_or(&Value::Null), byte_tps),
+            );
+        }
+        true
+    }
+
+    /// Pair fields by name, reporting missing, extra and reordered ones. Returns the
+    /// (type path, rpc field, bytecode field) pairs to compare.
+    fn pair_fields_by_name<'v>(
+        &mut self,
+        fields_path: &str,
+        rpc_fields: &'v [Value],
+        byte_fields: &'v [Value],
+    ) -> Vec<(String, &'v Value, &'v Value)> {
+        let rpc_names: Vec<&str> = rpc_fields.iter().map(field_name).collect();
+        let byte_names: Vec<&str> = byte_fields.iter().map(field_name).collect();
+        let mut pairs = Vec::new();
+        for rf in rpc_fields {
+            let name = field_name(rf);
+            match byte_fields.iter().find(|bf| field_name(bf) == name) {
+                Some(bf) => pairs.push((format!("{fields_path}/{name}/type"), rf, bf)),
+                None => {
+                    self.sink.push(
+                        MismatchKind::FieldMissing,
+                        MismatchSeverity::Error,
+                        format!("{fields_path}/{name}"),
+                        "field missing in bytecode".to_string(),
+                        Some(rf.clone()),
+                        None,
+                    );
+                }
+            }
+        }
+        for bf in byte_fields {
+            let name = field_name(bf);
+            if !rpc_names.contains(&name) {
+                self.sink.push(
+                    MismatchKind::FieldExtra,
+                    MismatchSeverity::Error,
+                    format!("{fields_path}/{name}"),
+                    "extra field in bytecode".to_string(),
+                    None,
+                    Some(bf.clone()),
+                );
+            }
+        }
+        let rpc_shared: Vec<&str> = rpc_names
+            .iter()
+            .copied()
+            .filter(|n| byte_names.contains(n))
+            .collect();
+        let byte_shared: Vec<&str> = byte_names
+            .iter()
+            .copied()
+            .filter(|n| rpc_names.contains(n))
+            .collect();
+        if rpc_shared != byte_shared {
+            self.sink.push(
+                MismatchKind::FieldOrderChanged,
+                MismatchSeverity::Error,
+                format!("{fields_path}/order"),
+                "field order changed".to_string(),
+                Some(serde_json::json!(rpc_shared)),
+                Some(serde_json::json!(byte_shared)),
+            );
+        }
+        pairs
+    }
+
+    /// Pair equally many fields by index, reporting name mismatches. Returns the
+    /// (type path, rpc field, bytecode field) pairs to compare.
+    fn pair_fields_by_position<'v>(
+        &mut self,
+        fields_path: &str,
+        rpc_fields: &'v [Value],
+        byte_fields: &'v [Value],
+    ) -> Vec<(String, &'v Value, &'v Value)> {
+        let mut pairs = Vec::new();
+        for (i, (rf, bf)) in rpc_fields.iter().zip(byte_fields).enumerate() {
+            if field_name(rf) != field_name(bf) {
+                self.sink.push(
+                    MismatchKind::FieldNameMismatch,
+                    MismatchSeverity::Error,
+                    format!("{fields_path}[{i}]/name"),
+                    "field name mismatch".to_string(),
+                    rf.get("name").cloned(),
+                    bf.get("name").cloned(),
+                );
+                continue;
+            }
+            pairs.push((format!("{fields_path}[{i}]/type"), rf, bf));
+        }
+        pairs
+    }
+
+    fn compare_struct_sizes(&mut self, spath: &str, rpc_struct: &Value, byte_struct: &Value) {
+        let sizes = (
+            estimate_struct_size(rpc_struct, SchemaSource::Rpc, self.rpc_decls),
+            estimate_struct_size(byte_struct, SchemaSource::Bytecode, self.byte_decls),
+        );
+        if let (Some(r), Some(b)) = sizes {
+            if r.min != b.min {
+                self.sink.push(
+                    MismatchKind::StructSizeChanged,
+                    MismatchSeverity::Warning,
+                    format!("{spath}/size"),
+                    format!(
+                        "estimated minimum size changed (rpc={} bytecode={} bytes)",
+                        r.min, b.min
+                    ),
+                    serde_json::to_value(r).ok(),
+                    serde_json::to_value(b).ok(),
+                );
+            }
+        }
+    }
+
+    /// Compare one field, param or return type, each given with the declaring item's type
+    /// params. Parse errors are reported in place of a type diff.
+    fn compare_type_site(
+        &mut self,
+        site: TypeSite,
+        path: &str,
+        rpc: (&Value, &[Value]),
+        bytecode: (&Value, &[Value]),
+    ) {
+        let ((rty, rpc_tps), (bty, byte_tps)) = (rpc, bytecode);
+        let policy = self.opts.unknown_type_policy;
+        let rcanon = type_to_canonical_json_with(
+            SchemaSource::Rpc,
+            &resolve_type_param_names(rty, rpc_tps),
+            policy,
+        );
+        let bcanon = type_to_canonical_json_with(
+            SchemaSource::Bytecode,
+            &resolve_type_param_names(bty, byte_tps),
+            policy,
+        );
+        self.type_sites_compared += 1;
+        let site_before = self.sink.total;
+        match (rcanon, bcanon) {
+            (Ok(mut r), Ok(mut b)) => {
+                if self.opts.unify_string_types {
+                    lower_string_types(&mut r);
+                    lower_string_types(&mut b);
+                }
+                if self.opts.unify_option_types {
+                    lower_option_types(&mut r);
+                    lower_option_types(&mut b);
+                }
+                canonicalize_json_value(&mut r);
+                canonicalize_json_value(&mut b);
+                // Consuming vs borrowing an object changes how a PTB must supply it, so it is
+                // reported on its own besides the type diff. Under `value_shape_mode` this is
+                // the only report of any other `&`/`&mut` difference.
+                let object_param = matches!(site, TypeSite::Param | TypeSite::InitParam)
+                    && may_be_object(&r)
+                    && may_be_object(&b);
+                let shape_only = self.opts.value_shape_mode && site != TypeSite::Field;
+                if object_param || shape_only {
+                    let (rmode, bmode) = (param_passing_mode(&r), param_passing_mode(&b));
+                    if rmode != bmode {
+                        self.sink.push(
+                            MismatchKind::PassingModeChanged,
+                            MismatchSeverity::Error,
+                            format!("{path}/passing_mode"),
+                            if object_param {
+                                "object passing mode changed".to_string()
+                            } else {
+                                "reference changed".to_string()
+                            },
+                            serde_json::to_value(rmode).ok(),
+                            serde_json::to_value(bmode).ok(),
+                        );
+                    }
+                }
+                if self.opts.value_shape_mode && site != TypeSite::Field {
+                    r = strip_outer_ref(r);
+                    b = strip_outer_ref(b);
+                }
+                let (kind, reason) = site.mismatch();
+                for (path, r_at, b_at) in type_diffs(&r, &b, path) {
+                    if self.sink.push(
+                        kind,
+                        MismatchSeverity::Error,
+                        path,
+                        reason.to_string(),
+                        Some(r_at),
+                        Some(b_at),
+                    ) {
+                        self.sink.attach_canonical(&r, &b);
+                    }
+                }
+            }
+            (Err(e), _) => {
+                self.sink.push(
+                    MismatchKind::RpcTypeParseError,
+                    self.parse_error_severity,
+                    path.to_string(),
+                    format!("rpc type parse error: {:#}", e),
+                    Some(rty.clone()),
+                    None,
+                );
+            }
+            (_, Err(e)) => {
+                self.sink.push(
+                    MismatchKind::BytecodeTypeParseError,
+                    self.parse_error_severity,
+                    path.to_string(),
+                    format!("bytecode type parse error: {:#}", e),
+                    None,
+                    Some(bty.clone()),
+                );
+            }
+        }
+        if self.sink.total > site_before {
+            self.type_sites_mismatched += 1;
+        }
+    }
+
+    fn compare_imports(&mut self, module: &ModulePair) {
+        let imports = |module: &Value| -> Option<BTreeSet<String>> {
+            let list = module
+                .get("uses")
+                .or_else(|| module.get("imports"))?
+                .as_array()?;
+            Some(
+                list.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect(),
+            )
+        };
+        let (Some(rpc_imports), Some(byte_imports)) =
+            (imports(module.rpc), imports(module.bytecode))
+        else {
+            return;
+        };
+        let (old, new) = self.oriented(&rpc_imports, &byte_imports);
+        for (name, reason) in new
+            .difference(old)
+            .map(|n| (n, "import added"))
+            .chain(old.difference(new).map(|n| (n, "import removed")))
+        {
+            self.sink.push(
+                MismatchKind::ImportChanged,
+                MismatchSeverity::Info,
+                format!("modules/{}/imports/{name}", module.name),
+                reason.to_string(),
+                None,
+                None,
+            );
+        }
+    }
+
+    /// Whether a function of this module passes `function_name_filter` and `focus_path`.
+    fn function_selected(&self, module_name: &str, fname: &str) -> bool {
+        self.opts
+            .function_name_filter
+            .as_deref()
+            .is_none_or(|pattern| path_matches_pattern(pattern, fname))
+            && self.in_focus(&format!("modules/{module_name}/functions/{fname}"))
+    }
+
+    fn compare_functions(&mut self, module: &ModulePair) {
+        let module_name = module.name;
+        let empty = serde_json::Map::new();
+        let rpc_funcs =
+            get_object(module.rpc, &["exposedFunctions", "exposed_functions"]).unwrap_or(&empty);
+        let byte_funcs = get_object(module.bytecode, &["functions"]).unwrap_or(&empty);
+
+        let mut rpc_func_names: Vec<&String> = rpc_funcs
+            .keys()
+            .filter(|f| self.function_selected(module_name, f))
+            .collect();
+        rpc_func_names.sort();
+        for fname in &rpc_func_names {
+            self.sink.verdict(module_name, fname);
+        }
+        if self.trace.is_some() && self.opts.function_name_filter.is_some() {
+            let mut filtered: Vec<&String> = rpc_funcs
+                .keys()
+                .filter(|f| !rpc_func_names.contains(f))
+                .collect();
+            filtered.sort();
+            for fname in filtered {
+                trace!(self, "function {module_name}::{fname}: skip (filtered)");
+            }
+        }
+        let function_casing: HashMap<&str, &str> = if self.opts.case_insensitive_names {
+            let rpc_only: Vec<&str> = rpc_func_names
+                .iter()
+                .map(|f| f.as_str())
+                .filter(|f| !byte_funcs.contains_key(*f))
+                .collect();
+            let byte_only: Vec<&str> = byte_funcs
+                .keys()
+                .map(|f| f.as_str())
+                .filter(|f| !rpc_funcs.contains_key(*f))
+                .collect();
+            casing_pairs(&rpc_only, &byte_only).into_iter().collect()
+        } else {
+            HashMap::new()
+        };
+
+        let rpc_public_only = rpc_funcs.values().all(|f| {
+            f.get("visibility")
+                .and_then(rpc_visibility_to_string)
+                .as_deref()
+                == Some("public")
+        });
+        self.check_type_param_disparity(module, (rpc_funcs, byte_funcs), rpc_public_only);
+        if let Some(init) = byte_funcs.get("init") {
+            if self.function_selected(module_name, "init") {
+                self.check_init_shape(module, init);
+            }
+        }
+
+        if self.opts.report_extra_functions {
+            let mut extra: Vec<&String> = byte_funcs
+                .iter()
+                .filter(|(f, byte_fun)| {
+                    !rpc_funcs.contains_key(*f)
+                        && !function_casing.values().any(|b| b == f)
+                        && (self.opts.report_private_extra_functions
+                            || rpc_would_expose(byte_fun, rpc_public_only))
+                        && self.function_selected(module_name, f)
+                })
+                .map(|(f, _)| f)
+                .collect();
+            extra.sort();
+            for fname in extra {
+                trace!(
+                    self,
+                    "function {module_name}::{fname}: mismatch (extra in bytecode)"
+                );
+                let byte_fun = &byte_funcs[fname.as_str()];
+                self.sink.push(
+                    MismatchKind::FunctionExtra,
+                    if rpc_would_expose(byte_fun, rpc_public_only) {
+                        MismatchSeverity::Error
+                    } else {
+                        MismatchSeverity::Info
+                    },
+                    format!("modules/{module_name}/functions/{fname}"),
+                    "extra function in bytecode".to_string(),
+                    None,
+                    Some(byte_fun.clone()),
+                );
+            }
+        }
+
+        for fname in rpc_func_names {
+            let Some(rpc_fun) = rpc_funcs.get(fname) else {
+                continue;
+            };
+            let byte_fname = function_casing
+                .get(fname.as_str())
+                .copied()
+                .unwrap_or(fname.as_str());
+            let Some(byte_fun) = byte_funcs.get(byte_fname) else {
+                trace!(
+                    self,
+                    "function {module_name}::{fname}: mismatch (missing in bytecode)"
+                );
+                self.sink.push(
+                    MismatchKind::FunctionMissing,
+                    MismatchSeverity::Error,
+                    format!("modules/{module_name}/functions/{fname}"),
+                    "function missing in bytecode".to_string(),
+                    Some(rpc_fun.clone()),
+                    None,
+                );
+                continue;
+            };
+            self.compare_function(module, (fname, byte_fname), rpc_fun, byte_fun);
+        }
+    }
+
+    /// Coarse signal only: modules this far apart are structurally different, whatever the
+    /// detailed diff says. Takes the (RPC, bytecode) function maps.
+    fn check_type_param_disparity(
+        &mut self,
+        module: &ModulePair,
+        funcs: (
+            &serde_json::Map<String, Value>,
+            &serde_json::Map<String, Value>,
+        ),
+        rpc_public_only: bool,
+    ) {
+        let (rpc_funcs, byte_funcs) = funcs;
+        let type_param_count = |v: &Value, key: &str| array_at(v, key).len();
+        let rpc_type_params: usize = module
+            .rpc_structs
+            .values()
+            .chain(rpc_funcs.values())
+            .map(|v| type_param_count(v, "typeParameters"))
+            .sum();
+        let byte_type_params: usize = module
+            .byte_structs
+            .values()
+            .chain(
+                byte_funcs
+                    .values()
+                    .filter(|f| rpc_would_expose(f, rpc_public_only)),
+            )
+            .map(|v| type_param_count(v, "type_params"))
+            .sum();
+        let (fewer, more) = if rpc_type_params < byte_type_params {
+            (rpc_type_params, byte_type_params)
+        } else {
+            (byte_type_params, rpc_type_params)
+        };
+        let path = format!("modules/{}/type_params", module.name);
+        if more >= TYPE_PARAM_DISPARITY_MIN
+            && more > fewer * TYPE_PARAM_DISPARITY_RATIO
+            && self.in_focus(&path)
+        {
+            self.sink.push(
+                MismatchKind::TypeParamDisparity,
+                MismatchSeverity::Info,
+                path,
+                format!(
+                    "declared type param totals differ widely (rpc={rpc_type_params} bytecode={byte_type_params})"
+                ),
+                Some(Value::from(rpc_type_params)),
+                Some(Value::from(byte_type_params)),
+            );
+        }
+    }
+
+    /// Compare one function present on both sides, given its (RPC, bytecode) names.
+    fn compare_function(
+        &mut self,
+        module: &ModulePair,
+        names: (&str, &str),
+        rpc_fun: &Value,
+        byte_fun: &Value,
+    ) {
+        let module_name = module.name;
+        let (fname, byte_fname) = names;
+        self.functions_compared += 1;
+        let function_before = self.sink.total;
+        let fpath = format!("modules/{module_name}/functions/{fname}");
+        if byte_fname != fname {
+            self.sink.push(
+                MismatchKind::NameCasing,
+                MismatchSeverity::Warning,
+                fpath.clone(),
+                format!("name casing differs: {fname} vs {byte_fname}"),
+                None,
+                None,
+            );
+        }
+
+        let rpc_vis = self.rpc_function_visibility(rpc_fun);
+        let byte_vis = byte_fun
+            .get("visibility")
+            .and_then(Value::as_str)
+            .unwrap_or("<missing>")
+            .to_string();
+        let rpc_entry = bool_at(rpc_fun, "isEntry");
+        let byte_entry = bool_at(byte_fun, "is_entry");
+        self.compare_callability(
+            &fpath,
+            (rpc_fun, byte_fun),
+            (&rpc_vis, &byte_vis),
+            (rpc_entry, byte_entry),
+        );
+        if self.opts.check_deprecation {
+            self.compare_deprecation(&fpath, rpc_fun, byte_fun);
+        }
+        if self.opts.check_tx_context {
+            self.compare_tx_context(&fpath, rpc_fun, byte_fun);
+        }
+        self.compare_function_type_params(&fpath, rpc_fun, byte_fun);
+        if self.opts.check_internal_type_exposure {
+            self.check_internal_type_exposure(
+                &fpath,
+                (rpc_fun, byte_fun),
+                (rpc_vis == "public", byte_vis == "public"),
+            );
+        }
+        if self.opts.check_acquires {
+            self.check_acquires(module, &fpath, rpc_fun, byte_fun);
+        }
+        if self.opts.check_entry_params && (rpc_entry || byte_entry) {
+            self.check_entry_params(&fpath, (rpc_fun, byte_fun), (rpc_entry, byte_entry));
+        }
+        self.check_collision(&fpath, rpc_fun, byte_fun);
+        // A changed `init` param is reported as a signature change rather than a param diff.
+        let param_site = if fname == "init" {
+            TypeSite::InitParam
+        } else {
+            TypeSite::Param
+        };
+        self.compare_params(&fpath, rpc_fun, byte_fun, param_site);
+        if self.opts.compare_param_names {
+            self.compare_param_names(&fpath, rpc_fun, byte_fun);
+        }
+        self.compare_returns(&fpath, rpc_fun, byte_fun);
+        trace!(
+            self,
+            "function {module_name}::{fname}: {}",
+            trace_outcome(function_before, self.sink.total)
+        );
+    }
+
+    fn rpc_function_visibility(&self, rpc_fun: &Value) -> String {
+        rpc_fun
+            .get("visibility")
+            .and_then(rpc_visibility_to_string)
+            .unwrap_or_else(|| {
+                if self.opts.infer_missing_visibility
+                    && rpc_fun.get("visibility").is_none()
+                    && !bool_at(rpc_fun, "isEntry")
+                {
+                    "public".to_string()
+                } else {
+                    "<unknown>".to_string()
+                }
+            })
+    }
+
+    /// Visibility, entry and the callability class derived from them. Takes the (RPC, bytecode)
+    /// functions, visibilities and entry flags.
+    fn compare_callability(
+        &mut self,
+        fpath: &str,
+        funs: (&Value, &Value),
+        visibility: (&str, &str),
+        entry: (bool, bool),
+    ) {
+        let (rpc_fun, byte_fun) = funs;
+        let (rpc_vis, byte_vis) = visibility;
+        let (rpc_entry, byte_entry) = entry;
+        if rpc_vis != byte_vis {
+            self.sink.push(
+                MismatchKind::VisibilityMismatch,
+                MismatchSeverity::Error,
+                format!("{fpath}/visibility"),
+                "visibility mismatch".to_string(),
+                rpc_fun.get("visibility").cloned(),
+                byte_fun.get("visibility").cloned(),
+            );
+        }
+        if rpc_entry != byte_entry {
+            self.sink.push(
+                MismatchKind::EntryMismatch,
+                MismatchSeverity::Error,
+                format!("{fpath}/is_entry"),
+                "entry mismatch".to_string(),
+                rpc_fun.get("isEntry").cloned(),
+                byte_fun.get("is_entry").cloned(),
+            );
+        }
+        let rpc_class = function_callability(rpc_vis, rpc_entry);
+        let byte_class = function_callability(byte_vis, byte_entry);
+        if rpc_class != byte_class {
+            self.sink.push(
+                MismatchKind::CallabilityChanged,
+                MismatchSeverity::Error,
+                format!("{fpath}/callability"),
+                "callability class changed".to_string(),
+                serde_json::to_value(rpc_class).ok(),
+                serde_json::to_value(byte_class).ok(),
+            );
+        }
+    }
+
+    fn compare_deprecation(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let rpc_deprecated = is_deprecated(rpc_fun);
+        let byte_deprecated = is_deprecated(byte_fun);
+        if rpc_deprecated != byte_deprecated {
+            self.sink.push(
+                MismatchKind::DeprecationChanged,
+                MismatchSeverity::Warning,
+                format!("{fpath}/deprecated"),
+                "deprecation status changed".to_string(),
+                Some(Value::Bool(rpc_deprecated)),
+                Some(Value::Bool(byte_deprecated)),
+            );
+        }
+    }
+
+    fn compare_tx_context(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let rpc_ctx = takes_tx_context(SchemaSource::Rpc, array_at(rpc_fun, "parameters"));
+        let byte_ctx = takes_tx_context(SchemaSource::Bytecode, array_at(byte_fun, "params"));
+        if rpc_ctx != byte_ctx {
+            self.sink.push(
+                MismatchKind::TxContextChanged,
+                MismatchSeverity::Warning,
+                format!("{fpath}/tx_context"),
+                if self.oriented(rpc_ctx, byte_ctx).1 {
+                    "TxContext parameter added".to_string()
+                } else {
+                    "TxContext parameter removed".to_string()
+                },
+                Some(Value::Bool(rpc_ctx)),
+                Some(Value::Bool(byte_ctx)),
+            );
+        }
+    }
+
+    fn compare_function_type_params(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let rpc_tps = array_at(rpc_fun, "typeParameters");
+        let byte_tps = array_at(byte_fun, "type_params");
+        if rpc_tps.len() == byte_tps.len() {
+            for (i, (rtp, btp)) in rpc_tps.iter().zip(byte_tps).enumerate() {
+                let rpc_constraints = abilities_from_value(rtp);
+                let byte_constraints = btp
+                    .get("constraints")
+                    .map(abilities_from_value)
+                    .unwrap_or_default();
+                let path = format!("{fpath}/type_params[{i}]");
+                self.compare_constraints(&path, i, &rpc_constraints, &byte_constraints);
+            }
+            return;
+        }
+        let extra_unused = self.opts.ignore_unused_type_params && {
+            let (source, fun, shorter, longer) = if rpc_tps.len() > byte_tps.len() {
+                (SchemaSource::Rpc, rpc_fun, byte_tps.len(), rpc_tps.len())
+            } else {
+                (
+                    SchemaSource::Bytecode,
+                    byte_fun,
+                    rpc_tps.len(),
+                    byte_tps.len(),
+                )
+            };
+            used_type_params(source, fun)
+                .is_some_and(|used| (shorter..longer).all(|i| !used.contains(&(i as u64))))
+        };
+        let (kind, reason) = type_param_arity_mismatch(rpc_tps.len(), byte_tps.len());
+        self.sink.push(
+            kind,
+            if extra_unused {
+                MismatchSeverity::Info
+            } else {
+                MismatchSeverity::Error
+            },
+            format!("{fpath}/type_params"),
+            reason,
+            rpc_fun.get("typeParameters").cloned(),
+            byte_fun.get("type_params").cloned(),
+        );
+    }
+
+    /// Takes the (RPC, bytecode) functions and whether each is public.
+    fn check_internal_type_exposure(
+        &mut self,
+        fpath: &str,
+        funs: (&Value, &Value),
+        public: (bool, bool),
+    ) {
+        let exposed = |public: bool, source, fun, decls| {
+            public
+                .then(|| internal_types_referenced(source, fun, decls))
+                .filter(|types| !types.is_empty())
+        };
+        let rpc_exposed = exposed(public.0, SchemaSource::Rpc, funs.0, self.rpc_decls);
+        let byte_exposed = exposed(public.1, SchemaSource::Bytecode, funs.1, self.byte_decls);
+        if rpc_exposed.is_some() || byte_exposed.is_some() {
+            self.sink.push(
+                MismatchKind::InternalTypeExposed,
+                MismatchSeverity::Warning,
+                format!("{fpath}/internal_types"),
+                "public function exposes internal type".to_string(),
+                rpc_exposed.map(|t| serde_json::json!(t)),
+                byte_exposed.map(|t| serde_json::json!(t)),
+            );
+        }
+    }
+
+    fn check_acquires(
+        &mut self,
+        module: &ModulePair,
+        fpath: &str,
+        rpc_fun: &Value,
+        byte_fun: &Value,
+    ) {
+        // Global storage ops only reach `key` structs of the declaring module, so anything
+        // else in the acquires table means it was extracted wrongly.
+        let bare_module = module.name.rsplit("::").next().unwrap_or(module.name);
+        let mut validity: BTreeMap<&str, (Option<bool>, Option<bool>)> = BTreeMap::new();
+        for (fun, structs, is_rpc) in [
+            (rpc_fun, &module.rpc_structs, true),
+            (byte_fun, &module.byte_structs, false),
+        ] {
+            for entry in array_at(fun, "acquires") {
+                let Some(name) = entry.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let valid = entry
+                    .get("module")
+                    .and_then(Value::as_str)
+                    .is_none_or(|m| m == bare_module)
+                    && structs.get(bare_struct_name(name)).is_some_and(|s| {
+                        s.get("abilities")
+                            .map(abilities_from_value)
+                            .unwrap_or_default()
+                            .iter()
+                            .any(|a| a == "key")
+                    });
+                let slot = validity.entry(name).or_default();
+                if is_rpc {
+                    slot.0 = Some(valid);
+                } else {
+                    slot.1 = Some(valid);
+                }
+            }
+        }
+        for (name, (rpc_valid, byte_valid)) in validity {
+            if rpc_valid == Some(false) || byte_valid == Some(false) {
+                self.sink.push(
+                    MismatchKind::InvalidAcquires,
+                    MismatchSeverity::Warning,
+                    format!("{fpath}/acquires/{name}"),
+                    "invalid acquires reference".to_string(),
+                    rpc_valid.map(Value::Bool),
+                    byte_valid.map(Value::Bool),
+                );
+            }
+        }
+    }
+
+    /// Takes the (RPC, bytecode) functions and entry flags; only entry sides are checked.
+    fn check_entry_params(&mut self, fpath: &str, funs: (&Value, &Value), entry: (bool, bool)) {
+        let rpc_params = array_at(funs.0, "parameters");
+        let byte_params = array_at(funs.1, "params");
+        let validity = |source, params: &[Value], i: usize| {
+            let canon = type_to_canonical_json(source, params.get(i)?).ok()?;
+            Some(is_valid_entry_param(&canon, true))
+        };
+        for i in 0..rpc_params.len().max(byte_params.len()) {
+            let rpc_valid = entry
+                .0
+                .then(|| validity(SchemaSource::Rpc, rpc_params, i))
+                .flatten();
+            let byte_valid = entry
+                .1
+                .then(|| validity(SchemaSource::Bytecode, byte_params, i))
+                .flatten();
+            if rpc_valid == Some(false) || byte_valid == Some(false) {
+                self.sink.push(
+                    MismatchKind::InvalidEntryParam,
+                    MismatchSeverity::Warning,
+                    format!("{fpath}/params[{i}]/entry"),
+                    "invalid entry parameter type".to_string(),
+                    rpc_valid.map(Value::Bool),
+                    byte_valid.map(Value::Bool),
+                );
+            }
+        }
+    }
+
+    /// Sui only publishes a module whose `init` is private, not entry, not generic, returns
+    /// nothing and takes an optional one-time witness (the module name upper-cased) followed by
+    /// a `TxContext` reference. RPC never lists `init`, so this checks the bytecode side alone.
+    fn check_init_shape(&mut self, module: &ModulePair, init: &Value) {
+        let bare_module = module.name.rsplit("::").next().unwrap_or(module.name);
+        let otw_name = bare_module.to_ascii_uppercase();
+        let is_ctx = |c: &Value| {
+            c.get("kind").and_then(Value::as_str) == Some("ref")
+                && c.get("to")
+                    .is_some_and(|to| is_canonical_datatype(to, "0x2", "tx_context", "TxContext"))
+        };
+        let is_otw = |c: &Value| {
+            c.get("kind").and_then(Value::as_str) == Some("datatype")
+                && c.get("module").and_then(Value::as_str) == Some(bare_module)
+                && c.get("name").and_then(Value::as_str) == Some(otw_name.as_str())
+                && array_at(c, "type_args").is_empty()
+        };
+        let params = array_at(init, "params");
+        let canon: Vec<Option<Value>> = params
+            .iter()
+            .map(|p| type_to_canonical_json(SchemaSource::Bytecode, p).ok())
+            .collect();
+        let problem = if init.get("visibility").and_then(Value::as_str) != Some("private")
+            || bool_at(init, "is_entry")
+        {
+            Some("must be private and not entry")
+        } else if !array_at(init, "type_params").is_empty() {
+            Some("must not be generic")
+        } else if !array_at(init, "returns").is_empty() {
+            Some("must not return values")
+        } else {
+            match canon.as_slice() {
+                [Some(ctx)] if is_ctx(ctx) => None,
+                [Some(otw), Some(ctx)] if is_ctx(ctx) => {
+                    (!is_otw(otw)).then_some("first param is not the module's one-time witness")
+                }
+                _ => Some("params must be an optional one-time witness and a TxContext reference"),
+            }
+        };
+        if let Some(problem) = problem {
+            self.sink.push(
+                MismatchKind::InitSignatureInvalid,
+                MismatchSeverity::Error,
+                format!("modules/{}/functions/init/signature", module.name),
+                format!("invalid init signature: {problem}"),
+                None,
+                Some(Value::Array(params.to_vec())),
+            );
+        }
+    }
+
+    /// JSON maps are last-wins, so two colliding definitions surface as one wildly different
+    /// function. Hint at that besides the ordinary mismatches.
+    fn check_collision(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let shape = |fun: &Value, params: &str, returns: &str, type_params: &str| {
+            (
+                array_at(fun, params).len(),
+                flatten_return_tuple(array_at(fun, returns).to_vec()).len(),
+                array_at(fun, type_params).len(),
+            )
+        };
+        let (rpc_params, rpc_rets, rpc_tps) =
+            shape(rpc_fun, "parameters", "return", "typeParameters");
+        let (byte_params, byte_rets, byte_tps) =
+            shape(byte_fun, "params", "returns", "type_params");
+        if rpc_params.abs_diff(byte_params) >= COLLISION_PARAM_GAP
+            || (rpc_params != byte_params && rpc_rets != byte_rets && rpc_tps != byte_tps)
+        {
+            self.sink.push(
+                MismatchKind::PossibleCollision,
+                MismatchSeverity::Info,
+                format!("{fpath}/collision"),
+                "possible function collision/extraction artifact".to_string(),
+                Some(serde_json::json!({
+                    "params": rpc_params,
+                    "returns": rpc_rets,
+                    "type_params": rpc_tps
+                })),
+                Some(serde_json::json!({
+                    "params": byte_params,
+                    "returns": byte_rets,
+                    "type_params": byte_tps
+                })),
+            );
+        }
+    }
+
+    fn compare_params(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value, site: TypeSite) {
+        let rpc_params = array_at(rpc_fun, "parameters");
+        let byte_params = array_at(byte_fun, "params");
+        if rpc_params.len() != byte_params.len() {
+            self.sink.push(
+                MismatchKind::ParamCountMismatch,
+                MismatchSeverity::Error,
+                format!("{fpath}/params"),
+                format!(
+                    "param count mismatch (rpc={} bytecode={})",
+                    rpc_params.len(),
+                    byte_params.len()
+                ),
+                rpc_fun.get("parameters").cloned(),
+                byte_fun.get("params").cloned(),
+            );
+            return;
+        }
+        let rpc_tps = array_at(rpc_fun, "typeParameters");
+        let byte_tps = array_at(byte_fun, "type_params");
+        for (i, (rp, bp)) in rpc_params.iter().zip(byte_params).enumerate() {
+            self.compare_type_site(
+                site,
+                &format!("{fpath}/params[{i}]"),
+                (rp, rpc_tps),
+                (bp, byte_tps),
+            );
+        }
+    }
+
+    fn compare_param_names(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let (Some(rpc_names), Some(byte_names)) = (
+            rpc_fun.get("parameterNames").and_then(Value::as_array),
+            byte_fun.get("param_names").and_then(Value::as_array),
+        ) else {
+            return;
+        };
+        for (i, (rn, bn)) in rpc_names.iter().zip(byte_names).enumerate() {
+            if rn != bn {
+                self.sink.push(
+                    MismatchKind::ParamNameMismatch,
+                    MismatchSeverity::Warning,
+                    format!("{fpath}/params[{i}]/name"),
+                    "parameter name mismatch".to_string(),
+                    Some(rn.clone()),
+                    Some(bn.clone()),
+                );
+            }
+        }
+    }
+
+    fn compare_returns(&mut self, fpath: &str, rpc_fun: &Value, byte_fun: &Value) {
+        let rpc_rets = flatten_return_tuple(array_at(rpc_fun, "return").to_vec());
+        let byte_rets = flatten_return_tuple(array_at(byte_fun, "returns").to_vec());
+        let rpc_potatoes = hot_potato_returns(SchemaSource::Rpc, &rpc_rets, self.rpc_decls);
+        let byte_potatoes = hot_potato_returns(SchemaSource::Bytecode, &byte_rets, self.byte_decls);
+        if rpc_potatoes != byte_potatoes {
+            self.sink.push(
+                MismatchKind::HotPotatoReturnChanged,
+                MismatchSeverity::Error,
+                format!("{fpath}/returns/hot_potato"),
+                "hot potato return changed".to_string(),
+                Some(serde_json::json!(rpc_potatoes)),
+                Some(serde_json::json!(byte_potatoes)),
+            );
+        }
+        if rpc_rets.len() != byte_rets.len() {
+            self.sink.push(
+                MismatchKind::ReturnCountMismatch,
+                MismatchSeverity::Error,
+                format!("{fpath}/returns"),
+                format!(
+                    "return count mismatch (rpc={} bytecode={})",
+                    rpc_rets.len(),
+                    byte_rets.len()
+                ),
+                rpc_fun.get("return").cloned(),
+                byte_fun.get("returns").cloned(),
+            );
+            return;
+        }
+        let rpc_tps = array_at(rpc_fun, "typeParameters");
+        let byte_tps = array_at(byte_fun, "type_params");
+        for (i, (rr, br)) in rpc_rets.iter().zip(&byte_rets).enumerate() {
+            self.compare_type_site(
+                TypeSite::Return,
+                &format!("{fpath}/returns[{i}]"),
+                (rr, rpc_tps),
+                (br, byte_tps),
+            );
+        }
+    }
+}
+
+/// Per-module counts of a full comparison: each compared module with its compared structs and
+/// functions and the mismatches whose path falls under it (dangling type references included).
+/// Uses the same options as a full comparison but keeps no mismatch samples; modules are named
+/// as the comparison's paths name them (`<address>::<name>` under `key_modules_by_address`, the
+/// RPC spelling under `case_insensitive_names`).
+pub fn module_breakdown(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: &InterfaceCompareOptions,
+) -> ModuleBreakdown {
+    let opts = InterfaceCompareOptions {
+        max_mismatches: 0,
+        ..opts.clone()
+    };
+    let unchanged = HashSet::new();
+    let mut walk = Walk::new(
+        &opts,
+        None,
+        SchemaSource::Rpc,
+        &unchanged,
+        rpc_interface_value,
+        bytecode_interface_value,
+    );
+    run_walk(
+        &mut walk,
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+    );
+    let modules = walk
+        .module_items
+        .iter()
+        .map(
+            |(module, &(structs_compared, functions_compared))| ModuleCompareStats {
+                module: module.clone(),
+                structs_compared,
+                functions_compared,
+                mismatches: walk.sink.per_module.get(module).copied().unwrap_or(0),
+            },
+        )
+        .collect();
+    ModuleBreakdown { modules }
+}
+
+/// Hex SHA-256 of a module's RPC and bytecode interfaces in canonical JSON; changes when either
+/// side does.
+pub fn module_fingerprint(rpc_module: &Value, bytecode_module: &Value) -> String {
+    let mut pair = serde_json::json!([rpc_module, bytecode_module]);
+    canonicalize_json_value(&mut pair);
+    bytes_to_hex(&sha256_32(pair.to_string().as_bytes()))
+}
+
+/// Compare only modules whose fingerprint differs from `previous` (module key → fingerprint
+/// from an earlier run; keys are `<address>::<name>` under `key_modules_by_address`). A
+/// fingerprint covers the module's `module_fingerprint`, the options that shape findings and
+/// both interfaces' other top-level keys (`dependencies`, `version`, ...). Matching modules are
+/// not compared and count as neither compared nor missing; findings from the earlier run are the
+/// caller's to keep. Their declarations stay visible to the rest of the walk. Returns the
+/// fingerprint map to persist for the next run.
+pub fn compare_interface_incremental(
+    package_id: &str,
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+    opts: InterfaceCompareOptions,
+    previous: &HashMap<String, String>,
+) -> (
+    InterfaceCompareSummary,
+    Vec<InterfaceCompareMismatch>,
+    HashMap<String, String>,
+) {
+    let empty_modules = serde_json::Map::new();
+    let mut rpc_modules = rpc_interface_value
+        .get("modules")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty_modules);
+    let mut byte_modules = bytecode_interface_value
+        .get("modules")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty_modules);
+    // Fingerprints are keyed like the walk keys modules, so `unchanged` matches its names.
+    let rpc_by_address;
+    let byte_by_address;
+    if opts.key_modules_by_address {
+        rpc_by_address = modules_keyed_by_address(rpc_modules);
+        byte_by_address = modules_keyed_by_address(byte_modules);
+        rpc_modules = &rpc_by_address;
+        byte_modules = &byte_by_address;
+    }
+
+    // Sampling and cancellation do not change what a module's comparison finds.
+    let relevant = InterfaceCompareOptions {
+        max_mismatches: 0,
+        cancel: None,
+        ..opts.clone()
+    };
+    let top_level = |v: &Value| -> Value {
+        let mut rest = v.as_object().cloned().unwrap_or_default();
+        rest.remove("modules");
+        Value::Object(rest)
+    };
+    let mut context = serde_json::json!([
+        format!("{relevant:?}"),
+        top_level(rpc_interface_value),
+        top_level(bytecode_interface_value)
+    ]);
+    canonicalize_json_value(&mut context);
+    let context = context.to_string();
+
+    let fingerprints: HashMap<String, String> = rpc_modules
+        .iter()
+        .filter_map(|(name, rpc_mod)| {
+            let byte_mod = byte_modules.get(name)?;
+            let module = module_fingerprint(rpc_mod, byte_mod);
+            let fingerprint = bytes_to_hex(&sha256_32(format!("{module}{context}").as_bytes()));
+            Some((name.clone(), fingerprint))
+        })
+        .collect();
+    let unchanged: HashSet<String> = fingerprints
+        .iter()
+        .filter(|(name, fingerprint)| previous.get(*name) == Some(*fingerprint))
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let (summary, mismatches, _) = compare_interface_impl(
+        package_id,
+        rpc_interface_value,
+        bytecode_interface_value,
+        opts,
+        SchemaSource::Rpc,
+        None,
+        &unchanged,
+    );
+    (summary, mismatches, fingerprints)
+}
+
+/// Compare `rpc_interface_value` against each `(label, bytecode interface)` candidate, e.g. one
+/// per published version, and return the closest: fewest `Error` mismatches, then fewest in
+/// total, then earliest. `None` when there are no candidates.
+pub fn best_match(
+    rpc_interface_value: &Value,
+    candidates: &[(String, Value)],
+    opts: &InterfaceCompareOptions,
+) -> Option<(String, InterfaceCompareReport)> {
+    candidates
+        .iter()
+        .map(|(label, bytecode)| {
+            let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+                label,
+                rpc_interface_value,
+                bytecode,
+                opts.clone(),
+            );
+            (
+                label.clone(),
+                InterfaceCompareReport::new(label.clone(), summary, mismatches),
+            )
+        })
+        .min_by_key(|(_, report)| {
+            (
+                report.summary.error_mismatches,
+                report.summary.mismatches_total,
+            )
+        })
+}
+
+/// Rewrite every `address` string found in `map` (keys and values normalized) to its target.
+fn remap_addresses(v: &mut Value, map: &HashMap<String, String>) {
+    match v {
+        Value::Object(obj) => {
+            for (k, v) in obj.iter_mut() {
+                if k == "address" {
+                    if let Some(to) = v.as_str().and_then(|a| remap_address(a, map)) {
+                        *v = Value::String(to);
+                        continue;
+                    }
+                }
+                remap_addresses(v, map);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| remap_addresses(v, map)),
+        _ => {}
+    }
+}
+
+/// The target of `address` in `map`, if it has one.
+fn remap_address(address: &str, map: &HashMap<String, String>) -> Option<String> {
+    normalize_address_str(address)
+        .ok()
+        .and_then(|a| map.get(&a))
+        .cloned()
+}
+
+/// `remap_addresses` over a whole interface, including the places an address is not stored
+/// under an `address` key: `dependencies` entries, `linkage_table` keys and
+/// `<address>::<name>` module keys.
+fn remap_interface_addresses(interface: &mut Value, map: &HashMap<String, String>) {
+    remap_addresses(interface, map);
+    if let Some(deps) = interface
+        .get_mut("dependencies")
+        .and_then(Value::as_array_mut)
+    {
+        for dep in deps.iter_mut() {
+            if let Some(to) = dep.as_str().and_then(|a| remap_address(a, map)) {
+                *dep = Value::String(to);
+            }
+        }
+    }
+    let remap_keys = |obj: &mut serde_json::Map<String, Value>, qualified: bool| {
+        *obj = std::mem::take(obj)
+            .into_iter()
+            .map(|(key, v)| {
+                let remapped = if qualified {
+                    key.rsplit_once("::").and_then(|(addr, name)| {
+                        remap_address(addr, map).map(|to| format!("{to}::{name}"))
+                    })
+                } else {
+                    remap_address(&key, map)
+                };
+                (remapped.unwrap_or(key), v)
+            })
+            .collect();
+    };
+    if let Some(linkage) = interface
+        .get_mut("linkage_table")
+        .and_then(Value::as_object_mut)
+    {
+        remap_keys(linkage, false);
+    }
+    if let Some(modules) = interface.get_mut("modules").and_then(Value::as_object_mut) {
+        remap_keys(modules, true);
+    }
+}
+
+/// Compare the same package as deployed on two networks: `mainnet` in the RPC schema against
+/// `testnet` in `testnet_source`'s schema (an RPC-schema `testnet`, e.g. a second fetch, is
+/// converted with `rpc_interface_to_bytecode_schema`). Each `(mainnet, testnet)` pair in
+/// `address_map` (the package IDs, plus any dependency or framework addresses that differ) is
+/// treated as the same address, so only structural differences are reported.
+pub fn compare_cross_network(
+    mainnet: &Value,
+    testnet: &Value,
+    testnet_source: SchemaSource,
+    address_map: &[(String, String)],
+    opts: InterfaceCompareOptions,
+) -> (InterfaceCompareSummary, Vec<InterfaceCompareMismatch>) {
+    let map: HashMap<String, String> = address_map
+        .iter()
+        .filter_map(|(main, test)| {
+            let main = normalize_address_str(main).ok()?;
+            Some((normalize_address_str(test).ok()?, main))
+        })
+        .collect();
+    let mut testnet = match testnet_source {
+        SchemaSource::Rpc => rpc_interface_to_bytecode_schema(testnet),
+        SchemaSource::Bytecode => testnet.clone(),
+    };
+    remap_interface_addresses(&mut testnet, &map);
+    compare_interface_rpc_vs_bytecode("", mainnet, &testnet, opts)
+}
+
+/// Percentage (0–100) of compared field/param/return type sites whose types matched
+/// (`type_sites_compared` vs `type_sites_mismatched`). No compared sites scores 100.
+pub fn type_coverage(summary: &InterfaceCompareSummary) -> f64 {
+    if summary.type_sites_compared == 0 {
+        return 100.0;
+    }
+    let matched = summary
+        .type_sites_compared
+        .saturating_sub(summary.type_sites_mismatched);
+    matched as f64 * 100.0 / summary.type_sites_compared as f64
+}
+
+/// Interface match percentage (0–100) for dashboards:
+///
+/// `matched = (structs_compared - structs_with_errors) + (functions_compared - functions_with_errors)`
+/// `total = structs_compared + functions_compared + structs_missing_in_bytecode + structs_extra_in_bytecode
+///     + functions_missing_in_bytecode + functions_extra_in_bytecode + modules_missing_in_bytecode
+///     + modules_extra_in_bytecode`
+///
+/// A compared item counts once however many errors it has. Two empty interfaces score 100.
+pub fn similarity_score(summary: &InterfaceCompareSummary) -> f64 {
+    let total = summary.structs_compared
+        + summary.functions_compared
+        + summary.structs_missing_in_bytecode
+        + summary.structs_extra_in_bytecode
+        + summary.functions_missing_in_bytecode
+        + summary.functions_extra_in_bytecode
+        + summary.modules_missing_in_bytecode
+        + summary.modules_extra_in_bytecode;
+    if total == 0 {
+        return 100.0;
+    }
+    let matched = summary
+        .structs_compared
+        .saturating_sub(summary.structs_with_errors)
+        + summary
+            .functions_compared
+            .saturating_sub(summary.functions_with_errors);
+    matched as f64 * 100.0 / total as f64
+}
+
+/// Count modules/structs/functions on each side so callers can decide whether a full
+/// `compare_interface_rpc_vs_bytecode` walk is warranted.
+pub fn quick_shape_check(
+    rpc_interface_value: &Value,
+    bytecode_interface_value: &Value,
+) -> ShapeCheck {
+    let rpc = extract_sanity_counts(rpc_interface_value.get("modules").unwrap_or(&Value::Null));
+    let bytecode = extract_sanity_counts(
+        bytecode_interface_value
+            .get("modules")
+            .unwrap_or(&Value::Null),
+    );
+    ShapeCheck {
+        modules_match: rpc.modules == bytecode.modules,
+        structs_match: rpc.structs == bytecode.structs,
+        functions_match: rpc.functions == bytecode.functions,
+        rpc,
+        bytecode,
+    }
+}
+
+/// Compare the serialized sizes of two interfaces. A large `percent_diff` usually means one
+/// extraction is incomplete and is worth investigating before a full walk.
+pub fn quick_size_diff(rpc_interface_value: &Value, bytecode_interface_value: &Value) -> SizeDiff {
+    let len = |v: &Value| serde_json::to_vec(v).map(|b| b.len()).unwrap_or(0);
+    let rpc_bytes = len(rpc_interface_value);
+    let bytecode_bytes = len(bytecode_interface_value);
+    let larger = rpc_bytes.max(bytecode_bytes);
+    let percent_diff = if larger == 0 {
+        0.0
+    } else {
+        rpc_bytes.abs_diff(bytecode_bytes) as f64 * 100.0 / larger as f64
+    };
+    SizeDiff {
+        rpc_bytes,
+        bytecode_bytes,
+        percent_diff,
+    }
+}
+
+/// Check a single interface for internal consistency, without a counterpart to compare against:
+/// every field/param/return type must canonicalize, every `key` struct must start with
+/// `id: 0x2::object::UID`, and non-native structs must declare at least one field.
+pub fn validate_interface(
+    interface_value: &Value,
+    source: SchemaSource,
+    opts: InterfaceCompareOptions,
+) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = Vec::new();
+    let mut push_issue = |path: String, reason: String, value: Option<Value>| {
+        if issues.len() < opts.max_mismatches {
+            let value = if opts.include_values { value } else { None };
+            issues.push(ValidationIssue {
+                path,
+                reason,
+                value,
+            });
+        }
+    };
+
+    let (function_keys, params_key, returns_key): (&[&str], &str, &str) = match source {
+        SchemaSource::Rpc => (
+            &["exposedFunctions", "exposed_functions"],
+            "parameters",
+            "return",
+        ),
+        SchemaSource::Bytecode => (&["functions"], "params", "returns"),
+    };
+
+    let empty_modules = serde_json::Map::new();
+    let modules_value = interface_value.get("modules").unwrap_or(&Value::Null);
+    let modules = modules_value.as_object().unwrap_or(&empty_modules);
+    let mut module_names: Vec<&String> = modules.keys().collect();
+    module_names.sort();
+
+    for module_name in module_names {
+        let module = modules.get(module_name).unwrap_or(&Value::Null);
+
+        let structs = get_object(module, &["structs"])
+            .cloned()
+            .unwrap_or_default();
+        let mut struct_names: Vec<&String> = structs.keys().collect();
+        struct_names.sort();
+        for sname in struct_names {
+            let Some(struct_def) = structs.get(sname) else {
+                continue;
+            };
+            let fields = struct_def
+                .get("fields")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let is_native = struct_def
+                .get("is_native")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if fields.is_empty() && !is_native {
+                push_issue(
+                    format!("modules/{module_name}/structs/{sname}/fields"),
+                    "non-native struct has no fields".to_string(),
+                    struct_def.get("fields").cloned(),
+                );
+            }
+            if struct_has_key(struct_def)
+                && !fields.first().is_some_and(|f| field_is_uid(source, f))
+            {
+                push_issue(
+                    format!("modules/{module_name}/structs/{sname}/fields[0]"),
+                    "key struct does not start with `id: 0x2::object::UID`".to_string(),
+                    fields.first().cloned(),
+                );
+            }
+            let declared = struct_def
+                .get("abilities")
+                .map(ability_set_from_value)
+                .unwrap_or(AbilitySet::EMPTY);
+            for (i, field) in fields.iter().enumerate() {
+                let ty = field.get("type").unwrap_or(&Value::Null);
+                let canon = match type_to_canonical_json(source, ty) {
+                    Ok(canon) => canon,
+                    Err(e) => {
+                        push_issue(
+                            format!("modules/{module_name}/structs/{sname}/fields[{i}]/type"),
+                            format!("type parse error: {:#}", e),
+                            Some(ty.clone()),
+                        );
+                        continue;
+                    }
+                };
+                if !opts.check_field_abilities {
+                    continue;
+                }
+                let Some(effective) = effective_field_abilities(&canon, modules_value) else {
+                    continue;
+                };
+                for ability in declared {
+                    let required = ability.requires();
+                    if !effective.has_ability(required) {
+                        push_issue(
+                            format!("modules/{module_name}/structs/{sname}/fields[{i}]/abilities"),
+                            format!(
+                                "field lacks `{}` required by struct ability `{}`",
+                                ability_name(required),
+                                ability_name(ability)
+                            ),
+                            Some(canon.clone()),
+                        );
+                    }
+                }
+            }
+        }
+
+        let funcs = get_object(module, function_keys)
+            .cloned()
+            .unwrap_or_default();
+        let mut func_names: Vec<&String> = funcs.keys().collect();
+        func_names.sort();
+        for fname in func_names {
+            let Some(fun) = funcs.get(fname) else {
+                continue;
+            };
+            for (key, label) in [(params_key, "params"), (returns_key, "returns")] {
+                let types = fun
+                    .get(key)
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                for (i, ty) in types.iter().enumerate() {
+                    if let Err(e) = type_to_canonical_json(source, ty) {
+                        push_issue(
+                            format!("modules/{module_name}/functions/{fname}/{label}[{i}]"),
+                            format!("type parse error: {:#}", e),
+                            Some(ty.clone()),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+fn field_is_uid(source: SchemaSource, field: &Value) -> bool {
+    field.get("name").and_then(Value::as_str) == Some("id")
+        && field
+            .get("type")
+            .and_then(|ty| type_to_canonical_json(source, ty).ok())
+            .is_some_and(|canon| is_canonical_datatype(&canon, "0x2", "object", "UID"))
+}
+
+pub fn bytecode_module_check(
+    normalized_module_names: &[String],
+    bcs_module_names: &[String],
+) -> BytecodeModuleCheck {
+    let normalized_set: HashSet<&str> =
+        normalized_module_names.iter().map(|s| s.as_str()).collect();
+    let bcs_set: HashSet<&str> = bcs_module_names.iter().map(|s| s.as_str()).collect();
+
+    let mut missing_in_bcs: Vec<String> = normalized_module_names
+        .iter()
+        .filter(|name| !bcs_set.contains(name.as_str()))
+        .cloned()
+        .collect();
+    missing_in_bcs.sort();
+
+    let mut extra_in_bcs: Vec<String> = bcs_module_names
+        .iter()
+        .filter(|name| !normalized_set.contains(name.as_str()))
+        .cloned()
+        .collect();
+    extra_in_bcs.sort();
+
+    BytecodeModuleCheck {
         normalized_modules: normalized_module_names.len(),
         bcs_modules: bcs_module_names.len(),
         missing_in_bcs,
         extra_in_bcs,
     }
-}
+}
+
+pub fn module_set_diff(left: &[String], right: &[String]) -> ModuleSetDiff {
+    let left_set: HashSet<&str> = left.iter().map(|s| s.as_str()).collect();
+    let right_set: HashSet<&str> = right.iter().map(|s| s.as_str()).collect();
+
+    let mut missing_in_right: Vec<String> = left
+        .iter()
+        .filter(|name| !right_set.contains(name.as_str()))
+        .cloned()
+        .collect();
+    missing_in_right.sort();
+
+    let mut extra_in_right: Vec<String> = right
+        .iter()
+        .filter(|name| !left_set.contains(name.as_str()))
+        .cloned()
+        .collect();
+    extra_in_right.sort();
+
+    ModuleSetDiff {
+        left_count: left.len(),
+        right_count: right.len(),
+        missing_in_right,
+        extra_in_right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_interface_rpc_vs_bytecode_smoke_ok() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [],
+                            "fields": [
+                                {"name":"x", "type":"U64"}
+                            ]
+                        }
+                    },
+                    "exposedFunctions": {
+                        "f": {
+                            "visibility":"Public",
+                            "isEntry": false,
+                            "typeParameters": [],
+                            "parameters": ["U64"],
+                            "return": []
+                        }
+                    }
+                }
+            }
+        });
+
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "address": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    "structs": {
+                        "S": {
+                            "abilities": ["store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [{"name":"x", "type": {"kind":"u64"}}]
+                        }
+                    },
+                    "functions": {
+                        "f": {
+                            "visibility": "public",
+                            "is_entry": false,
+                            "is_native": false,
+                            "type_params": [],
+                            "params": [{"kind":"u64"}],
+                            "returns": [],
+                            "acquires": []
+                        }
+                    }
+                }
+            }
+        });
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.mismatches_total, 0, "{mismatches:#?}");
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_compare_interface_rpc_vs_bytecode_detects_type_mismatch() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [],
+                            "fields": [{"name":"x", "type":"U64"}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "address": "0x1",
+                    "structs": {
+                        "S": {
+                            "abilities": ["store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [{"name":"x", "type": {"kind":"u128"}}]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: false,
+                ..Default::default()
+            },
+        );
+        assert!(summary.mismatches_total > 0);
+        assert!(mismatches
+            .iter()
+            .any(|m| m.path.contains("/fields[0]/type")));
+        assert!(mismatches
+            .iter()
+            .all(|m| m.rpc.is_none() && m.bytecode.is_none()));
+    }
+
+    fn bytecode_uid_field() -> Value {
+        serde_json::json!({
+            "name": "id",
+            "type": {"kind": "datatype", "address": "0x2", "module": "object", "name": "UID", "type_args": []}
+        })
+    }
+
+    #[test]
+    fn test_validate_interface_clean() {
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Obj": {
+                            "abilities": ["key", "store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [bytecode_uid_field(), {"name": "x", "type": {"kind": "u64"}}]
+                        }
+                    },
+                    "functions": {
+                        "f": {
+                            "visibility": "public",
+                            "is_entry": false,
+                            "type_params": [],
+                            "params": [{"kind": "u64"}],
+                            "returns": [{"kind": "bool"}]
+                        }
+                    }
+                }
+            }
+        });
+
+        let issues = validate_interface(
+            &bytecode,
+            SchemaSource::Bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert!(issues.is_empty(), "{issues:#?}");
+    }
+
+    #[test]
+    fn test_validate_interface_field_abilities() {
+        let interface = |vault_field: Value| {
+            serde_json::json!({
+                "modules": {
+                    "m": {
+                        "address": "0x1",
+                        "structs": {
+                            "Receipt": {
+                                "abilities": ["drop"],
+                                "type_params": [],
+                                "is_native": false,
+                                "fields": [{"name": "amount", "type": {"kind": "u64"}}]
+                            },
+                            "Coin": {
+                                "abilities": ["store"],
+                                "type_params": [{"constraints": [], "is_phantom": true}],
+                                "is_native": false,
+                                "fields": [{"name": "value", "type": {"kind": "u64"}}]
+                            },
+                            "Vault": {
+                                "abilities": ["key"],
+                                "type_params": [],
+                                "is_native": false,
+                                "fields": [bytecode_uid_field(), vault_field]
+                            }
+                        },
+                        "functions": {}
+                    }
+                }
+            })
+        };
+        let datatype = |name: &str, type_args: Value| serde_json::json!({"kind": "datatype", "address": "0x1", "module": "m", "name": name, "type_args": type_args});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            check_field_abilities: true,
+            ..Default::default()
+        };
+
+        let legal = interface(serde_json::json!({
+            "name": "coins",
+            "type": {"kind": "vector", "type": datatype("Coin", serde_json::json!([datatype("Receipt", serde_json::json!([]))]))}
+        }));
+        assert!(validate_interface(&legal, SchemaSource::Bytecode, opts.clone()).is_empty());
+
+        let illegal = interface(serde_json::json!({
+            "name": "receipt",
+            "type": datatype("Receipt", serde_json::json!([]))
+        }));
+        let issues = validate_interface(&illegal, SchemaSource::Bytecode, opts.clone());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].path,
+            "modules/m/structs/Vault/fields[1]/abilities"
+        );
+        assert_eq!(
+            issues[0].reason,
+            "field lacks `store` required by struct ability `key`"
+        );
+
+        let unchecked = InterfaceCompareOptions {
+            check_field_abilities: false,
+            ..opts
+        };
+        assert!(validate_interface(&illegal, SchemaSource::Bytecode, unchecked).is_empty());
+    }
+
+    #[test]
+    fn test_validate_interface_reports_unparseable_field_type() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [],
+                            "fields": [{"name": "x", "type": "U63"}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+
+        let issues = validate_interface(
+            &rpc,
+            SchemaSource::Rpc,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert_eq!(issues[0].path, "modules/m/structs/S/fields[0]/type");
+        assert_eq!(issues[0].value, Some(serde_json::json!("U63")));
+    }
+
+    #[test]
+    fn test_compare_interface_redacts_values_at_matching_paths() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [],
+                            "fields": [{"name":"secret", "type":"U64"}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {
+                            "abilities": ["store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [{"name":"secret", "type": {"kind":"u128"}}]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                redact_paths: vec!["modules/*/structs/S/fields[*]".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.mismatches_total, 1);
+        assert_eq!(mismatches[0].path, "modules/m/structs/S/fields[0]/type");
+        assert_eq!(mismatches[0].rpc, Some(serde_json::json!("<redacted>")));
+        assert_eq!(
+            mismatches[0].bytecode,
+            Some(serde_json::json!("<redacted>"))
+        );
+    }
+
+    fn single_function_interfaces(
+        rpc_vis: &str,
+        rpc_entry: bool,
+        byte_vis: &str,
+        byte_entry: bool,
+    ) -> (Value, Value) {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {},
+                    "exposedFunctions": {
+                        "f": {
+                            "visibility": rpc_vis,
+                            "isEntry": rpc_entry,
+                            "typeParameters": [],
+                            "parameters": [],
+                            "return": []
+                        }
+                    }
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {},
+                    "functions": {
+                        "f": {
+                            "visibility": byte_vis,
+                            "is_entry": byte_entry,
+                            "type_params": [],
+                            "params": [],
+                            "returns": []
+                        }
+                    }
+                }
+            }
+        });
+        (rpc, bytecode)
+    }
+
+    #[test]
+    fn test_function_callability_classes() {
+        use FunctionCallability::*;
+        assert_eq!(function_callability("public", true), Entrypoint);
+        assert_eq!(function_callability("public", false), Public);
+        assert_eq!(function_callability("friend", true), EntryOnly);
+        assert_eq!(function_callability("private", false), Internal);
+    }
+
+    #[test]
+    fn test_compare_interface_reports_callability_class_change() {
+        let (rpc, bytecode) = single_function_interfaces("Public", true, "private", true);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        let m = mismatches
+            .iter()
+            .find(|m| m.path == "modules/m/functions/f/callability")
+            .expect("callability mismatch");
+        assert_eq!(m.reason, "callability class changed");
+        assert_eq!(m.rpc, Some(serde_json::json!("entrypoint")));
+        assert_eq!(m.bytecode, Some(serde_json::json!("entry_only")));
+        // Only the visibility change counts against the function; the class is derived from it.
+        assert_eq!(summary.function_mismatches, 1);
+        assert_eq!(summary.error_mismatches, 2);
+
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        let (summary, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(summary.mismatches_total, 0);
+    }
+
+    #[test]
+    fn test_compare_interface_cancel_stops_walk() {
+        let module = serde_json::json!({"structs": {}, "exposedFunctions": {}, "functions": {}});
+        let iface = serde_json::json!({"modules": {"a": module.clone(), "b": module}});
+        let cancel = Arc::new(AtomicBool::new(false));
+        let opts = InterfaceCompareOptions {
+            cancel: Some(cancel.clone()),
+            ..Default::default()
+        };
+
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &iface, &iface, opts.clone());
+        assert!(summary.complete);
+        assert_eq!(summary.modules_compared, 2);
+
+        cancel.store(true, Ordering::Relaxed);
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &iface, &iface, opts.clone());
+        assert!(!summary.complete);
+        assert_eq!(summary.modules_compared, 0);
+
+        // Cancelled mid-walk: the module in progress finishes, the rest are skipped.
+        cancel.store(false, Ordering::Relaxed);
+        let module = serde_json::json!({"structs": {}, "exposedFunctions": {}, "functions": {}});
+        let iface =
+            serde_json::json!({"modules": {"a": module.clone(), "b": module.clone(), "c": module}});
+        let unchanged = HashSet::new();
+        let flip = |_: &str| cancel.store(true, Ordering::Relaxed);
+        let mut walk = Walk::new(&opts, None, SchemaSource::Rpc, &unchanged, &iface, &iface);
+        walk.after_module = Some(&flip);
+        let complete = run_walk(&mut walk, "0x1", &iface, &iface);
+        let (summary, _, _) = walk.finish(complete);
+        assert!(!summary.complete);
+        assert_eq!(summary.modules_compared, 1);
+    }
+
+    #[test]
+    fn test_compare_interface_matches_struct_names_with_arity_suffix() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "table": {
+                    "structs": {
+                        "Table": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [
+                                {"constraints": {"abilities": []}, "isPhantom": true},
+                                {"constraints": {"abilities": []}, "isPhantom": true}
+                            ],
+                            "fields": [{"name": "size", "type": "U64"}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "table": {
+                    "structs": {
+                        "Table<2>": {
+                            "abilities": ["store"],
+                            "type_params": [
+                                {"constraints": [], "is_phantom": true},
+                                {"constraints": [], "is_phantom": true}
+                            ],
+                            "is_native": false,
+                            "fields": [{"name": "size", "type": {"kind": "u64"}}]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.structs_compared, 1);
+        assert_eq!(summary.mismatches_total, 0, "{mismatches:#?}");
+    }
+
+    #[test]
+    fn test_compare_interface_accessor_field_warning() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Pool": {
+                            "abilities": { "abilities": ["Store"] },
+                            "typeParameters": [],
+                            "fields": [{"name": "reserve", "type": "U64"}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Pool": {
+                            "abilities": ["store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [{"name": "reserves", "type": {"kind": "u64"}}]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+
+        let run = |check_accessor_fields| {
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                &bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    check_accessor_fields,
+                    ..Default::default()
+                },
+            )
+            .1
+        };
+
+        let mismatches = run(true);
+        let warning = mismatches
+            .iter()
+            .find(|m| m.path == "modules/m/structs/Pool/accessors")
+            .expect("accessor warning");
+        assert_eq!(warning.reason, "accessor-affecting field change");
+        assert_eq!(warning.severity, MismatchSeverity::Warning);
+
+        assert!(run(false)
+            .iter()
+            .all(|m| m.severity == MismatchSeverity::Error));
+    }
+
+    #[test]
+    fn test_compare_interface_reports_duplicate_field_names() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Coin": {
+                            "abilities": {"abilities": ["Store"]},
+                            "typeParameters": [],
+                            "fields": [
+                                {"name": "amount", "type": "U64"},
+                                {"name": "owner", "type": "Address"}
+                            ]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Coin": {
+                            "abilities": ["store"],
+                            "type_params": [],
+                            "is_native": false,
+                            "fields": [
+                                {"name": "amount", "type": {"kind": "u64"}},
+                                {"name": "amount", "type": {"kind": "address"}}
+                            ]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.mismatches_total, 1);
+        assert_eq!(mismatches[0].path, "modules/m/structs/Coin/fields");
+        assert_eq!(mismatches[0].reason, "duplicate field name");
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Error);
+        assert_eq!(mismatches[0].rpc, None);
+        assert_eq!(mismatches[0].bytecode, Some(serde_json::json!(["amount"])));
+    }
+
+    #[test]
+    fn test_summary_exit_code_by_fail_on_level() {
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        let (clean, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(clean.exit_code(MismatchSeverity::Info), 0);
+
+        let warnings_only = InterfaceCompareSummary {
+            mismatches_total: 1,
+            warning_mismatches: 1,
+            ..clean
+        };
+        assert_eq!(warnings_only.exit_code(MismatchSeverity::Error), 0);
+        assert_eq!(warnings_only.exit_code(MismatchSeverity::Warning), 2);
+
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "friend", false);
+        let (with_errors, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert!(with_errors.error_mismatches > 0);
+        assert_eq!(with_errors.exit_code(MismatchSeverity::Error), 1);
+        assert_eq!(with_errors.exit_code(MismatchSeverity::Info), 1);
+    }
+
+    #[test]
+    fn test_compare_interface_function_name_filter() {
+        let rpc_fun = serde_json::json!({
+            "visibility": "Public",
+            "isEntry": false,
+            "typeParameters": [],
+            "parameters": ["U64"],
+            "return": []
+        });
+        let byte_fun = |kind: &str| {
+            serde_json::json!({
+                "visibility": "public",
+                "is_entry": false,
+                "type_params": [],
+                "params": [{"kind": kind}],
+                "returns": []
+            })
+        };
+        let rpc = serde_json::json!({
+            "modules": {"pool": {"structs": {}, "exposedFunctions": {
+                "swap_a_b": rpc_fun, "swap_b_a": rpc_fun, "deposit": rpc_fun
+            }}}
+        });
+        let bytecode = serde_json::json!({
+            "modules": {"pool": {"structs": {}, "functions": {
+                "swap_a_b": byte_fun("u64"), "swap_b_a": byte_fun("u64"), "deposit": byte_fun("u128")
+            }}}
+        });
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                function_name_filter: Some("swap*".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.functions_compared, 2);
+        assert!(mismatches.is_empty());
+
+        let (summary, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(summary.functions_compared, 3);
+        assert_eq!(summary.function_mismatches, 1);
+    }
+
+    #[test]
+    fn test_compare_interface_reports_module_name_key_mismatch() {
+        let rpc = serde_json::json!({"modules": {"pool": {"structs": {}, "exposedFunctions": {}}}});
+        let bytecode = serde_json::json!({
+            "modules": {"pool": {"name": "pools", "structs": {}, "functions": {}}}
+        });
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.error_mismatches, 1);
+        assert_eq!(mismatches[0].path, "modules/pool/name");
+        assert_eq!(mismatches[0].reason, "module name/key mismatch");
+        assert_eq!(mismatches[0].bytecode, Some(serde_json::json!("pools")));
+    }
+
+    #[test]
+    fn test_compare_interface_tx_context_gained_and_lost() {
+        let rpc_ctx = serde_json::json!({"MutableReference": {"Struct": {
+            "address": "0x2", "module": "tx_context", "name": "TxContext", "typeArguments": []
+        }}});
+        let byte_ctx = serde_json::json!({"kind": "ref", "mutable": true, "to": {
+            "kind": "datatype", "address": "0x2", "module": "tx_context", "name": "TxContext",
+            "type_args": []
+        }});
+        let interfaces = |rpc_params: Vec<Value>, byte_params: Vec<Value>| {
+            let (mut rpc, mut bytecode) =
+                single_function_interfaces("Public", false, "public", false);
+            rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = Value::from(rpc_params);
+            bytecode["modules"]["m"]["functions"]["f"]["params"] = Value::from(byte_params);
+            (rpc, bytecode)
+        };
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            check_tx_context: true,
+            include_values: true,
+            ..Default::default()
+        };
+        let run = |rpc_params: Vec<Value>, byte_params: Vec<Value>| {
+            let (rpc, bytecode) = interfaces(rpc_params, byte_params);
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone()).1
+        };
+
+        let gained = run(vec![], vec![byte_ctx.clone()]);
+        let ctx: Vec<_> = gained
+            .iter()
+            .filter(|m| m.path == "modules/m/functions/f/tx_context")
+            .collect();
+        assert_eq!(ctx.len(), 1);
+        assert_eq!(ctx[0].reason, "TxContext parameter added");
+        assert_eq!(ctx[0].severity, MismatchSeverity::Warning);
+
+        let lost = run(vec![rpc_ctx.clone()], vec![]);
+        assert!(lost
+            .iter()
+            .any(|m| m.path.ends_with("/tx_context") && m.reason == "TxContext parameter removed"));
+
+        // With bytecode as the reference, the node serving no TxContext is a removal.
+        let (rpc, bytecode) = interfaces(vec![], vec![byte_ctx.clone()]);
+        let (_, reverse) = compare_interface_bytecode_vs_rpc("0x1", &bytecode, &rpc, opts.clone());
+        let ctx = reverse
+            .iter()
+            .find(|m| m.kind == MismatchKind::TxContextChanged)
+            .unwrap();
+        assert_eq!(ctx.reason, "TxContext parameter removed");
+        assert_eq!(
+            (ctx.rpc.clone(), ctx.bytecode.clone()),
+            (Some(Value::Bool(false)), Some(Value::Bool(true)))
+        );
+
+        let kept = run(vec![rpc_ctx], vec![byte_ctx]);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_compare_interface_extra_functions_ignore_private_when_rpc_public_only() {
+        let (rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        let byte_fun = |visibility: &str| {
+            serde_json::json!({
+                "visibility": visibility,
+                "is_entry": false,
+                "type_params": [],
+                "params": [],
+                "returns": []
+            })
+        };
+        let functions = &mut bytecode["modules"]["m"]["functions"];
+        functions["helper"] = byte_fun("private");
+        functions["internal"] = byte_fun("friend");
+        functions["added"] = byte_fun("public");
+
+        let run = |report_extra_functions, report_private_extra_functions| {
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                &bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    report_extra_functions,
+                    report_private_extra_functions,
+                    ..Default::default()
+                },
+            )
+            .1
+        };
+        let mismatches = run(true, false);
+        let paths: Vec<&str> = mismatches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["modules/m/functions/added"]);
+        assert_eq!(mismatches[0].reason, "extra function in bytecode");
+        assert!(run(false, false).is_empty());
+        assert!(run(false, true).is_empty());
+
+        let mismatches = run(true, true);
+        let found: Vec<(&str, MismatchSeverity)> = mismatches
+            .iter()
+            .map(|m| (m.path.as_str(), m.severity))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("modules/m/functions/added", MismatchSeverity::Error),
+                ("modules/m/functions/helper", MismatchSeverity::Info),
+                ("modules/m/functions/internal", MismatchSeverity::Info),
+            ]
+        );
+    }
+
+    fn key_struct_interfaces(rpc_abilities: &[&str], byte_abilities: &[&str]) -> (Value, Value) {
+        let rpc = serde_json::json!({
+            "modules": {"m": {"structs": {"Ticket": {
+                "abilities": {"abilities": rpc_abilities},
+                "typeParameters": [],
+                "fields": [{"name": "id", "type": {"Struct": {
+                    "address": "0x2", "module": "object", "name": "UID", "typeArguments": []
+                }}}]
+            }}, "exposedFunctions": {}}}
+        });
+        let bytecode = serde_json::json!({
+            "modules": {"m": {"structs": {"Ticket": {
+                "abilities": byte_abilities,
+                "type_params": [],
+                "is_native": false,
+                "fields": [bytecode_uid_field()]
+            }}, "functions": {}}}
+        });
+        (rpc, bytecode)
+    }
+
+    #[test]
+    fn test_compare_interface_public_transfer_gained_and_lost() {
+        let run = |rpc_abilities: &[&str], byte_abilities: &[&str]| {
+            let (rpc, bytecode) = key_struct_interfaces(rpc_abilities, byte_abilities);
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                &bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    ..Default::default()
+                },
+            )
+            .1
+        };
+        let transfer = |mismatches: &[InterfaceCompareMismatch]| {
+            mismatches
+                .iter()
+                .find(|m| m.path == "modules/m/structs/Ticket/public_transfer")
+                .map(|m| (m.kind, m.reason.clone()))
+        };
+
+        let gained = run(&["Key"], &["key", "store"]);
+        assert_eq!(
+            transfer(&gained),
+            Some((
+                MismatchKind::PublicTransferGained,
+                "public transfer gained".to_string()
+            ))
+        );
+        let lost = run(&["Key", "Store"], &["key"]);
+        assert_eq!(
+            transfer(&lost),
+            Some((
+                MismatchKind::PublicTransferLost,
+                "public transfer lost".to_string()
+            ))
+        );
+        let report = crate::compat::upgrade_report(&lost);
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path.ends_with("/public_transfer")
+                && c.compatibility == crate::compat::Compatibility::Breaking));
+        assert!(crate::compat::upgrade_report(&gained)
+            .changes
+            .iter()
+            .any(|c| c.path.ends_with("/public_transfer")
+                && c.compatibility == crate::compat::Compatibility::Compatible));
+
+        assert_eq!(transfer(&run(&["Store"], &[])), None);
+    }
+
+    #[test]
+    fn test_compare_interface_reports_deepest_nested_generic_difference() {
+        // Table<ID, vector<Balance<T>>> with T differing only at the innermost argument.
+        let rpc_struct = |module: &str, name: &str, args: Value| serde_json::json!({"Struct": {"address": "0x2", "module": module, "name": name, "typeArguments": args}});
+        let byte_struct = |module: &str, name: &str, args: Value| serde_json::json!({"kind": "datatype", "address": "0x2", "module": module, "name": name, "type_args": args});
+        let rpc_table = |coin: &str| {
+            rpc_struct(
+                "table",
+                "Table",
+                serde_json::json!([
+                    rpc_struct("object", "ID", serde_json::json!([])),
+                    {"Vector": rpc_struct("balance", "Balance", serde_json::json!([rpc_struct("sui", coin, serde_json::json!([]))]))}
+                ]),
+            )
+        };
+        let byte_table = |coin: &str| {
+            byte_struct(
+                "table",
+                "Table",
+                serde_json::json!([
+                    byte_struct("object", "ID", serde_json::json!([])),
+                    {"kind": "vector", "type": byte_struct("balance", "Balance", serde_json::json!([byte_struct("sui", coin, serde_json::json!([]))]))}
+                ]),
+            )
+        };
+        let interfaces = |rpc_coin: &str, byte_coin: &str| {
+            let rpc = serde_json::json!({"modules": {"m": {"structs": {"Vault": {
+                "abilities": {"abilities": ["Store"]},
+                "typeParameters": [],
+                "fields": [{"name": "balances", "type": rpc_table(rpc_coin)}]
+            }}, "exposedFunctions": {}}}});
+            let bytecode = serde_json::json!({"modules": {"m": {"structs": {"Vault": {
+                "abilities": ["store"],
+                "type_params": [],
+                "is_native": false,
+                "fields": [{"name": "balances", "type": byte_table(byte_coin)}]
+            }}, "functions": {}}}});
+            (rpc, bytecode)
+        };
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            ..Default::default()
+        };
+
+        let (rpc, bytecode) = interfaces("SUI", "SUI");
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.mismatches_total, 0);
+
+        let (rpc, bytecode) = interfaces("SUI", "USDC");
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].path,
+            "modules/m/structs/Vault/fields[0]/type/type_args[1]/type/type_args[0]"
+        );
+        assert_eq!(mismatches[0].rpc.as_ref().unwrap()["name"], "SUI");
+        assert_eq!(mismatches[0].bytecode.as_ref().unwrap()["name"], "USDC");
+    }
+
+    #[test]
+    fn test_type_parse_errors_name_the_failing_side() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!(["U64"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "u512"}]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::BytecodeTypeParseError);
+        assert!(mismatches[0]
+            .reason
+            .starts_with("bytecode type parse error"));
+    }
+
+    #[test]
+    fn test_compare_interface_unknown_type_policy() {
+        let unknown = serde_json::json!({"Enum128": {"variants": 3}});
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([unknown.clone(), "U64"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([unknown, {"kind": "u64"}]);
+        let run = |unknown_type_policy| {
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                &bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    unknown_type_policy,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let (summary, mismatches) = run(UnknownTypePolicy::Error);
+        assert_eq!(summary.error_mismatches, 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::RpcTypeParseError);
+        assert!(mismatches[0].reason.starts_with("rpc type parse error"));
+
+        let (summary, mismatches) = run(UnknownTypePolicy::Warn);
+        assert_eq!(summary.error_mismatches, 0);
+        assert_eq!(summary.warning_mismatches, 1);
+        assert_eq!(mismatches[0].path, "modules/m/functions/f/params[0]");
+
+        let (summary, _) = run(UnknownTypePolicy::PassThrough);
+        assert_eq!(summary.mismatches_total, 0);
+        assert_eq!(summary.functions_compared, 1);
+    }
+
+    #[test]
+    fn test_compare_interface_param_names_under_flag() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!(["U64", "U64"]);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameterNames"] =
+            serde_json::json!(["amount", "min_out"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "u64"}, {"kind": "u64"}]);
+        bytecode["modules"]["m"]["functions"]["f"]["param_names"] =
+            serde_json::json!(["amount", "min_amount_out"]);
+        let run = |bytecode: &Value, compare_param_names| {
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    compare_param_names,
+                    ..Default::default()
+                },
+            )
+            .1
+        };
+
+        let mismatches = run(&bytecode, true);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "modules/m/functions/f/params[1]/name");
+        assert_eq!(mismatches[0].reason, "parameter name mismatch");
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Warning);
+        assert!(run(&bytecode, false).is_empty());
+
+        bytecode["modules"]["m"]["functions"]["f"]
+            .as_object_mut()
+            .unwrap()
+            .remove("param_names");
+        assert!(run(&bytecode, true).is_empty());
+    }
+
+    #[test]
+    fn test_similarity_score() {
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        let (perfect, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(similarity_score(&perfect), 100.0);
+
+        let (diverged, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &serde_json::json!({"modules": {"other": {"structs": {}, "functions": {}}}}),
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(similarity_score(&diverged), 0.0);
+
+        let partial = InterfaceCompareSummary {
+            structs_compared: 1,
+            functions_compared: 3,
+            function_mismatches: 1,
+            functions_with_errors: 1,
+            ..perfect
+        };
+        assert_eq!(similarity_score(&partial), 75.0);
+
+        // Three errors in one function cost it once; the bytecode-only struct joins the total.
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["g"] =
+            rpc["modules"]["m"]["exposedFunctions"]["f"].clone();
+        bytecode["modules"]["m"]["functions"]["g"] =
+            bytecode["modules"]["m"]["functions"]["f"].clone();
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!(["U64", "U8", "Bool"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "u8"}, {"kind": "u64"}, {"kind": "address"}]);
+        bytecode["modules"]["m"]["structs"] = serde_json::json!({"Extra": {
+            "abilities": [], "type_params": [], "is_native": false, "fields": []
+        }});
+        let (summary, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(summary.function_mismatches, 3);
+        assert_eq!(summary.functions_with_errors, 1);
+        assert_eq!(summary.structs_extra_in_bytecode, 1);
+        assert!((similarity_score(&summary) - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quick_shape_check_flags_function_count_difference() {
+        let (rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        bytecode["modules"]["m"]["functions"]["helper"] =
+            bytecode["modules"]["m"]["functions"]["f"].clone();
+
+        let shape = quick_shape_check(&rpc, &bytecode);
+        assert_eq!((shape.rpc.modules, shape.bytecode.modules), (1, 1));
+        assert_eq!((shape.rpc.functions, shape.bytecode.functions), (1, 2));
+        assert!(shape.modules_match);
+        assert!(shape.structs_match);
+        assert!(!shape.functions_match);
+    }
+
+    #[test]
+    fn test_quick_size_diff_percentage() {
+        let rpc = serde_json::json!({"modules": {}});
+        let bytecode = serde_json::json!({"modules": {"m": {"x": 1234}}});
+        let diff = quick_size_diff(&rpc, &bytecode);
+        assert_eq!((diff.rpc_bytes, diff.bytecode_bytes), (14, 28));
+        assert_eq!(diff.percent_diff, 50.0);
+        assert_eq!(quick_size_diff(&rpc, &rpc).percent_diff, 0.0);
+    }
+
+    #[test]
+    fn test_module_breakdown_clean_modules() {
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        let (_, mut bad_bytecode) = single_function_interfaces("Public", false, "friend", false);
+        let mut rpc = rpc;
+        rpc["modules"]["n"] = rpc["modules"]["m"].clone();
+        bad_bytecode["modules"]["n"] = bad_bytecode["modules"]["m"].clone();
+        bad_bytecode["modules"]["m"] = bytecode["modules"]["m"].clone();
+
+        let breakdown = module_breakdown(
+            "0x1",
+            &rpc,
+            &bad_bytecode,
+            &InterfaceCompareOptions::default(),
+        );
+        assert_eq!(breakdown.modules.len(), 2);
+        assert_eq!(breakdown.clean_modules(), vec!["m".to_string()]);
+        assert!(breakdown.modules[1].mismatches > 0);
+    }
+
+    #[test]
+    fn test_module_breakdown_uses_walk_module_keys() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["address"] = serde_json::json!("0x1");
+        bytecode["modules"]["M"] = bytecode["modules"]["m"].take();
+        bytecode["modules"].as_object_mut().unwrap().remove("m");
+        bytecode["modules"]["M"]["address"] = serde_json::json!("0x1");
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            key_modules_by_address: true,
+            case_insensitive_names: true,
+            ..Default::default()
+        };
+        let (summary, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.modules_compared, 1);
+        let key = format!("{}::m", normalize_address_str("0x1").unwrap());
+        assert!(mismatches
+            .iter()
+            .all(|m| m.path.starts_with(&format!("modules/{key}"))));
+
+        let breakdown = module_breakdown("0x1", &rpc, &bytecode, &opts);
+        assert_eq!(breakdown.modules.len(), 1);
+        assert_eq!(breakdown.modules[0].module, key);
+        assert_eq!(breakdown.modules[0].functions_compared, 1);
+    }
+
+    #[test]
+    fn test_compare_interface_accept_paths_marks_mismatch_accepted() {
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "friend", false);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                accept_paths: vec!["modules/m/functions/f/visibility".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].accepted);
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Info);
+        assert_eq!(summary.mismatches_total, 1);
+        assert_eq!(summary.error_mismatches, 0);
+        assert_eq!(summary.accepted_mismatches, 1);
+        assert_eq!(summary.exit_code(MismatchSeverity::Warning), 0);
+    }
+
+    #[test]
+    fn test_compare_interface_ignore_paths_suppresses_mismatch() {
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "friend", true);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (baseline, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(baseline.error_mismatches >= 2);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                ignore_paths: vec!["modules/*/functions/*/visibility".to_string()],
+                ..opts
+            },
+        );
+        assert!(mismatches.iter().all(|m| !m.path.ends_with("/visibility")));
+        assert_eq!(summary.ignored_mismatches, 1);
+        assert_eq!(summary.mismatches_total, baseline.mismatches_total - 1);
+        assert_eq!(summary.item_mismatches, baseline.item_mismatches - 1);
+        assert_eq!(
+            summary.function_mismatches,
+            baseline.function_mismatches - 1
+        );
+        assert_eq!(summary.error_mismatches, baseline.error_mismatches - 1);
+        assert_eq!(mismatches.len(), summary.mismatches_total);
+    }
+
+    #[test]
+    fn test_compare_interface_ignore_paths_leaves_no_count_behind() {
+        let (mut rpc, mut bytecode) = receipt_interfaces(&["Drop"], &["drop"]);
+        rpc["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] =
+            serde_json::json!("U128");
+        rpc["modules"]["gone"] = serde_json::json!({"structs": {}, "exposedFunctions": {}});
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] =
+            serde_json::json!({"kind": "u64"});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ignore_paths: vec![
+                "modules/gone".to_string(),
+                "modules/flash/structs/Receipt/fields*".to_string(),
+            ],
+            ..Default::default()
+        };
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+        assert_eq!(summary.ignored_mismatches, 2);
+        assert_eq!(summary.mismatches_total, 0);
+        assert_eq!(summary.modules_missing_in_bytecode, 0);
+        assert_eq!(summary.module_set_mismatches, 0);
+        assert_eq!(summary.struct_mismatches, 0);
+        assert_eq!(summary.type_sites_compared, 1);
+        assert_eq!(summary.type_sites_mismatched, 0);
+    }
+
+    #[test]
+    fn test_compare_bytecode_vs_rpc_mirrors_direction() {
+        let (mut rpc, bytecode) = single_function_interfaces("Public", false, "friend", false);
+        rpc["modules"]["n"] = rpc["modules"]["m"].clone();
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            ..Default::default()
+        };
+        let (forward_summary, forward) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        let (summary, mismatches) = compare_interface_bytecode_vs_rpc("0x1", &bytecode, &rpc, opts);
+
+        assert_eq!(forward_summary.modules_missing_in_bytecode, 1);
+        assert_eq!(summary.modules_extra_in_rpc, 1);
+        assert_eq!(summary.modules_missing_in_rpc, 0);
+        assert_eq!(summary.mismatches_total, forward_summary.mismatches_total);
+
+        let module = mismatches.iter().find(|m| m.path == "modules/n").unwrap();
+        assert_eq!(module.kind, MismatchKind::ModuleExtra);
+        assert_eq!(module.reason, "extra module in rpc");
+
+        let visibility = |ms: &[InterfaceCompareMismatch]| {
+            ms.iter()
+                .find(|m| m.kind == MismatchKind::VisibilityMismatch)
+                .map(|m| (m.rpc.clone(), m.bytecode.clone()))
+                .unwrap()
+        };
+        // Value slots stay tied to their source; only the direction of the finding flips.
+        assert_eq!(visibility(&mismatches), visibility(&forward));
+        let (rpc_value, _) = visibility(&mismatches);
+        assert_eq!(rpc_value, Some(Value::String("Public".to_string())));
+    }
+
+    #[test]
+    fn test_compare_bytecode_vs_rpc_reports_bytecode_only_items() {
+        let (rpc, mut bytecode) = receipt_interfaces(&["Drop"], &["drop"]);
+        bytecode["modules"]["flash"]["structs"]["Loan"] =
+            bytecode["modules"]["flash"]["structs"]["Receipt"].clone();
+        bytecode["modules"]["flash"]["functions"]["helper"] = serde_json::json!({
+            "visibility": "private", "is_entry": false, "is_native": false,
+            "type_params": [], "params": [], "returns": []
+        });
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, mismatches) = compare_interface_bytecode_vs_rpc("0x1", &bytecode, &rpc, opts);
+        let found: Vec<(MismatchKind, MismatchSeverity, &str, &str)> = mismatches
+            .iter()
+            .map(|m| (m.kind, m.severity, m.path.as_str(), m.reason.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    MismatchKind::StructMissing,
+                    MismatchSeverity::Error,
+                    "modules/flash/structs/Loan",
+                    "struct missing in rpc"
+                ),
+                (
+                    MismatchKind::FunctionMissing,
+                    MismatchSeverity::Info,
+                    "modules/flash/functions/helper",
+                    "function missing in rpc"
+                ),
+            ]
+        );
+        assert_eq!(summary.struct_mismatches, 1);
+    }
+
+    #[test]
+    fn test_compare_bytecode_vs_rpc_orients_ability_signals() {
+        // The node serves `Receipt` without `store`: a loss relative to the bytecode.
+        let (rpc, bytecode) = receipt_interfaces(&["Key"], &["key", "store"]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            ..Default::default()
+        };
+        let (_, mismatches) = compare_interface_bytecode_vs_rpc("0x1", &bytecode, &rpc, opts);
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MismatchKind::AbilitiesMismatch,
+                MismatchKind::DynamicFieldStoreLost,
+                MismatchKind::PublicTransferLost,
+            ]
+        );
+        assert!(mismatches
+            .iter()
+            .all(|m| crate::compat::classify(m) == crate::compat::Compatibility::Breaking));
+        let transfer = &mismatches[2];
+        assert_eq!(transfer.reason, "public transfer lost");
+        assert_eq!(
+            (transfer.rpc.clone(), transfer.bytecode.clone()),
+            (Some(Value::Bool(false)), Some(Value::Bool(true)))
+        );
+
+        let (rpc, bytecode) = receipt_interfaces(&["Drop"], &[]);
+        let (_, mismatches) = compare_interface_bytecode_vs_rpc(
+            "0x1",
+            &bytecode,
+            &rpc,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert!(mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::HotPotatoChanged
+                && m.reason == "hot potato gained drop"));
+    }
+
+    #[test]
+    fn test_trace_records_each_visited_item() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", true);
+        rpc["modules"]["m"]["structs"]["S"] = serde_json::json!({
+            "abilities": {"abilities": ["Drop"]},
+            "typeParameters": [],
+            "fields": [{"name": "x", "type": "U64"}]
+        });
+        bytecode["modules"]["m"]["structs"]["S"] = serde_json::json!({
+            "abilities": ["drop"],
+            "type_params": [],
+            "is_native": false,
+            "fields": [{"name": "x", "type": {"kind": "u64"}}]
+        });
+        rpc["modules"]["gone"] = serde_json::json!({"structs": {}, "exposedFunctions": {}});
+
+        let mut trace = Vec::new();
+        let (summary, _) = compare_interface_rpc_vs_bytecode_traced(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+            &mut trace,
+        );
+        assert_eq!(
+            trace,
+            vec![
+                "module gone: mismatch (missing in bytecode)",
+                "struct m::S: match",
+                "function m::f: mismatch",
+                "module m: mismatch",
+            ]
+        );
+
+        let (untraced, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(untraced.mismatches_total, summary.mismatches_total);
+    }
+
+    fn receipt_interfaces(rpc_abilities: &[&str], byte_abilities: &[&str]) -> (Value, Value) {
+        let rpc = serde_json::json!({
+            "modules": {"flash": {"structs": {"Receipt": {
+                "abilities": {"abilities": rpc_abilities},
+                "typeParameters": [],
+                "fields": [{"name": "amount", "type": "U64"}]
+            }}, "exposedFunctions": {}}}
+        });
+        let bytecode = serde_json::json!({
+            "modules": {"flash": {"structs": {"Receipt": {
+                "abilities": byte_abilities,
+                "type_params": [],
+                "is_native": false,
+                "fields": [{"name": "amount", "type": {"kind": "u64"}}]
+            }}, "functions": {}}}
+        });
+        (rpc, bytecode)
+    }
+
+    #[test]
+    fn test_derived_struct_signals_do_not_count_as_struct_mismatches() {
+        let (rpc, bytecode) = receipt_interfaces(&[], &["copy", "drop"]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MismatchKind::AbilitiesMismatch,
+                MismatchKind::HotPotatoChanged,
+                MismatchKind::CopyabilityChanged
+            ]
+        );
+        assert_eq!(summary.struct_mismatches, 1);
+        assert_eq!(summary.error_mismatches, 2);
+        assert_eq!(summary.warning_mismatches, 1);
+    }
+
+    #[test]
+    fn test_match_fields_by_name_reports_each_field_once() {
+        let (mut rpc, mut bytecode) = receipt_interfaces(&["drop"], &["drop"]);
+        rpc["modules"]["flash"]["structs"]["Receipt"]["fields"] = serde_json::json!([
+            {"name": "amount", "type": "U64"},
+            {"name": "fee", "type": "U64"},
+            {"name": "owner", "type": "Address"}
+        ]);
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"] = serde_json::json!([
+            {"name": "fee", "type": {"kind": "u128"}},
+            {"name": "nonce", "type": {"kind": "u8"}},
+            {"name": "amount", "type": {"kind": "u64"}}
+        ]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (_, positional) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(positional
+            .iter()
+            .all(|m| m.kind == MismatchKind::FieldNameMismatch));
+        assert_eq!(positional.len(), 3);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                match_fields_by_name: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.struct_mismatches, 4);
+        let found: Vec<(MismatchKind, &str)> = mismatches
+            .iter()
+            .map(|m| (m.kind, m.path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    MismatchKind::FieldMissing,
+                    "modules/flash/structs/Receipt/fields/owner"
+                ),
+                (
+                    MismatchKind::FieldExtra,
+                    "modules/flash/structs/Receipt/fields/nonce"
+                ),
+                (
+                    MismatchKind::FieldOrderChanged,
+                    "modules/flash/structs/Receipt/fields/order"
+                ),
+                (
+                    MismatchKind::FieldTypeMismatch,
+                    "modules/flash/structs/Receipt/fields/fee/type"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hot_potato_gaining_drop_is_flagged() {
+        let (rpc, bytecode) = receipt_interfaces(&[], &["drop"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let hot: Vec<&InterfaceCompareMismatch> = mismatches
+            .iter()
+            .filter(|m| m.kind == MismatchKind::HotPotatoChanged)
+            .collect();
+        assert_eq!(hot.len(), 1);
+        assert_eq!(hot[0].path, "modules/flash/structs/Receipt/hot_potato");
+        assert_eq!(hot[0].reason, "hot potato gained drop");
+        assert_eq!(hot[0].severity, MismatchSeverity::Error);
+    }
+
+    #[test]
+    fn test_stable_hot_potato_is_not_flagged() {
+        let (rpc, bytecode) = receipt_interfaces(&[], &[]);
+        let (summary, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert_eq!(summary.mismatches_total, 0);
+    }
+
+    #[test]
+    fn test_case_insensitive_names_match_with_casing_warning() {
+        let (rpc, _) = receipt_interfaces(&["Drop"], &["drop"]);
+        let bytecode = serde_json::json!({
+            "modules": {"FLASH": {"structs": {"receipt": {
+                "abilities": ["drop"],
+                "type_params": [],
+                "is_native": false,
+                "fields": [{"name": "amount", "type": {"kind": "u64"}}]
+            }}, "functions": {}}}
+        });
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+
+        let (strict, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(strict.modules_missing_in_bytecode, 1);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                case_insensitive_names: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.modules_missing_in_bytecode, 0);
+        assert_eq!(summary.structs_compared, 1);
+        assert_eq!(summary.error_mismatches, 0);
+        let reasons: Vec<&str> = mismatches.iter().map(|m| m.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "name casing differs: flash vs FLASH",
+                "name casing differs: Receipt vs receipt",
+            ]
+        );
+        assert!(
+            mismatches
+                .iter()
+                .all(|m| m.severity == MismatchSeverity::Warning
+                    && m.kind == MismatchKind::NameCasing)
+        );
+    }
+
+    #[test]
+    fn test_type_param_disparity_is_info() {
+        let interfaces = |rpc_n: usize, byte_n: usize| {
+            let rpc_tps = vec![
+                serde_json::json!({"constraints": {"abilities": []}, "isPhantom": true});
+                rpc_n
+            ];
+            let byte_tps = vec![serde_json::json!({"constraints": [], "is_phantom": true}); byte_n];
+            let rpc = serde_json::json!({"modules": {"m": {"structs": {"Wide": {
+                "abilities": {"abilities": []},
+                "typeParameters": rpc_tps,
+                "fields": []
+            }}, "exposedFunctions": {}}}});
+            let bytecode = serde_json::json!({"modules": {"m": {"structs": {"Wide": {
+                "abilities": [],
+                "type_params": byte_tps,
+                "is_native": false,
+                "fields": []
+            }}, "functions": {}}}});
+            (rpc, bytecode)
+        };
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+
+        let (rpc, bytecode) = interfaces(40, 5);
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        let signal: Vec<&InterfaceCompareMismatch> = mismatches
+            .iter()
+            .filter(|m| m.kind == MismatchKind::TypeParamDisparity)
+            .collect();
+        assert_eq!(signal.len(), 1);
+        assert_eq!(signal[0].path, "modules/m/type_params");
+        assert_eq!(signal[0].severity, MismatchSeverity::Info);
+
+        let (rpc, bytecode) = interfaces(8, 5);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert!(mismatches
+            .iter()
+            .all(|m| m.kind != MismatchKind::TypeParamDisparity));
+    }
+
+    #[test]
+    fn test_copy_removal_has_dedicated_signal() {
+        let (rpc, bytecode) = receipt_interfaces(&["Copy", "Drop"], &["drop"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MismatchKind::AbilitiesMismatch,
+                MismatchKind::CopyabilityChanged
+            ]
+        );
+        assert_eq!(mismatches[1].reason, "copy lost");
+        assert_eq!(mismatches[1].severity, MismatchSeverity::Warning);
+    }
+
+    #[test]
+    fn test_incremental_compare_skips_unchanged_modules() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["a"] = rpc["modules"]["m"].clone();
+        bytecode["modules"]["a"] = bytecode["modules"]["m"].clone();
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (first, _, fingerprints) =
+            compare_interface_incremental("0x1", &rpc, &bytecode, opts.clone(), &HashMap::new());
+        assert_eq!(first.modules_compared, 2);
+
+        let mut changed = bytecode.clone();
+        changed["modules"]["m"]["functions"]["f"]["visibility"] = serde_json::json!("friend");
+        let (second, mismatches, updated) =
+            compare_interface_incremental("0x1", &rpc, &changed, opts, &fingerprints);
+        assert_eq!(second.modules_compared, 1);
+        assert!(!mismatches.is_empty());
+        assert!(mismatches
+            .iter()
+            .all(|m| m.path.starts_with("modules/m/functions/f/")));
+        assert_eq!(updated["a"], fingerprints["a"]);
+        assert_ne!(updated["m"], fingerprints["m"]);
+    }
+
+    #[test]
+    fn test_incremental_compare_keys_modules_by_address() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        for name in ["a", "m"] {
+            for side in [&mut rpc, &mut bytecode] {
+                side["modules"][name] = side["modules"]["m"].clone();
+                side["modules"][name]["address"] = serde_json::json!("0x1");
+                side["modules"][name]["name"] = serde_json::json!(name);
+            }
+        }
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            key_modules_by_address: true,
+            ..Default::default()
+        };
+        let (first, _, fingerprints) =
+            compare_interface_incremental("0x1", &rpc, &bytecode, opts.clone(), &HashMap::new());
+        assert_eq!(first.modules_compared, 2);
+        let key = |name: &str| format!("{}::{name}", normalize_address_str("0x1").unwrap());
+        assert!(fingerprints.contains_key(&key("a")));
+
+        let mut changed = bytecode.clone();
+        changed["modules"]["m"]["functions"]["f"]["visibility"] = serde_json::json!("friend");
+        let (second, mismatches, updated) =
+            compare_interface_incremental("0x1", &rpc, &changed, opts, &fingerprints);
+        assert_eq!(second.modules_compared, 1);
+        assert!(!mismatches.is_empty());
+        assert_eq!(updated[&key("a")], fingerprints[&key("a")]);
+        assert_ne!(updated[&key("m")], fingerprints[&key("m")]);
+    }
+
+    #[test]
+    fn test_incremental_compare_keeps_package_context() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["version"] = serde_json::json!(1);
+        bytecode["version"] = serde_json::json!(1);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            check_package_version: true,
+            ..Default::default()
+        };
+        let (_, _, fingerprints) =
+            compare_interface_incremental("0x1", &rpc, &bytecode, opts.clone(), &HashMap::new());
+
+        // A different option set invalidates every fingerprint.
+        let (summary, _, refreshed) = compare_interface_incremental(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                compare_param_names: true,
+                ..opts.clone()
+            },
+            &fingerprints,
+        );
+        assert_eq!(summary.modules_compared, 1);
+        assert_ne!(refreshed["m"], fingerprints["m"]);
+
+        // So does a top-level key, which the comparison still sees.
+        bytecode["version"] = serde_json::json!(2);
+        let (summary, mismatches, refreshed) =
+            compare_interface_incremental("0x1", &rpc, &bytecode, opts, &fingerprints);
+        assert_ne!(refreshed["m"], fingerprints["m"]);
+        assert!(!summary.complete);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "version");
+    }
+
+    #[test]
+    fn test_return_tuple_is_flattened_on_either_side() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["return"] = serde_json::json!(["U64", "Bool"]);
+        bytecode["modules"]["m"]["functions"]["f"]["returns"] = serde_json::json!([
+            {"kind": "tuple", "types": [{"kind": "u64"}, {"kind": "bool"}]}
+        ]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.mismatches_total, 0);
+
+        let mut swapped_rpc = rpc.clone();
+        swapped_rpc["modules"]["m"]["exposedFunctions"]["f"]["return"] =
+            serde_json::json!([{"Tuple": ["U64", "Bool"]}]);
+        let mut listed = bytecode.clone();
+        listed["modules"]["m"]["functions"]["f"]["returns"] =
+            serde_json::json!([{"kind": "u64"}, {"kind": "bool"}]);
+        let (summary, _) =
+            compare_interface_rpc_vs_bytecode("0x1", &swapped_rpc, &listed, opts.clone());
+        assert_eq!(summary.mismatches_total, 0);
+
+        bytecode["modules"]["m"]["functions"]["f"]["returns"] = serde_json::json!([
+            {"kind": "tuple", "types": [{"kind": "u64"}, {"kind": "bool"}, {"kind": "u8"}]}
+        ]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::ReturnCountMismatch);
+    }
+
+    #[test]
+    fn test_compare_imports_reports_added_import_as_info() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["uses"] = serde_json::json!(["0x2::coin"]);
+        bytecode["modules"]["m"]["imports"] = serde_json::json!(["0x2::coin", "0x2::balance"]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.mismatches_total, 0);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                compare_imports: true,
+                ..opts.clone()
+            },
+        );
+        assert_eq!(summary.info_mismatches, 1);
+        assert_eq!(summary.error_mismatches, 0);
+        assert_eq!(mismatches[0].path, "modules/m/imports/0x2::balance");
+        assert_eq!(mismatches[0].reason, "import added");
+
+        let (_, mismatches) = compare_interface_bytecode_vs_rpc(
+            "0x1",
+            &bytecode,
+            &rpc,
+            InterfaceCompareOptions {
+                compare_imports: true,
+                ..opts
+            },
+        );
+        assert_eq!(mismatches[0].reason, "import removed");
+    }
+
+    #[test]
+    fn test_include_canonical_attaches_both_forms() {
+        let (rpc, mut bytecode) = receipt_interfaces(&["Drop"], &["drop"]);
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] =
+            serde_json::json!({"kind": "u128"});
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                include_canonical: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(mismatches.len(), 1);
+        let m = &mismatches[0];
+        assert_eq!(m.rpc_canonical, Some(serde_json::json!({"kind": "u64"})));
+        assert_eq!(
+            m.bytecode_canonical,
+            Some(serde_json::json!({"kind": "u128"}))
+        );
+        assert_ne!(m.rpc_canonical, m.bytecode_canonical);
+        assert!(m.rpc.is_some());
+    }
+
+    #[test]
+    fn test_key_modules_by_address_matches_same_named_modules() {
+        let rpc_coin = |fname: &str, address: &str| {
+            serde_json::json!({
+                "address": address,
+                "name": "coin",
+                "structs": {},
+                "exposedFunctions": {fname: {
+                    "visibility": "Public",
+                    "isEntry": false,
+                    "typeParameters": [],
+                    "parameters": [],
+                    "return": []
+                }}
+            })
+        };
+        let byte_coin = |fname: &str, address: &str| {
+            serde_json::json!({
+                "address": address,
+                "name": "coin",
+                "structs": {},
+                "functions": {fname: {
+                    "visibility": "public",
+                    "is_entry": false,
+                    "type_params": [],
+                    "params": [],
+                    "returns": []
+                }}
+            })
+        };
+        let rpc = serde_json::json!({"modules": {
+            "coin": rpc_coin("mint", "0xa"),
+            "0xb::coin": rpc_coin("burn", "0xb")
+        }});
+        let bytecode = serde_json::json!({"modules": {
+            "0xa::coin": byte_coin("mint", "0xa"),
+            "coin": byte_coin("burn", "0xb")
+        }});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+
+        let (bare, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(bare.modules_missing_in_bytecode, 1);
+        assert_eq!(bare.modules_extra_in_bytecode, 1);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                key_modules_by_address: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.modules_compared, 2);
+        assert_eq!(summary.functions_compared, 2);
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+    }
+
+    #[test]
+    fn test_type_coverage() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!(["U64", "Bool"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "u64"}, {"kind": "bool"}]);
+        let coverage = |bytecode: &Value| {
+            let (summary, _) = compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                bytecode,
+                InterfaceCompareOptions::default(),
+            );
+            (summary.type_sites_compared, type_coverage(&summary))
+        };
+        assert_eq!(coverage(&bytecode), (2, 100.0));
+
+        bytecode["modules"]["m"]["functions"]["f"]["params"][1] = serde_json::json!({"kind": "u8"});
+        assert_eq!(coverage(&bytecode), (2, 50.0));
+
+        bytecode["modules"]["m"]["functions"]["f"]["params"][0] =
+            serde_json::json!({"kind": "u128"});
+        assert_eq!(coverage(&bytecode), (2, 0.0));
+    }
+
+    #[test]
+    fn test_check_entry_params() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", true, "public", true);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([
+            "U64",
+            {"Vector": "U8"},
+            {"MutableReference": {"Struct": {
+                "address": "0x2", "module": "tx_context", "name": "TxContext", "typeArguments": []
+            }}}
+        ]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([
+            {"kind": "u64"},
+            {"kind": "vector", "type": {"kind": "u8"}},
+            {"kind": "ref", "mutable": true, "to": {
+                "kind": "datatype", "address": "0x2", "module": "tx_context", "name": "TxContext",
+                "type_args": []
+            }}
+        ]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            check_entry_params: true,
+            ..Default::default()
+        };
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.mismatches_total, 0);
+
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"][0] =
+            serde_json::json!({"Reference": "U64"});
+        bytecode["modules"]["m"]["functions"]["f"]["params"][0] =
+            serde_json::json!({"kind": "ref", "mutable": false, "to": {"kind": "u64"}});
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "modules/m/functions/f/params[0]/entry");
+        assert_eq!(mismatches[0].reason, "invalid entry parameter type");
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Warning);
+    }
+
+    #[test]
+    fn test_named_type_param_reference_unifies_with_index() {
+        let rpc = serde_json::json!({"modules": {"m": {"structs": {"Box": {
+            "abilities": {"abilities": ["Store"]},
+            "typeParameters": [{"constraints": {"abilities": []}, "isPhantom": false}],
+            "fields": [{"name": "x", "type": {"Vector": {"TypeParameter": 0}}}]
+        }}, "exposedFunctions": {}}}});
+        let bytecode_with = |name: &str| {
+            serde_json::json!({"modules": {"m": {"structs": {"Box": {
+                "abilities": ["store"],
+                "type_params": [{"name": "T", "constraints": [], "is_phantom": false}],
+                "is_native": false,
+                "fields": [{"name": "x", "type": {
+                    "kind": "vector",
+                    "type": {"kind": "type_param", "name": name}
+                }}]
+            }}, "functions": {}}}})
+        };
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode_with("T"), opts.clone());
+        assert_eq!(summary.mismatches_total, 0, "{mismatches:?}");
+
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode_with("U"), opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "modules/m/structs/Box/fields[0]/type");
+    }
+
+    #[test]
+    fn test_module_set_and_item_mismatches_add_up() {
+        let (mut rpc, bytecode) = single_function_interfaces("Public", false, "friend", false);
+        rpc["modules"]["gone"] = serde_json::json!({"structs": {}, "exposedFunctions": {}});
+        let (summary, _) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert_eq!(summary.module_set_mismatches, 1);
+        assert!(summary.item_mismatches > 0);
+        assert_eq!(
+            summary.mismatches_total,
+            summary.module_set_mismatches + summary.item_mismatches
+        );
+    }
+
+    #[test]
+    fn test_infer_missing_visibility_on_exposed_function() {
+        let (mut rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]
+            .as_object_mut()
+            .unwrap()
+            .remove("visibility");
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (strict, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(strict.error_mismatches > 0);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                infer_missing_visibility: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.mismatches_total, 0, "{mismatches:?}");
+    }
+
+    #[test]
+    fn test_best_match_picks_exact_candidate() {
+        let (rpc, exact) = single_function_interfaces("Public", false, "public", false);
+        let (_, friend) = single_function_interfaces("Public", false, "friend", false);
+        let mut extra_module = exact.clone();
+        extra_module["modules"]["n"] = serde_json::json!({"structs": {}, "functions": {}});
+        let candidates = vec![
+            ("v1".to_string(), friend),
+            ("v2".to_string(), exact),
+            ("v3".to_string(), extra_module),
+        ];
+        let (label, report) =
+            best_match(&rpc, &candidates, &InterfaceCompareOptions::default()).unwrap();
+        assert_eq!(label, "v2");
+        assert_eq!(report.summary.mismatches_total, 0);
+        assert!(best_match(&rpc, &[], &InterfaceCompareOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_ignore_unused_type_params_downgrades_arity_mismatch() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["typeParameters"] =
+            serde_json::json!([{"abilities": []}]);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([{"TypeParameter": 0}]);
+        bytecode["modules"]["m"]["functions"]["f"]["type_params"] =
+            serde_json::json!([{"constraints": []}, {"constraints": []}]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "type_param", "index": 0}]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (_, strict) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(strict[0].severity, MismatchSeverity::Error);
+
+        let lenient = InterfaceCompareOptions {
+            ignore_unused_type_params: true,
+            ..opts
+        };
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, lenient.clone());
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::TypeParamArityMismatch);
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Info);
+
+        bytecode["modules"]["m"]["functions"]["f"]["returns"] =
+            serde_json::json!([{"kind": "type_param", "index": 1}]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, lenient);
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Error);
+    }
+
+    #[test]
+    fn test_compare_struct_sizes_flags_added_u128_field() {
+        let (rpc, mut bytecode) = receipt_interfaces(&[], &[]);
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({"name": "fee", "type": {"kind": "u128"}}));
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            compare_struct_sizes: true,
+            ..Default::default()
+        };
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        let size = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::StructSizeChanged)
+            .unwrap();
+        assert_eq!(size.path, "modules/flash/structs/Receipt/size");
+        assert_eq!(size.severity, MismatchSeverity::Warning);
+        assert_eq!(
+            size.reason,
+            "estimated minimum size changed (rpc=8 bytecode=24 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_param_passing_mode_change_is_flagged() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        let coin_rpc = serde_json::json!({"Struct": {"address": "0x2", "module": "coin", "name": "Coin", "typeArguments": []}});
+        let coin_byte = serde_json::json!({"kind": "datatype", "address": "0x2", "module": "coin", "name": "Coin", "type_args": []});
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([{ "MutableReference": coin_rpc }]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([coin_byte]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        let mode = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::PassingModeChanged)
+            .unwrap();
+        assert_eq!(mode.path, "modules/m/functions/f/params[0]/passing_mode");
+        assert_eq!(mode.reason, "object passing mode changed");
+        assert_eq!(mode.rpc, Some(serde_json::json!("by_mut_ref")));
+        assert_eq!(mode.bytecode, Some(serde_json::json!("by_value")));
+        assert!(mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::ParamTypeMismatch));
+    }
+
+    #[test]
+    fn test_passing_mode_is_only_compared_for_object_params() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([{ "MutableReference": "U64" }]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([{"kind": "u64"}]);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(kinds, vec![MismatchKind::ParamTypeMismatch]);
+        assert_eq!(summary.function_mismatches, 1);
+
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([{ "MutableReference": {"TypeParameter": 0} }]);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["typeParameters"] =
+            serde_json::json!([{"abilities": ["Key"]}]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "type_param", "index": 0}]);
+        bytecode["modules"]["m"]["functions"]["f"]["type_params"] =
+            serde_json::json!([{"constraints": ["key"]}]);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MismatchKind::PassingModeChanged,
+                MismatchKind::ParamTypeMismatch
+            ]
+        );
+        // The mode is derived from the type diff, which alone counts against the function.
+        assert_eq!(summary.function_mismatches, 1);
+        assert_eq!(summary.error_mismatches, 2);
+    }
+
+    #[test]
+    fn test_unify_string_types_matches_string_and_bytes() {
+        let (mut rpc, mut bytecode) = receipt_interfaces(&[], &[]);
+        rpc["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] = serde_json::json!({
+            "Struct": {"address": "0x1", "module": "string", "name": "String", "typeArguments": []}
+        });
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] =
+            serde_json::json!({"kind": "vector", "type": {"kind": "u8"}});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.struct_mismatches, 1);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                unify_string_types: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.struct_mismatches, 0);
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_unify_option_types_matches_option_and_vector() {
+        let (mut rpc, mut bytecode) = receipt_interfaces(&[], &[]);
+        rpc["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] = serde_json::json!({
+            "Struct": {"address": "0x1", "module": "option", "name": "Option", "typeArguments": ["U64"]}
+        });
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] =
+            serde_json::json!({"kind": "vector", "type": {"kind": "u64"}});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, _) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(summary.struct_mismatches, 1);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                unify_option_types: true,
+                ..opts
+            },
+        );
+        assert_eq!(summary.struct_mismatches, 0);
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_check_deprecation_flags_one_sided_marker() {
+        let (rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        bytecode["modules"]["m"]["functions"]["f"]["attributes"] =
+            serde_json::json!([{"name": "deprecated", "note": "use g"}]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(mismatches.is_empty());
+
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                check_deprecation: true,
+                ..opts
+            },
+        );
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::DeprecationChanged);
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Warning);
+        assert_eq!(mismatches[0].path, "modules/m/functions/f/deprecated");
+        assert_eq!(mismatches[0].reason, "deprecation status changed");
+    }
 
-pub fn module_set_diff(left: &[String], right: &[String]) -> ModuleSetDiff {
-    let left_set: HashSet<&str> = left.iter().map(|s| s.as_str()).collect();
-    let right_set: HashSet<&str> = right.iter().map(|s| s.as_str()).collect();
+    #[test]
+    fn test_focus_path_limits_walk_to_one_function() {
+        let rpc_fun = |param: &str| serde_json::json!({"visibility": "Public", "isEntry": false, "typeParameters": [], "parameters": [param], "return": []});
+        let byte_fun = |param: &str| serde_json::json!({"visibility": "public", "is_entry": false, "type_params": [], "params": [{"kind": param}], "returns": []});
+        let rpc = serde_json::json!({"modules": {
+            "pool": {"structs": {"Pool": {"abilities": {"abilities": ["Key"]}, "typeParameters": [], "fields": []}},
+                     "exposedFunctions": {"swap": rpc_fun("U64"), "add": rpc_fun("U8")}},
+            "gone": {"structs": {}, "exposedFunctions": {}}
+        }});
+        let bytecode = serde_json::json!({"modules": {
+            "pool": {"structs": {"Pool": {"abilities": ["key", "store"], "type_params": [], "is_native": false, "fields": []}},
+                     "functions": {"swap": byte_fun("u128"), "add": byte_fun("u16")}}
+        }});
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            focus_path: Some("modules/pool/functions/swap".to_string()),
+            ..Default::default()
+        };
+        let mut trace = Vec::new();
+        let (summary, mismatches) =
+            compare_interface_rpc_vs_bytecode_traced("0x1", &rpc, &bytecode, opts, &mut trace);
+        assert!(!mismatches.is_empty());
+        assert!(mismatches
+            .iter()
+            .all(|m| m.path.starts_with("modules/pool/functions/swap/")));
+        assert_eq!(summary.mismatches_total, mismatches.len());
+        assert_eq!(summary.structs_compared, 0);
+        assert_eq!(summary.functions_compared, 1);
+        assert_eq!(
+            trace,
+            vec![
+                "function pool::swap: mismatch".to_string(),
+                "module pool: mismatch".to_string()
+            ]
+        );
+    }
 
-    let mut missing_in_right: Vec<String> = left
-        .iter()
-        .filter(|name| !right_set.contains(name.as_str()))
-        .cloned()
-        .collect();
-    missing_in_right.sort();
+    fn rpc_datatype(address: &str, module: &str, name: &str) -> Value {
+        serde_json::json!({"Struct": {
+            "address": address, "module": module, "name": name, "typeArguments": []
+        }})
+    }
 
-    let mut extra_in_right: Vec<String> = right
-        .iter()
-        .filter(|name| !left_set.contains(name.as_str()))
-        .cloned()
-        .collect();
-    extra_in_right.sort();
+    fn byte_datatype(address: &str, module: &str, name: &str) -> Value {
+        serde_json::json!({
+            "kind": "datatype", "address": address, "module": module, "name": name,
+            "type_args": []
+        })
+    }
 
-    ModuleSetDiff {
-        left_count: left.len(),
-        right_count: right.len(),
-        missing_in_right,
-        extra_in_right,
+    fn byte_init(params: Vec<Value>) -> Value {
+        serde_json::json!({
+            "visibility": "private", "is_entry": false, "is_native": false,
+            "type_params": [], "params": params, "returns": []
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_init_signature_change_is_flagged() {
+        let (mut rpc, mut bytecode) =
+            single_function_interfaces("Private", false, "private", false);
+        let rpc_f = rpc["modules"]["m"]["exposedFunctions"]["f"].take();
+        rpc["modules"]["m"]["exposedFunctions"] = serde_json::json!({"init": rpc_f});
+        rpc["modules"]["m"]["exposedFunctions"]["init"]["parameters"] = serde_json::json!([
+            rpc_datatype("0x1", "m", "WITNESS"),
+            {"MutableReference": rpc_datatype("0x2", "tx_context", "TxContext")}
+        ]);
+        bytecode["modules"]["m"]["functions"] = serde_json::json!({"init": byte_init(vec![
+            byte_datatype("0x1", "m", "M"),
+            serde_json::json!({
+                "kind": "ref", "mutable": true,
+                "to": byte_datatype("0x2", "tx_context", "TxContext")
+            }),
+        ])});
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        // Reported once, as a signature change, and not again as a param type mismatch.
+        let found: Vec<(MismatchKind, &str)> = mismatches
+            .iter()
+            .map(|m| (m.kind, m.reason.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(MismatchKind::InitSignatureChanged, "init signature changed")]
+        );
+        assert!(mismatches[0]
+            .path
+            .starts_with("modules/m/functions/init/params[0]"));
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Error);
+        assert_eq!(summary.function_mismatches, 1);
+    }
 
     #[test]
-    fn test_compare_interface_rpc_vs_bytecode_smoke_ok() {
-        let rpc = serde_json::json!({
-            "modules": {
-                "m": {
-                    "structs": {
-                        "S": {
-                            "abilities": { "abilities": ["Store"] },
-                            "typeParameters": [],
-                            "fields": [
-                                {"name":"x", "type":"U64"}
-                            ]
-                        }
-                    },
-                    "exposedFunctions": {
-                        "f": {
-                            "visibility":"Public",
-                            "isEntry": false,
-                            "typeParameters": [],
-                            "parameters": ["U64"],
-                            "return": []
-                        }
-                    }
-                }
-            }
+    fn test_init_shape_checked_without_rpc_entry() {
+        // RPC never lists the private `init`, as with any real package.
+        let (rpc, bytecode) = single_function_interfaces("Public", false, "public", false);
+        let ctx = serde_json::json!({
+            "kind": "ref", "mutable": true,
+            "to": byte_datatype("0x2", "tx_context", "TxContext")
         });
+        let run = |init: Value| {
+            let mut bytecode = bytecode.clone();
+            bytecode["modules"]["m"]["functions"]["init"] = init;
+            compare_interface_rpc_vs_bytecode(
+                "0x1",
+                &rpc,
+                &bytecode,
+                InterfaceCompareOptions {
+                    max_mismatches: 10,
+                    ..Default::default()
+                },
+            )
+        };
 
-        let bytecode = serde_json::json!({
-            "modules": {
-                "m": {
-                    "address": "0x0000000000000000000000000000000000000000000000000000000000000001",
-                    "structs": {
-                        "S": {
-                            "abilities": ["store"],
-                            "type_params": [],
-                            "is_native": false,
-                            "fields": [{"name":"x", "type": {"kind":"u64"}}]
-                        }
-                    },
-                    "functions": {
-                        "f": {
-                            "visibility": "public",
-                            "is_entry": false,
-                            "is_native": false,
-                            "type_params": [],
-                            "params": [{"kind":"u64"}],
-                            "returns": [],
-                            "acquires": []
-                        }
-                    }
-                }
-            }
-        });
+        let (_, mismatches) = run(byte_init(vec![byte_datatype("0x1", "m", "M"), ctx.clone()]));
+        assert!(mismatches.is_empty());
+        let (_, mismatches) = run(byte_init(vec![ctx.clone()]));
+        assert!(mismatches.is_empty());
 
-        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+        let (summary, mismatches) = run(byte_init(vec![
+            byte_datatype("0x1", "m", "WITNESS"),
+            ctx.clone(),
+        ]));
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::InitSignatureInvalid);
+        assert_eq!(mismatches[0].path, "modules/m/functions/init/signature");
+        assert_eq!(
+            mismatches[0].reason,
+            "invalid init signature: first param is not the module's one-time witness"
+        );
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Error);
+        // `init` is not an item both sides declare, so it does not count as a mismatched one.
+        assert_eq!(summary.function_mismatches, 0);
+
+        let (_, mismatches) = run(byte_init(vec![byte_datatype("0x1", "m", "M")]));
+        assert_eq!(
+            mismatches[0].reason,
+            "invalid init signature: params must be an optional one-time witness and a \
+             TxContext reference"
+        );
+    }
+
+    #[test]
+    fn test_check_acquires_flags_missing_struct() {
+        let (rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        bytecode["modules"]["m"]["structs"] = serde_json::json!({
+            "Vault": {"abilities": ["key"], "type_params": [], "is_native": false, "fields": []}
+        });
+        bytecode["modules"]["m"]["functions"]["f"]["acquires"] = serde_json::json!([
+            {"address": "0x1", "module": "m", "name": "Ghost"},
+            {"address": "0x1", "module": "m", "name": "Vault"}
+        ]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
             "0x1",
             &rpc,
             &bytecode,
             InterfaceCompareOptions {
                 max_mismatches: 10,
                 include_values: true,
+                check_acquires: true,
+                ..Default::default()
             },
         );
-        assert_eq!(summary.mismatches_total, 0, "{mismatches:#?}");
+        let acquires: Vec<&InterfaceCompareMismatch> = mismatches
+            .iter()
+            .filter(|m| m.kind == MismatchKind::InvalidAcquires)
+            .collect();
+        assert_eq!(acquires.len(), 1);
+        assert_eq!(acquires[0].path, "modules/m/functions/f/acquires/Ghost");
+        assert_eq!(acquires[0].reason, "invalid acquires reference");
+        assert_eq!(acquires[0].rpc, None);
+        assert_eq!(acquires[0].bytecode, Some(Value::Bool(false)));
+    }
+
+    #[test]
+    fn test_compare_cross_network_unifies_mapped_package_addresses() {
+        let (mut mainnet, mut testnet) =
+            single_function_interfaces("Public", false, "public", false);
+        mainnet["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([
+            {"Struct": {"address": "0xaa", "module": "m", "name": "Pool", "typeArguments": []}}
+        ]);
+        testnet["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([
+            {"kind": "datatype", "address": "0xbb", "module": "m", "name": "Pool", "type_args": []}
+        ]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (summary, _) = compare_cross_network(
+            &mainnet,
+            &testnet,
+            SchemaSource::Bytecode,
+            &[],
+            opts.clone(),
+        );
+        assert_eq!(summary.function_mismatches, 1);
+
+        let map = [("0xaa".to_string(), "0xbb".to_string())];
+        let (summary, mismatches) =
+            compare_cross_network(&mainnet, &testnet, SchemaSource::Bytecode, &map, opts);
+        assert_eq!(summary.mismatches_total, 0);
         assert!(mismatches.is_empty());
     }
 
     #[test]
-    fn test_compare_interface_rpc_vs_bytecode_detects_type_mismatch() {
-        let rpc = serde_json::json!({
-            "modules": {
-                "m": {
-                    "structs": {
-                        "S": {
-                            "abilities": { "abilities": ["Store"] },
-                            "typeParameters": [],
-                            "fields": [{"name":"x", "type":"U64"}]
-                        }
-                    },
-                    "exposedFunctions": {}
-                }
-            }
+    fn test_compare_cross_network_remaps_declared_dependencies() {
+        let (mut mainnet, mut testnet) =
+            single_function_interfaces("Public", false, "public", false);
+        mainnet["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([
+            {"Struct": {"address": "0xcc", "module": "dep", "name": "Coin", "typeArguments": []}}
+        ]);
+        testnet["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([
+            {"kind": "datatype", "address": "0xdd", "module": "dep", "name": "Coin", "type_args": []}
+        ]);
+        testnet["dependencies"] = serde_json::json!(["0xdd"]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let map = [("0xcc".to_string(), "0xdd".to_string())];
+        let (summary, mismatches) =
+            compare_cross_network(&mainnet, &testnet, SchemaSource::Bytecode, &map, opts);
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+        assert_eq!(summary.mismatches_total, 0);
+    }
+
+    #[test]
+    fn test_compare_cross_network_accepts_two_rpc_fetches() {
+        let (mainnet, _) = single_function_interfaces("Public", true, "public", true);
+        let mut mainnet = mainnet;
+        mainnet["modules"]["m"]["structs"]["Pool"] = serde_json::json!({
+            "abilities": {"abilities": ["Key"]},
+            "typeParameters": [{"constraints": {"abilities": ["Store"]}, "isPhantom": true}],
+            "fields": [{"name": "id", "type": "Address"}]
         });
+        mainnet["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([
+            {"MutableReference": {"Struct": {
+                "address": "0xaa", "module": "m", "name": "Pool",
+                "typeArguments": [{"TypeParameter": 0}]
+            }}}
+        ]);
+        mainnet["modules"]["m"]["exposedFunctions"]["f"]["typeParameters"] =
+            serde_json::json!([{"abilities": ["Store"]}]);
+        let mut testnet = mainnet.clone();
+        testnet["modules"]["m"]["exposedFunctions"]["f"]["parameters"][0]["MutableReference"]
+            ["Struct"]["address"] = serde_json::json!("0xbb");
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let map = [("0xaa".to_string(), "0xbb".to_string())];
+        let (summary, mismatches) =
+            compare_cross_network(&mainnet, &testnet, SchemaSource::Rpc, &map, opts.clone());
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+        assert_eq!(summary.functions_compared, 1);
+        assert_eq!(summary.structs_compared, 1);
 
-        let bytecode = serde_json::json!({
-            "modules": {
-                "m": {
-                    "address": "0x1",
-                    "structs": {
-                        "S": {
-                            "abilities": ["store"],
-                            "type_params": [],
-                            "is_native": false,
-                            "fields": [{"name":"x", "type": {"kind":"u128"}}]
-                        }
-                    },
-                    "functions": {}
-                }
-            }
+        testnet["modules"]["m"]["exposedFunctions"]["f"]["visibility"] =
+            serde_json::json!("Friend");
+        let (_, mismatches) =
+            compare_cross_network(&mainnet, &testnet, SchemaSource::Rpc, &map, opts);
+        assert!(mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::VisibilityMismatch));
+    }
+
+    #[test]
+    fn test_check_internal_type_exposure_flags_abilityless_return() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["structs"] = serde_json::json!({
+            "Secret": {"abilities": {"abilities": []}, "typeParameters": [], "fields": []}
         });
+        bytecode["modules"]["m"]["structs"] = serde_json::json!({
+            "Secret": {"abilities": [], "type_params": [], "is_native": false, "fields": []}
+        });
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["return"] = serde_json::json!([
+            {"Struct": {"address": "0x1", "module": "m", "name": "Secret", "typeArguments": []}}
+        ]);
+        bytecode["modules"]["m"]["functions"]["f"]["returns"] = serde_json::json!([
+            {"kind": "datatype", "address": "0x1", "module": "m", "name": "Secret", "type_args": []}
+        ]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            ..Default::default()
+        };
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(mismatches.is_empty());
+
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                check_internal_type_exposure: true,
+                ..opts
+            },
+        );
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::InternalTypeExposed);
+        assert_eq!(mismatches[0].severity, MismatchSeverity::Warning);
+        assert_eq!(
+            mismatches[0].reason,
+            "public function exposes internal type"
+        );
+        assert_eq!(
+            mismatches[0].bytecode,
+            Some(serde_json::json!(["m::Secret"]))
+        );
+    }
+
+    #[test]
+    fn test_check_package_version_short_circuits() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "friend", false);
+        rpc["version"] = serde_json::json!("3");
+        bytecode["version"] = serde_json::json!(4);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            check_package_version: true,
+            ..Default::default()
+        };
+        let (summary, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(!summary.complete);
+        assert_eq!(summary.modules_compared, 0);
+        assert_eq!(summary.error_mismatches, 1);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::PackageVersionMismatch);
+        assert_eq!(mismatches[0].path, "version");
+        assert!(mismatches[0].reason.starts_with("package version mismatch"));
+        assert!(mismatches[0].reason.contains("align versions"));
+
+        bytecode["version"] = serde_json::json!(3);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert!(summary.complete);
+        assert_eq!(mismatches[0].kind, MismatchKind::VisibilityMismatch);
+    }
+
+    #[test]
+    fn test_value_shape_mode_unifies_references() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        let coin_rpc = serde_json::json!({"Struct": {"address": "0x2", "module": "coin", "name": "Coin", "typeArguments": []}});
+        let coin_byte = serde_json::json!({"kind": "datatype", "address": "0x2", "module": "coin", "name": "Coin", "type_args": []});
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] =
+            serde_json::json!([{ "Reference": coin_rpc }]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([coin_byte]);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::ParamTypeMismatch));
+
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                value_shape_mode: true,
+                ..opts.clone()
+            },
+        );
+        let kinds: Vec<MismatchKind> = mismatches.iter().map(|m| m.kind).collect();
+        assert_eq!(kinds, vec![MismatchKind::PassingModeChanged]);
 
+        // `&u64` vs `u64` returns: the type diff unifies, the reference change does not vanish.
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["return"] =
+            serde_json::json!([{"Reference": "U64"}]);
+        bytecode["modules"]["m"]["functions"]["f"]["returns"] =
+            serde_json::json!([{"kind": "u64"}]);
         let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                value_shape_mode: true,
+                ..opts
+            },
+        );
+        assert_eq!(mismatches.len(), 1, "{mismatches:?}");
+        assert_eq!(mismatches[0].kind, MismatchKind::PassingModeChanged);
+        assert_eq!(
+            mismatches[0].path,
+            "modules/m/functions/f/returns[0]/passing_mode"
+        );
+        assert_eq!(mismatches[0].reason, "reference changed");
+        assert_eq!(summary.type_sites_mismatched, 1);
+    }
+
+    #[test]
+    fn test_receiving_stays_a_distinct_nominal_type() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", true, "public", true);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["typeParameters"] =
+            serde_json::json!([{"abilities": ["Key"]}]);
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([{
+            "Struct": {"address": "0x2", "module": "transfer", "name": "Receiving", "typeArguments": [{"TypeParameter": 0}]}
+        }]);
+        bytecode["modules"]["m"]["functions"]["f"]["type_params"] =
+            serde_json::json!([{"constraints": ["key"]}]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([{
+            "kind": "datatype",
+            "address": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "module": "transfer",
+            "name": "Receiving",
+            "type_args": [{"kind": "type_param", "index": 0}]
+        }]);
+        // None of the unifying modes may see through the wrapper.
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            value_shape_mode: true,
+            unify_option_types: true,
+            ..Default::default()
+        };
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "type_param", "index": 0}]);
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::ParamTypeMismatch);
+        assert_eq!(mismatches[0].path, "modules/m/functions/f/params[0]");
+
+        let coin = serde_json::json!({
+            "kind": "datatype",
+            "address": "0x2",
+            "module": "coin",
+            "name": "Coin",
+            "type_args": [{"kind": "datatype", "address": "0x2", "module": "sui", "name": "SUI", "type_args": []}]
+        });
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"][0]["Struct"]["typeArguments"] = serde_json::json!([{
+            "Struct": {"address": "0x2", "module": "coin", "name": "Coin", "typeArguments": [
+                {"Struct": {"address": "0x2", "module": "sui", "name": "SUI", "typeArguments": []}}
+            ]}
+        }]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([coin]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::ParamTypeMismatch);
+    }
+
+    #[test]
+    fn test_field_from_removed_dependency_is_dangling() {
+        let (rpc, mut bytecode) = receipt_interfaces(&[], &[]);
+        bytecode["dependencies"] = serde_json::json!(["0xcafe"]);
+        bytecode["modules"]["flash"]["structs"]["Receipt"]["fields"][0]["type"] = serde_json::json!(
+            {"kind": "datatype", "address": "0xbeef", "module": "pool", "name": "Pool", "type_args": []}
+        );
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
             "0x1",
             &rpc,
             &bytecode,
             InterfaceCompareOptions {
                 max_mismatches: 10,
-                include_values: false,
+                include_values: true,
+                ..Default::default()
             },
         );
-        assert!(summary.mismatches_total > 0);
-        assert!(mismatches
+        let dangling: Vec<&InterfaceCompareMismatch> = mismatches
             .iter()
-            .any(|m| m.path.contains("/fields[0]/type")));
+            .filter(|m| m.kind == MismatchKind::UnavailableTypeReference)
+            .collect();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(
+            dangling[0].path,
+            "modules/flash/structs/Receipt/fields/amount"
+        );
+        assert_eq!(dangling[0].reason, "type references unavailable package");
+        assert_eq!(dangling[0].severity, MismatchSeverity::Error);
+        let beef = normalize_address_str("0xbeef").unwrap();
+        assert_eq!(dangling[0].bytecode, Some(Value::String(beef)));
+
+        bytecode["dependencies"] = serde_json::json!(["0xcafe", "0xbeef"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert!(!mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::UnavailableTypeReference));
+    }
+
+    #[test]
+    fn test_hot_potato_return_started_and_stopped() {
+        let receipt_rpc = serde_json::json!({"Struct": {"address": "0x1", "module": "m", "name": "Receipt", "typeArguments": []}});
+        let receipt_byte = serde_json::json!({"kind": "datatype", "address": "0x1", "module": "m", "name": "Receipt", "type_args": []});
+        let interfaces = |rpc_returns: bool, byte_returns: bool| {
+            let (mut rpc, mut bytecode) =
+                single_function_interfaces("Public", false, "public", false);
+            rpc["modules"]["m"]["structs"] = serde_json::json!({
+                "Receipt": {"abilities": {"abilities": []}, "typeParameters": [], "fields": []}
+            });
+            bytecode["modules"]["m"]["structs"] = serde_json::json!({
+                "Receipt": {"abilities": [], "type_params": [], "is_native": false, "fields": []}
+            });
+            if rpc_returns {
+                rpc["modules"]["m"]["exposedFunctions"]["f"]["return"] =
+                    serde_json::json!([receipt_rpc.clone()]);
+            }
+            if byte_returns {
+                bytecode["modules"]["m"]["functions"]["f"]["returns"] =
+                    serde_json::json!([receipt_byte.clone()]);
+            }
+            (rpc, bytecode)
+        };
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            include_values: true,
+            ..Default::default()
+        };
+        let potato = |rpc: &Value, bytecode: &Value| {
+            let (_, mismatches) =
+                compare_interface_rpc_vs_bytecode("0x1", rpc, bytecode, opts.clone());
+            mismatches
+                .into_iter()
+                .find(|m| m.kind == MismatchKind::HotPotatoReturnChanged)
+        };
+
+        let (rpc, bytecode) = interfaces(false, true);
+        let started = potato(&rpc, &bytecode).unwrap();
+        assert_eq!(started.path, "modules/m/functions/f/returns/hot_potato");
+        assert_eq!(started.reason, "hot potato return changed");
+        assert_eq!(started.severity, MismatchSeverity::Error);
+        assert_eq!(started.bytecode, Some(serde_json::json!(["m::Receipt"])));
+
+        let (rpc, bytecode) = interfaces(true, false);
+        let stopped = potato(&rpc, &bytecode).unwrap();
+        assert_eq!(stopped.rpc, Some(serde_json::json!(["m::Receipt"])));
+        assert_eq!(stopped.bytecode, Some(serde_json::json!([])));
+
+        let (rpc, bytecode) = interfaces(true, true);
+        assert!(potato(&rpc, &bytecode).is_none());
+    }
+
+    #[test]
+    fn test_function_verdicts_aggregate_mismatches() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "friend", false);
+        let f = rpc["modules"]["m"]["exposedFunctions"]["f"].clone();
+        rpc["modules"]["m"]["exposedFunctions"]["same"] = f.clone();
+        rpc["modules"]["m"]["exposedFunctions"]["gone"] = f;
+        bytecode["modules"]["m"]["functions"]["same"] = serde_json::json!({
+            "visibility": "public", "is_entry": false, "is_native": false,
+            "type_params": [], "params": [], "returns": []
+        });
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([{"kind": "u8"}]);
+        let (_, mismatches, verdicts) = compare_interface_rpc_vs_bytecode_with_verdicts(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 1,
+                per_function_verdicts: true,
+                ..Default::default()
+            },
+        );
+        // Truncation leaves the verdicts whole.
+        assert_eq!(mismatches.len(), 1);
+
+        let verdicts = verdicts.unwrap();
+        let by_name = |name: &str| verdicts.iter().find(|v| v.name == name).unwrap();
+        assert_eq!(verdicts.len(), 3);
+        assert_eq!(by_name("f").status, FunctionStatus::Changed);
+        assert_eq!(
+            by_name("f")
+                .changes
+                .iter()
+                .map(|c| c.kind)
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([
+                MismatchKind::VisibilityMismatch,
+                MismatchKind::CallabilityChanged,
+                MismatchKind::ParamCountMismatch
+            ])
+        );
+        assert_eq!(by_name("gone").status, FunctionStatus::Removed);
+        assert_eq!(by_name("gone").changes.len(), 1);
+        assert_eq!(by_name("same").status, FunctionStatus::Compatible);
+        assert!(by_name("same").changes.is_empty());
+
+        let (_, _, verdicts) = compare_interface_rpc_vs_bytecode_with_verdicts(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions::default(),
+        );
+        assert!(verdicts.is_none());
+    }
+
+    #[test]
+    fn test_drastically_different_function_gets_collision_hint() {
+        let (mut rpc, mut bytecode) = single_function_interfaces("Public", false, "public", false);
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        rpc["modules"]["m"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!(["U64"]);
+        bytecode["modules"]["m"]["functions"]["f"]["params"] =
+            serde_json::json!([{"kind": "u64"}, {"kind": "u64"}]);
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert!(!mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::PossibleCollision));
+
+        bytecode["modules"]["m"]["functions"]["f"]["params"] = serde_json::json!([
+            {"kind": "address"}, {"kind": "u8"}, {"kind": "bool"}, {"kind": "u64"}, {"kind": "u128"}
+        ]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        let hint = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::PossibleCollision)
+            .unwrap();
+        assert_eq!(hint.path, "modules/m/functions/f/collision");
+        assert_eq!(hint.severity, MismatchSeverity::Info);
+        assert_eq!(
+            hint.reason,
+            "possible function collision/extraction artifact"
+        );
         assert!(mismatches
             .iter()
-            .all(|m| m.rpc.is_none() && m.bytecode.is_none()));
+            .any(|m| m.kind == MismatchKind::ParamCountMismatch));
     }
 }
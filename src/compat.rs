@@ -0,0 +1,730 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::comparator::{
+    compare_interface_rpc_vs_bytecode, rpc_would_expose, InterfaceCompareOptions,
+};
+use crate::types::{InterfaceCompareMismatch, MismatchKind, MismatchSeverity};
+
+/// Upgrade impact of one difference, reading the RPC side as the published (old) interface
+/// and the bytecode side as the candidate (new) one.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+/// What changed, for the differences `compat` understands in detail.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UpgradeChangeKind {
+    /// A type param gained an ability constraint; existing instantiations may no longer satisfy it.
+    ConstraintAdded {
+        ability: String,
+    },
+    /// A type param dropped an ability constraint; every existing instantiation still fits.
+    ConstraintRemoved {
+        ability: String,
+    },
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpgradeChange {
+    pub path: String,
+    pub reason: String,
+    pub kind: UpgradeChangeKind,
+    pub compatibility: Compatibility,
+    /// A compatible change to the interface itself (as opposed to an accepted or advisory
+    /// finding), i.e. something the new version adds or relaxes.
+    pub additive: bool,
+}
+
+/// Detailed kind of a comparator mismatch; constraint paths end in the ability name.
+pub fn change_kind(mismatch: &InterfaceCompareMismatch) -> UpgradeChangeKind {
+    let ability = || {
+        mismatch
+            .path
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    };
+    match mismatch.kind {
+        MismatchKind::ConstraintAdded => UpgradeChangeKind::ConstraintAdded { ability: ability() },
+        MismatchKind::ConstraintRemoved => {
+            UpgradeChangeKind::ConstraintRemoved { ability: ability() }
+        }
+        _ => UpgradeChangeKind::Other,
+    }
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct UpgradeReport {
+    pub breaking: usize,
+    pub compatible: usize,
+    pub changes: Vec<UpgradeChange>,
+}
+
+/// Classify a comparator mismatch. Additions on the new side, relaxed constraints, accepted
+/// differences and advisory (non-error) findings are compatible; everything else is breaking.
+pub fn classify(mismatch: &InterfaceCompareMismatch) -> Compatibility {
+    if mismatch.accepted || mismatch.severity < MismatchSeverity::Error {
+        return Compatibility::Compatible;
+    }
+    match mismatch.kind {
+        // Which type param is phantom drives ability derivation for every instantiation.
+        MismatchKind::PhantomChanged
+        | MismatchKind::ConstraintAdded
+        | MismatchKind::HotPotatoChanged
+        | MismatchKind::DynamicFieldStoreLost
+        | MismatchKind::GenericityChanged => Compatibility::Breaking,
+        MismatchKind::ConstraintRemoved
+        | MismatchKind::ModuleExtra
+        | MismatchKind::ConstantExtra
+        | MismatchKind::StructExtra
+        | MismatchKind::FunctionExtra
+        // Gaining `store` on a `key` struct only widens what holders can do; losing it is breaking.
+        | MismatchKind::PublicTransferGained => Compatibility::Compatible,
+        _ => Compatibility::Breaking,
+    }
+}
+
+pub fn upgrade_report(mismatches: &[InterfaceCompareMismatch]) -> UpgradeReport {
+    let mut report = UpgradeReport::default();
+    for m in mismatches {
+        let compatibility = classify(m);
+        match compatibility {
+            Compatibility::Breaking => report.breaking += 1,
+            Compatibility::Compatible => report.compatible += 1,
+        }
+        report.changes.push(UpgradeChange {
+            path: m.path.clone(),
+            reason: m.reason.clone(),
+            kind: change_kind(m),
+            compatibility,
+            additive: compatibility == Compatibility::Compatible
+                && !m.accepted
+                && m.severity == MismatchSeverity::Error,
+        });
+    }
+    report
+}
+
+/// Whether `actual` (bytecode schema) still provides everything in `required` (RPC schema), e.g.
+/// the subset of a package an integrator calls. Anything extra in `actual` is ignored, as is any
+/// difference `classify` considers compatible; the returned mismatches are the breaking ones,
+/// capped at `opts.max_mismatches`.
+pub fn provides_all(
+    required: &Value,
+    actual: &Value,
+    opts: &InterfaceCompareOptions,
+) -> (bool, Vec<InterfaceCompareMismatch>) {
+    let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+        "",
+        required,
+        actual,
+        InterfaceCompareOptions {
+            max_mismatches: usize::MAX,
+            report_extra_functions: false,
+            ..opts.clone()
+        },
+    );
+    let mut missing: Vec<InterfaceCompareMismatch> = mismatches
+        .into_iter()
+        .filter(|m| classify(m) == Compatibility::Breaking)
+        .collect();
+    let ok = missing.is_empty();
+    missing.truncate(opts.max_mismatches);
+    (ok, missing)
+}
+
+/// Sui package upgrade policy, from most to least permissive.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradePolicy {
+    /// Any change `classify` considers compatible, plus any change to a non-public function.
+    /// Struct type params may not change at all, not even to drop a constraint.
+    Compatible,
+    /// Only new modules, structs, functions and constants; existing items stay as they are,
+    /// except non-public functions, which may change or go.
+    Additive,
+    /// No interface changes at all (only dependencies may move). Private non-entry functions
+    /// are not part of the interface, so they may come and go.
+    DepOnly,
+    /// No upgrades.
+    Immutable,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PolicyCheckResult {
+    pub policy: UpgradePolicy,
+    pub permitted: bool,
+    /// Interface changes the policy does not allow. Accepted findings never count, nor do
+    /// advisory ones other than added `public(friend)` functions.
+    pub disallowed: Vec<UpgradeChange>,
+}
+
+/// Whether upgrading from `old` (RPC schema, the published package) to `new` (bytecode schema,
+/// the candidate build) is permitted under `policy`, and which changes are not.
+pub fn check_against_policy(old: &Value, new: &Value, policy: UpgradePolicy) -> PolicyCheckResult {
+    let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+        "",
+        old,
+        new,
+        InterfaceCompareOptions {
+            max_mismatches: usize::MAX,
+            report_extra_functions: true,
+            report_private_extra_functions: true,
+            // The visibility of an added function decides whether RPC could have listed it.
+            include_values: true,
+            ..Default::default()
+        },
+    );
+    let report = upgrade_report(&mismatches);
+    let disallowed: Vec<UpgradeChange> = mismatches
+        .iter()
+        .zip(report.changes)
+        .filter(|(m, _)| {
+            // RPC never lists private non-entry functions, so their absence from `old` says
+            // nothing about whether they are new.
+            let visible_extra = m.kind == MismatchKind::FunctionExtra
+                && m.bytecode
+                    .as_ref()
+                    .is_some_and(|f| rpc_would_expose(f, false));
+            !m.accepted
+                && (visible_extra
+                    || (m.severity == MismatchSeverity::Error
+                        && m.kind != MismatchKind::FunctionExtra))
+        })
+        .filter(|(m, change)| {
+            if struct_type_param_change(m) {
+                return true;
+            }
+            let non_public_function = old_function(old, &m.path)
+                .is_some_and(|f| f.get("visibility").and_then(Value::as_str) != Some("Public"));
+            match policy {
+                UpgradePolicy::Compatible => {
+                    change.compatibility == Compatibility::Breaking && !non_public_function
+                }
+                UpgradePolicy::Additive => {
+                    !non_public_function
+                        && !matches!(
+                            m.kind,
+                            MismatchKind::ModuleExtra
+                                | MismatchKind::StructExtra
+                                | MismatchKind::FunctionExtra
+                                | MismatchKind::ConstantExtra
+                        )
+                }
+                UpgradePolicy::DepOnly | UpgradePolicy::Immutable => true,
+            }
+        })
+        .map(|(_, change)| change)
+        .collect();
+    PolicyCheckResult {
+        policy,
+        permitted: policy != UpgradePolicy::Immutable && disallowed.is_empty(),
+        disallowed,
+    }
+}
+
+/// Whether `mismatch` changes a struct's type params, which no upgrade policy allows.
+fn struct_type_param_change(mismatch: &InterfaceCompareMismatch) -> bool {
+    matches!(
+        mismatch.kind,
+        MismatchKind::ConstraintAdded
+            | MismatchKind::ConstraintRemoved
+            | MismatchKind::PhantomChanged
+    ) && mismatch.path.contains("/structs/")
+}
+
+/// The function of `old` (RPC schema) that a `modules/<m>/functions/<f>...` path falls under.
+fn old_function<'a>(old: &'a Value, path: &str) -> Option<&'a Value> {
+    let (module, rest) = path.strip_prefix("modules/")?.split_once("/functions/")?;
+    let name = rest.split(['/', '[']).next()?;
+    old.get("modules")?
+        .get(module)?
+        .get("exposedFunctions")?
+        .get(name)
+}
+
+/// Semantic version bump an upgrade calls for.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SemverBump {
+    None,
+    /// Only accepted or advisory findings; the interface itself is unchanged.
+    Patch,
+    /// Additive, compatible interface changes.
+    Minor,
+    /// At least one breaking change.
+    Major,
+}
+
+fn change_bump(change: &UpgradeChange) -> SemverBump {
+    match change.compatibility {
+        Compatibility::Breaking => SemverBump::Major,
+        Compatibility::Compatible if change.additive => SemverBump::Minor,
+        Compatibility::Compatible => SemverBump::Patch,
+    }
+}
+
+/// The bump the most significant change in `report` calls for.
+pub fn recommend_bump(report: &UpgradeReport) -> SemverBump {
+    report
+        .changes
+        .iter()
+        .map(change_bump)
+        .max()
+        .unwrap_or(SemverBump::None)
+}
+
+/// The changes that decide `recommend_bump`, e.g. every breaking change for a major bump.
+pub fn deciding_changes(report: &UpgradeReport) -> Vec<&UpgradeChange> {
+    let bump = recommend_bump(report);
+    report
+        .changes
+        .iter()
+        .filter(|c| change_bump(c) == bump)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swapped_phantom_flags_are_breaking_per_index() {
+        let rpc = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Pair": {
+                            "abilities": {"abilities": ["Store"]},
+                            "typeParameters": [
+                                {"constraints": {"abilities": []}, "isPhantom": true},
+                                {"constraints": {"abilities": []}, "isPhantom": false}
+                            ],
+                            "fields": [{"name": "x", "type": {"TypeParameter": 1}}]
+                        }
+                    },
+                    "exposedFunctions": {}
+                }
+            }
+        });
+        let bytecode = serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "Pair": {
+                            "abilities": ["store"],
+                            "type_params": [
+                                {"constraints": [], "is_phantom": false},
+                                {"constraints": [], "is_phantom": true}
+                            ],
+                            "is_native": false,
+                            "fields": [{"name": "x", "type": {"kind": "type_param", "index": 1}}]
+                        }
+                    },
+                    "functions": {}
+                }
+            }
+        });
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let paths: Vec<&str> = mismatches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "modules/m/structs/Pair/type_params[0]/is_phantom",
+                "modules/m/structs/Pair/type_params[1]/is_phantom",
+            ]
+        );
+        assert!(mismatches[0].reason.contains("type param 0"));
+
+        let report = upgrade_report(&mismatches);
+        assert_eq!(report.breaking, 2);
+        assert!(report
+            .changes
+            .iter()
+            .all(|c| c.compatibility == Compatibility::Breaking));
+    }
+
+    fn generic_struct_interfaces(
+        rpc_constraints: &[&str],
+        byte_constraints: &[&str],
+    ) -> (Value, Value) {
+        let rpc = serde_json::json!({
+            "modules": {"m": {"structs": {"Box": {
+                "abilities": {"abilities": ["Store"]},
+                "typeParameters": [{"constraints": {"abilities": rpc_constraints}, "isPhantom": false}],
+                "fields": [{"name": "x", "type": {"TypeParameter": 0}}]
+            }}, "exposedFunctions": {}}}
+        });
+        let bytecode = serde_json::json!({
+            "modules": {"m": {"structs": {"Box": {
+                "abilities": ["store"],
+                "type_params": [{"constraints": byte_constraints, "is_phantom": false}],
+                "is_native": false,
+                "fields": [{"name": "x", "type": {"kind": "type_param", "index": 0}}]
+            }}, "functions": {}}}
+        });
+        (rpc, bytecode)
+    }
+
+    #[test]
+    fn test_constraint_added_is_breaking() {
+        let (rpc, bytecode) = generic_struct_interfaces(&[], &["store"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let report = upgrade_report(&mismatches);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(
+            report.changes[0].path,
+            "modules/m/structs/Box/type_params[0]/constraints/store"
+        );
+        assert_eq!(
+            report.changes[0].kind,
+            UpgradeChangeKind::ConstraintAdded {
+                ability: "store".to_string()
+            }
+        );
+        assert_eq!(report.changes[0].compatibility, Compatibility::Breaking);
+    }
+
+    #[test]
+    fn test_constraint_removed_is_compatible() {
+        let (rpc, bytecode) = generic_struct_interfaces(&["Copy", "Drop"], &["copy"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let report = upgrade_report(&mismatches);
+        assert_eq!(report.breaking, 0);
+        assert_eq!(report.compatible, 1);
+        assert_eq!(
+            report.changes[0].kind,
+            UpgradeChangeKind::ConstraintRemoved {
+                ability: "drop".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_function_object_bounds_added_are_breaking() {
+        let (mut rpc, mut bytecode) = generic_struct_interfaces(&[], &[]);
+        rpc["modules"]["m"]["exposedFunctions"]["put"] = serde_json::json!({
+            "visibility": "Public", "isEntry": false,
+            "typeParameters": [{"abilities": []}],
+            "parameters": [{"TypeParameter": 0}], "return": []
+        });
+        bytecode["modules"]["m"]["functions"]["put"] = serde_json::json!({
+            "visibility": "public", "is_entry": false, "is_native": false,
+            "type_params": [{"constraints": ["key", "store"]}],
+            "params": [{"kind": "type_param", "index": 0}], "returns": []
+        });
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let report = upgrade_report(&mismatches);
+        let paths: Vec<&str> = report.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "modules/m/functions/put/type_params[0]/constraints/key",
+                "modules/m/functions/put/type_params[0]/constraints/store",
+            ]
+        );
+        assert_eq!(
+            report.changes[0].kind,
+            UpgradeChangeKind::ConstraintAdded {
+                ability: "key".to_string()
+            }
+        );
+        assert_eq!(report.breaking, 2);
+    }
+
+    #[test]
+    fn test_genericity_changes_are_breaking() {
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (mut rpc, mut bytecode) = generic_struct_interfaces(&[], &[]);
+        rpc["modules"]["m"]["structs"]["Box"]["typeParameters"] = serde_json::json!([]);
+        rpc["modules"]["m"]["structs"]["Box"]["fields"][0]["type"] = serde_json::json!("U64");
+        bytecode["modules"]["m"]["structs"]["Box"]["fields"][0]["type"] =
+            serde_json::json!({"kind": "u64"});
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::GenericityChanged);
+        assert_eq!(mismatches[0].path, "modules/m/structs/Box/type_params");
+        assert_eq!(classify(&mismatches[0]), Compatibility::Breaking);
+
+        let (mut rpc, mut bytecode) = generic_struct_interfaces(&[], &[]);
+        rpc["modules"]["m"]["exposedFunctions"]["unwrap"] = serde_json::json!({
+            "visibility": "Public", "isEntry": false,
+            "typeParameters": [{"abilities": []}, {"abilities": []}],
+            "parameters": [], "return": []
+        });
+        bytecode["modules"]["m"]["functions"]["unwrap"] = serde_json::json!({
+            "visibility": "public", "is_entry": false, "is_native": false,
+            "type_params": [], "params": [], "returns": []
+        });
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].kind, MismatchKind::GenericityChanged);
+        assert_eq!(
+            mismatches[0].reason,
+            "genericity changed (rpc=2 bytecode=0)"
+        );
+        assert_eq!(classify(&mismatches[0]), Compatibility::Breaking);
+    }
+
+    #[test]
+    fn test_check_against_policy() {
+        let with_extra_function = |mut bytecode: Value| {
+            bytecode["modules"]["m"]["functions"]["new_fn"] = serde_json::json!({
+                "visibility": "public", "is_entry": false, "is_native": false,
+                "type_params": [], "params": [], "returns": []
+            });
+            bytecode
+        };
+        let (old, unchanged) = generic_struct_interfaces(&["Copy"], &["copy"]);
+        let (_, added) = generic_struct_interfaces(&["Copy"], &["copy", "drop"]);
+        let (_, relaxed) = generic_struct_interfaces(&["Copy"], &[]);
+        let extended = with_extra_function(unchanged.clone());
+        let check = |new: &Value, policy| check_against_policy(&old, new, policy);
+
+        // Struct type params are fixed once published, even relaxing `copy` is rejected.
+        let result = check(&relaxed, UpgradePolicy::Compatible);
+        assert!(!result.permitted);
+        assert_eq!(
+            result.disallowed[0].path,
+            "modules/m/structs/Box/type_params[0]/constraints/copy"
+        );
+        let result = check(&added, UpgradePolicy::Compatible);
+        assert!(!result.permitted);
+        assert_eq!(
+            result.disallowed[0].path,
+            "modules/m/structs/Box/type_params[0]/constraints/drop"
+        );
+
+        assert!(check(&extended, UpgradePolicy::Additive).permitted);
+        let result = check(&relaxed, UpgradePolicy::Additive);
+        assert!(!result.permitted);
+        assert_eq!(result.disallowed.len(), 1);
+
+        assert!(check(&unchanged, UpgradePolicy::DepOnly).permitted);
+        let result = check(&extended, UpgradePolicy::DepOnly);
+        assert!(!result.permitted);
+        assert_eq!(result.disallowed[0].path, "modules/m/functions/new_fn");
+
+        let result = check(&unchanged, UpgradePolicy::Immutable);
+        assert!(!result.permitted);
+        assert!(result.disallowed.is_empty());
+        assert_eq!(
+            check(&extended, UpgradePolicy::Immutable).disallowed.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_check_against_policy_added_items() {
+        let (old, unchanged) = generic_struct_interfaces(&["Copy"], &["copy"]);
+        let mut with_struct = unchanged.clone();
+        with_struct["modules"]["m"]["structs"]["Extra"] = serde_json::json!({
+            "abilities": ["drop"], "type_params": [], "is_native": false,
+            "fields": [{"name": "value", "type": "U64"}]
+        });
+        let mut with_private_fn = unchanged.clone();
+        with_private_fn["modules"]["m"]["functions"]["helper"] = serde_json::json!({
+            "visibility": "private", "is_entry": false, "is_native": false,
+            "type_params": [], "params": [], "returns": []
+        });
+        let check = |new: &Value, policy| check_against_policy(&old, new, policy);
+
+        assert!(check(&with_struct, UpgradePolicy::Compatible).permitted);
+        assert!(check(&with_struct, UpgradePolicy::Additive).permitted);
+        let result = check(&with_struct, UpgradePolicy::DepOnly);
+        assert!(!result.permitted);
+        assert_eq!(result.disallowed[0].path, "modules/m/structs/Extra");
+
+        // RPC never lists private helpers, so one that was always there must not count.
+        assert!(check(&with_private_fn, UpgradePolicy::Additive).permitted);
+        assert!(check(&with_private_fn, UpgradePolicy::DepOnly).permitted);
+
+        for (visibility, is_entry) in [("public", false), ("private", true)] {
+            let mut with_visible_fn = unchanged.clone();
+            with_visible_fn["modules"]["m"]["functions"]["run"] = serde_json::json!({
+                "visibility": visibility, "is_entry": is_entry, "is_native": false,
+                "type_params": [], "params": [], "returns": []
+            });
+            assert!(check(&with_visible_fn, UpgradePolicy::Additive).permitted);
+            let result = check(&with_visible_fn, UpgradePolicy::DepOnly);
+            assert!(!result.permitted);
+            assert_eq!(result.disallowed[0].path, "modules/m/functions/run");
+        }
+    }
+
+    #[test]
+    fn test_check_against_policy_non_public_functions() {
+        let (mut old, unchanged) = generic_struct_interfaces(&["Copy"], &["copy"]);
+        for (name, visibility, is_entry) in [
+            ("helper", "Friend", false),
+            ("run", "Private", true),
+            ("api", "Public", false),
+        ] {
+            old["modules"]["m"]["exposedFunctions"][name] = serde_json::json!({
+                "visibility": visibility, "isEntry": is_entry,
+                "typeParameters": [], "parameters": ["U64"], "return": []
+            });
+        }
+        let fun = |visibility: &str, is_entry: bool, param: &str| {
+            serde_json::json!({
+                "visibility": visibility, "is_entry": is_entry, "is_native": false,
+                "type_params": [], "params": [{"kind": param}], "returns": []
+            })
+        };
+        let mut new = unchanged.clone();
+        new["modules"]["m"]["functions"]["run"] = fun("private", true, "u128");
+        new["modules"]["m"]["functions"]["api"] = fun("public", false, "u64");
+        let check = |new: &Value, policy| check_against_policy(&old, new, policy);
+
+        // Dropping a `public(package)` function and changing an entry one are both allowed.
+        assert!(check(&new, UpgradePolicy::Compatible).permitted);
+        assert!(check(&new, UpgradePolicy::Additive).permitted);
+        assert_eq!(check(&new, UpgradePolicy::DepOnly).disallowed.len(), 2);
+
+        new["modules"]["m"]["functions"]["api"] = fun("public", false, "u128");
+        let result = check(&new, UpgradePolicy::Compatible);
+        assert!(!result.permitted);
+        assert_eq!(result.disallowed.len(), 1);
+        assert!(result.disallowed[0]
+            .path
+            .starts_with("modules/m/functions/api"));
+    }
+
+    #[test]
+    fn test_recommend_bump() {
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+
+        let (rpc, bytecode) = generic_struct_interfaces(&[], &["store"]);
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        let report = upgrade_report(&mismatches);
+        assert_eq!(recommend_bump(&report), SemverBump::Major);
+        assert_eq!(deciding_changes(&report).len(), 1);
+
+        let (rpc, bytecode) = generic_struct_interfaces(&["Store"], &[]);
+        let (_, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        let report = upgrade_report(&mismatches);
+        assert_eq!(recommend_bump(&report), SemverBump::Minor);
+        assert_eq!(
+            deciding_changes(&report)[0].path,
+            "modules/m/structs/Box/type_params[0]/constraints/store"
+        );
+
+        let (rpc, bytecode) = generic_struct_interfaces(&["Store"], &["store"]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        let report = upgrade_report(&mismatches);
+        assert_eq!(recommend_bump(&report), SemverBump::None);
+        assert!(deciding_changes(&report).is_empty());
+    }
+
+    #[test]
+    fn test_provides_all_ignores_extras_and_flags_missing() {
+        let fun = serde_json::json!({
+            "visibility": "public",
+            "is_entry": false,
+            "type_params": [],
+            "params": [{"kind": "u64"}],
+            "returns": []
+        });
+        let required = serde_json::json!({
+            "modules": {"pool": {"structs": {}, "exposedFunctions": {"swap": {
+                "visibility": "Public",
+                "isEntry": false,
+                "typeParameters": [],
+                "parameters": ["U64"],
+                "return": []
+            }}}}
+        });
+        let actual = serde_json::json!({
+            "modules": {
+                "pool": {"structs": {}, "functions": {"swap": fun, "deposit": fun}},
+                "extra": {"structs": {}, "functions": {}}
+            }
+        });
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let (ok, mismatches) = provides_all(&required, &actual, &opts);
+        assert!(ok);
+        assert!(mismatches.is_empty());
+
+        let without_swap = serde_json::json!({
+            "modules": {"pool": {"structs": {}, "functions": {"deposit": fun}}}
+        });
+        let (ok, mismatches) = provides_all(&required, &without_swap, &opts);
+        assert!(!ok);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "modules/pool/functions/swap");
+        assert_eq!(mismatches[0].kind, MismatchKind::FunctionMissing);
+    }
+
+    #[test]
+    fn test_store_loss_flags_dynamic_field_impact_as_breaking() {
+        let (rpc, mut bytecode) = generic_struct_interfaces(&[], &[]);
+        bytecode["modules"]["m"]["structs"]["Box"]["abilities"] = serde_json::json!([]);
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let store = mismatches
+            .iter()
+            .find(|m| m.kind == MismatchKind::DynamicFieldStoreLost)
+            .unwrap();
+        assert_eq!(store.path, "modules/m/structs/Box/store");
+        assert!(store.reason.contains("dynamic field"));
+        assert_eq!(classify(store), Compatibility::Breaking);
+    }
+}
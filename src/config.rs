@@ -0,0 +1,204 @@
+use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::comparator::{compare_interface_rpc_vs_bytecode_with_verdicts, InterfaceCompareOptions};
+use crate::report::{
+    render_csv, render_github_summary, render_html, render_markdown, render_terminal, write_jsonl,
+};
+use crate::types::{
+    FunctionVerdict, InterfaceCompareMismatch, InterfaceCompareReport, InterfaceCompareSummary,
+    MismatchSeverity, SchemaSource,
+};
+
+/// Rewrites an interface document before it is compared (e.g. remapping addresses or lowering
+/// types a schema spells differently). Called once per side.
+pub type TypeNormalizer = Arc<dyn Fn(SchemaSource, &mut Value) + Send + Sync>;
+
+/// When a comparison counts as failed; see `InterfaceCompareSummary::exit_code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComparePolicy {
+    pub fail_on: MismatchSeverity,
+}
+
+impl Default for ComparePolicy {
+    fn default() -> Self {
+        ComparePolicy {
+            fail_on: MismatchSeverity::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyOutcome {
+    pub passed: bool,
+    pub exit_code: i32,
+}
+
+/// Which `report` renderer `CompareConfig::run` uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Html,
+    GithubSummary,
+    /// `render_terminal` without color.
+    Terminal,
+    Csv,
+    /// One JSON mismatch per line, as `write_jsonl` writes.
+    Jsonl,
+}
+
+/// Everything one comparison needs, for callers that would otherwise wire options, a
+/// normalizer, a pass/fail policy and a renderer together themselves.
+#[derive(Clone, Default)]
+pub struct CompareConfig {
+    pub options: InterfaceCompareOptions,
+    pub normalizer: Option<TypeNormalizer>,
+    pub policy: ComparePolicy,
+    pub format: ReportFormat,
+}
+
+impl fmt::Debug for CompareConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompareConfig")
+            .field("options", &self.options)
+            .field("normalizer", &self.normalizer.is_some())
+            .field("policy", &self.policy)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfiguredOutcome {
+    pub summary: InterfaceCompareSummary,
+    pub mismatches: Vec<InterfaceCompareMismatch>,
+    pub policy: PolicyOutcome,
+    pub report: String,
+    /// Set when `options.per_function_verdicts` is.
+    pub function_verdicts: Option<Vec<FunctionVerdict>>,
+}
+
+impl CompareConfig {
+    /// Normalize, compare, apply the policy and render, in one call.
+    pub fn run<'a>(
+        &self,
+        package_id: &str,
+        rpc: &'a Value,
+        bytecode: &'a Value,
+    ) -> ConfiguredOutcome {
+        let normalized = |source, value: &'a Value| -> Cow<'a, Value> {
+            match &self.normalizer {
+                Some(normalizer) => {
+                    let mut value = value.clone();
+                    normalizer(source, &mut value);
+                    Cow::Owned(value)
+                }
+                None => Cow::Borrowed(value),
+            }
+        };
+        let rpc = normalized(SchemaSource::Rpc, rpc);
+        let bytecode = normalized(SchemaSource::Bytecode, bytecode);
+        let (summary, mismatches, verdicts) = compare_interface_rpc_vs_bytecode_with_verdicts(
+            package_id,
+            &rpc,
+            &bytecode,
+            self.options.clone(),
+        );
+        let report = InterfaceCompareReport::new(package_id.to_string(), summary, mismatches);
+
+        let (summary, mismatches) = (&report.summary, &report.mismatches);
+        let rendered = match self.format {
+            ReportFormat::Markdown => {
+                render_markdown(summary, mismatches, None, MismatchSeverity::Info)
+            }
+            ReportFormat::Html => render_html(summary, mismatches, None, MismatchSeverity::Info),
+            ReportFormat::GithubSummary => render_github_summary(summary, mismatches),
+            ReportFormat::Terminal => render_terminal(&report, false),
+            ReportFormat::Csv => render_csv(mismatches),
+            ReportFormat::Jsonl => {
+                let mut out = Vec::new();
+                write_jsonl(&mut out, mismatches, MismatchSeverity::Info)
+                    .ok()
+                    .and_then(|_| String::from_utf8(out).ok())
+                    .unwrap_or_default()
+            }
+        };
+        let exit_code = summary.exit_code(self.policy.fail_on);
+        ConfiguredOutcome {
+            summary: report.summary,
+            mismatches: report.mismatches,
+            policy: PolicyOutcome {
+                passed: exit_code == 0,
+                exit_code,
+            },
+            report: rendered,
+            function_verdicts: verdicts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MismatchKind;
+
+    #[test]
+    fn test_compare_config_run_end_to_end_with_failing_policy() {
+        let rpc = serde_json::json!({"modules": {"pool": {"structs": {}, "exposedFunctions": {
+            "swap": {"visibility": "Public", "isEntry": false, "typeParameters": [], "parameters": ["U64"], "return": []}
+        }}}});
+        let bytecode = serde_json::json!({"modules": {"pool": {"structs": {}, "functions": {
+            "swap": {"visibility": "public", "is_entry": false, "type_params": [], "params": [{"kind": "u128"}], "returns": []}
+        }}}});
+        let config = CompareConfig {
+            options: InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+            format: ReportFormat::GithubSummary,
+            ..Default::default()
+        };
+        let outcome = config.run("0x1", &rpc, &bytecode);
+        assert_eq!(outcome.summary.error_mismatches, 1);
+        assert_eq!(outcome.mismatches[0].kind, MismatchKind::ParamTypeMismatch);
+        assert_eq!(
+            outcome.policy,
+            PolicyOutcome {
+                passed: false,
+                exit_code: 1
+            }
+        );
+        assert!(outcome
+            .report
+            .contains("<details><summary>❌ 1 error</summary>"));
+
+        let jsonl = CompareConfig {
+            format: ReportFormat::Jsonl,
+            ..config.clone()
+        }
+        .run("0x1", &rpc, &bytecode);
+        let lines: Vec<&str> = jsonl.report.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let line: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(line["kind"], "param_type_mismatch");
+
+        // A normalizer that widens the RPC side makes the same inputs pass.
+        let widen: TypeNormalizer = Arc::new(|source, value: &mut Value| {
+            if source == SchemaSource::Rpc {
+                value["modules"]["pool"]["exposedFunctions"]["swap"]["parameters"] =
+                    serde_json::json!(["U128"]);
+            }
+        });
+        let outcome = CompareConfig {
+            normalizer: Some(widen),
+            ..config
+        }
+        .run("0x1", &rpc, &bytecode);
+        assert!(outcome.policy.passed);
+        assert!(outcome.mismatches.is_empty());
+        assert!(outcome.function_verdicts.is_none());
+    }
+}
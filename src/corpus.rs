@@ -198,6 +198,7 @@ pub async fn run_corpus(args: &Args, client: Arc<sui_sdk::SuiClient>) -> Result<
         let compare_opts = InterfaceCompareOptions {
             max_mismatches: corpus_max_mismatches,
             include_values: corpus_include_values,
+            ..Default::default()
         };
 
         join_set.spawn(async move {
@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+
+use crate::args::RetryConfig;
+use crate::rpc::{build_interface_value_for_package, fetch_package_digest};
+
+/// Fetch failures callers may want to match on (wrapped in `anyhow::Error`; use `downcast_ref`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The package object's digest is not the one the caller expected, i.e. a different upgrade.
+    DigestMismatch { expected: String, actual: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::DigestMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "package digest mismatch: expected {expected}, got {actual}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Check a fetched package digest against the expected one (base58, compared exactly).
+pub fn verify_digest(expected: &str, actual: &str) -> std::result::Result<(), FetchError> {
+    if expected.trim() == actual.trim() {
+        Ok(())
+    } else {
+        Err(FetchError::DigestMismatch {
+            expected: expected.trim().to_string(),
+            actual: actual.trim().to_string(),
+        })
+    }
+}
+
+/// Transport seam for fetching one package's RPC interface JSON.
+pub trait InterfaceFetcher: Send + Sync + 'static {
+    fn fetch(&self, package_id: &str) -> impl Future<Output = Result<Value>> + Send;
+}
+
+pub struct RpcFetcher {
+    pub client: Arc<SuiClient>,
+    pub retry: RetryConfig,
+}
+
+impl InterfaceFetcher for RpcFetcher {
+    async fn fetch(&self, package_id: &str) -> Result<Value> {
+        let id = ObjectID::from_str(package_id).map_err(|e| anyhow!("invalid id: {}", e))?;
+        let (_, value) =
+            build_interface_value_for_package(Arc::clone(&self.client), id, self.retry).await?;
+        Ok(value)
+    }
+}
+
+/// Fetch a package interface only after confirming the package object has `expected_digest`.
+pub async fn fetch_verified(
+    client: Arc<SuiClient>,
+    package_id: &str,
+    expected_digest: &str,
+    retry: RetryConfig,
+) -> Result<Value> {
+    let id = ObjectID::from_str(package_id).map_err(|e| anyhow!("invalid id: {}", e))?;
+    let actual = fetch_package_digest(Arc::clone(&client), id, retry).await?;
+    verify_digest(expected_digest, &actual)?;
+    let (_, value) = build_interface_value_for_package(client, id, retry).await?;
+    Ok(value)
+}
+
+/// Fetch every id with at most `concurrency` requests in flight. Results keep the input order
+/// and a failing package only affects its own entry.
+pub async fn fetch_many_with<F: InterfaceFetcher>(
+    fetcher: Arc<F>,
+    ids: &[String],
+    concurrency: usize,
+) -> Vec<(String, Result<Value>)> {
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut join_set: tokio::task::JoinSet<(usize, Result<Value>)> = tokio::task::JoinSet::new();
+    for (i, id) in ids.iter().enumerate() {
+        let fetcher = Arc::clone(&fetcher);
+        let semaphore = Arc::clone(&semaphore);
+        let id = id.clone();
+        join_set.spawn(async move {
+            let result = match semaphore.acquire_owned().await {
+                Ok(_permit) => fetcher.fetch(&id).await,
+                Err(e) => Err(anyhow!("semaphore closed: {}", e)),
+            };
+            (i, result)
+        });
+    }
+
+    let mut results: Vec<Option<Result<Value>>> = ids.iter().map(|_| None).collect();
+    while let Some(joined) = join_set.join_next().await {
+        if let Ok((i, result)) = joined {
+            results[i] = Some(result);
+        }
+    }
+    ids.iter()
+        .cloned()
+        .zip(results)
+        .map(|(id, r)| (id, r.unwrap_or_else(|| Err(anyhow!("fetch task failed")))))
+        .collect()
+}
+
+/// `fetch_many_with` over a fresh client for `endpoint`, retrying each fetch per `retry`.
+pub async fn fetch_many(
+    endpoint: &str,
+    ids: &[String],
+    concurrency: usize,
+    retry: RetryConfig,
+) -> Vec<(String, Result<Value>)> {
+    let client = match SuiClientBuilder::default().build(endpoint).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            let msg = format!("{:#}", e);
+            return ids
+                .iter()
+                .map(|id| (id.clone(), Err(anyhow!("build client: {}", msg))))
+                .collect();
+        }
+    };
+    let fetcher = RpcFetcher { client, retry };
+    fetch_many_with(Arc::new(fetcher), ids, concurrency).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MockFetcher {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl InterfaceFetcher for MockFetcher {
+        async fn fetch(&self, package_id: &str) -> Result<Value> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            for _ in 0..4 {
+                tokio::task::yield_now().await;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            if package_id == "0xbad" {
+                return Err(anyhow!("not found"));
+            }
+            Ok(serde_json::json!({"package_id": package_id}))
+        }
+    }
+
+    #[test]
+    fn test_verify_digest() {
+        let digest = "8Ck2hjXTvXbQnCkVLdfTHAtMHmpKwZL1JaD8FEjPrQ7s";
+        assert_eq!(verify_digest(digest, digest), Ok(()));
+        let err =
+            verify_digest(digest, "9Dk2hjXTvXbQnCkVLdfTHAtMHmpKwZL1JaD8FEjPrQ7s").unwrap_err();
+        assert_eq!(
+            err,
+            FetchError::DigestMismatch {
+                expected: digest.to_string(),
+                actual: "9Dk2hjXTvXbQnCkVLdfTHAtMHmpKwZL1JaD8FEjPrQ7s".to_string(),
+            }
+        );
+        let wrapped = anyhow::Error::from(err);
+        assert!(wrapped.downcast_ref::<FetchError>().is_some());
+        assert!(wrapped.to_string().starts_with("package digest mismatch"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_respects_concurrency_and_isolates_errors() {
+        let ids: Vec<String> = (0..10)
+            .map(|i| format!("0x{i}"))
+            .chain(["0xbad".to_string()])
+            .collect();
+        let fetcher = Arc::new(MockFetcher::default());
+        let results = fetch_many_with(Arc::clone(&fetcher), &ids, 3).await;
+
+        assert_eq!(
+            results.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            ids.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(results.iter().filter(|(_, r)| r.is_ok()).count(), 10);
+        assert!(results[10].1.is_err());
+        let max = fetcher.max_in_flight.load(Ordering::SeqCst);
+        assert!((1..=3).contains(&max), "max in flight = {max}");
+    }
+}
@@ -1,9 +1,19 @@
 pub mod args;
 pub mod bytecode;
+pub mod catalog;
 pub mod comparator;
+pub mod compat;
+pub mod config;
+#[cfg(feature = "rpc")]
 pub mod corpus;
+#[cfg(feature = "rpc")]
+pub mod fetch;
 pub mod normalization;
+pub mod report;
+#[cfg(feature = "rpc")]
 pub mod rpc;
+#[cfg(feature = "rpc")]
 pub mod runner;
 pub mod types;
 pub mod utils;
+pub mod workspace;
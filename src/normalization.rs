@@ -1,8 +1,29 @@
-use crate::types::BytecodeStructRefJson;
+use crate::bytecode::{
+    build_bytecode_interface_value_from_compiled_modules, decode_module_map_entry_bytes,
+};
+use crate::types::{
+    BytecodeStructRefJson, InputFormat, SchemaSource, SizeEstimate, UnknownTypePolicy,
+};
 use crate::utils::bytes_to_hex_prefixed;
-use anyhow::{anyhow, Result};
-use move_binary_format::file_format::{CompiledModule, SignatureToken};
+use anyhow::{anyhow, Context, Result};
+use move_binary_format::file_format::{Ability, AbilitySet, CompiledModule, SignatureToken};
 use serde_json::Value;
+use std::borrow::Cow;
+
+/// Parse an interface document into the `serde_json::Value` shape the comparator consumes.
+pub fn parse_interface_str(s: &str, fmt: InputFormat) -> Result<Value> {
+    match fmt {
+        InputFormat::Json => serde_json::from_str(s).context("parse JSON interface"),
+        #[cfg(feature = "yaml")]
+        InputFormat::Yaml => serde_yaml::from_str(s).context("parse YAML interface"),
+        #[cfg(not(feature = "yaml"))]
+        InputFormat::Yaml => Err(anyhow!("YAML input requires the `yaml` feature")),
+        #[cfg(feature = "toml")]
+        InputFormat::Toml => toml::from_str(s).context("parse TOML interface"),
+        #[cfg(not(feature = "toml"))]
+        InputFormat::Toml => Err(anyhow!("TOML input requires the `toml` feature")),
+    }
+}
 
 pub fn normalize_address_str(addr: &str) -> Result<String> {
     let s = addr.trim();
@@ -23,12 +44,35 @@ pub fn normalize_address_str(addr: &str) -> Result<String> {
     Ok(format!("0x{:0>64}", hex))
 }
 
+/// Strip a generic-arity suffix some extractors append to struct names (`Table<2>` -> `Table`).
+pub fn bare_struct_name(name: &str) -> &str {
+    match name.find('<') {
+        Some(i) if name.ends_with('>') => &name[..i],
+        _ => name,
+    }
+}
+
+/// Map an RPC visibility to the bytecode token (`public`/`friend`/`private`). Accepts a bare
+/// string (`"Public"`), a unit-variant object (`{"Public": null}`) or a wrapper
+/// (`{"visibility": "Public"}`); `Package` (`public(package)`) maps to `friend`.
 pub fn rpc_visibility_to_string(v: &Value) -> Option<String> {
-    let s = v.as_str()?;
-    match s {
-        "Public" => Some("public".to_string()),
-        "Friend" => Some("friend".to_string()),
-        "Private" => Some("private".to_string()),
+    let s = match v {
+        Value::String(s) => s.as_str(),
+        Value::Object(obj) => {
+            if let Some(inner) = obj.get("visibility") {
+                return rpc_visibility_to_string(inner);
+            }
+            if obj.len() != 1 {
+                return None;
+            }
+            obj.keys().next()?.as_str()
+        }
+        _ => return None,
+    };
+    match s.to_ascii_lowercase().as_str() {
+        "public" => Some("public".to_string()),
+        "friend" | "package" => Some("friend".to_string()),
+        "private" => Some("private".to_string()),
         _ => None,
     }
 }
@@ -55,7 +99,111 @@ pub fn abilities_from_value(value: &Value) -> Vec<String> {
     Vec::new()
 }
 
+/// Canonical type JSON. Datatypes always reduce to the nominal `{address, module, name,
+/// type_args}` form; structure an extractor inlined (e.g. `fields`) is dropped.
 pub fn rpc_type_to_canonical_json(v: &Value) -> Result<Value> {
+    rpc_type_to_canonical(v, false)
+}
+
+/// Rewrite type-parameter references by name (`{"TypeParameter": "T"}`, or a bytecode
+/// `{"kind": "type_param", "name": "T"}` without an index) to their index in `declared`, the
+/// enclosing struct's or function's type-param list (entries with a `name`). Unknown names and
+/// index-based references are left alone.
+pub fn resolve_type_param_names<'a>(v: &'a Value, declared: &[Value]) -> Cow<'a, Value> {
+    if !declared.iter().any(|tp| tp.get("name").is_some()) {
+        return Cow::Borrowed(v);
+    }
+    fn walk(v: &Value, declared: &[Value]) -> Value {
+        let index_of = |name: &str| {
+            declared
+                .iter()
+                .position(|tp| tp.get("name").and_then(Value::as_str) == Some(name))
+        };
+        match v {
+            Value::Object(obj) => {
+                if let Some(idx) = obj
+                    .get("TypeParameter")
+                    .and_then(Value::as_str)
+                    .and_then(index_of)
+                {
+                    return serde_json::json!({ "TypeParameter": idx });
+                }
+                if obj.get("kind").and_then(Value::as_str) == Some("type_param")
+                    && obj.get("index").is_none()
+                {
+                    if let Some(idx) = obj.get("name").and_then(Value::as_str).and_then(index_of) {
+                        return serde_json::json!({"kind": "type_param", "index": idx});
+                    }
+                }
+                Value::Object(
+                    obj.iter()
+                        .map(|(k, v)| (k.clone(), walk(v, declared)))
+                        .collect(),
+                )
+            }
+            Value::Array(items) => Value::Array(items.iter().map(|v| walk(v, declared)).collect()),
+            other => other.clone(),
+        }
+    }
+    Cow::Owned(walk(v, declared))
+}
+
+/// Move source spelling of a canonical type, e.g. `&mut 0x…02::coin::Coin<T0>`. Type params are
+/// written `T<index>`; opaque nodes fall back to their raw JSON.
+pub fn canonical_type_to_move_string(canon: &Value) -> String {
+    let kind = canon.get("kind").and_then(Value::as_str).unwrap_or("");
+    match kind {
+        "vector" => format!(
+            "vector<{}>",
+            canonical_type_to_move_string(canon.get("type").unwrap_or(&Value::Null))
+        ),
+        "ref" => format!(
+            "{}{}",
+            if canon.get("mutable").and_then(Value::as_bool) == Some(true) {
+                "&mut "
+            } else {
+                "&"
+            },
+            canonical_type_to_move_string(canon.get("to").unwrap_or(&Value::Null))
+        ),
+        "type_param" => format!(
+            "T{}",
+            canon.get("index").and_then(Value::as_u64).unwrap_or(0)
+        ),
+        "datatype" => {
+            let field = |k: &str| canon.get(k).and_then(Value::as_str).unwrap_or("?");
+            let args: Vec<String> = canon
+                .get("type_args")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(canonical_type_to_move_string)
+                .collect();
+            let base = format!(
+                "{}::{}::{}",
+                field("address"),
+                field("module"),
+                field("name")
+            );
+            if args.is_empty() {
+                base
+            } else {
+                format!("{base}<{}>", args.join(", "))
+            }
+        }
+        "opaque" => canon.get("raw").unwrap_or(&Value::Null).to_string(),
+        _ if !kind.is_empty() => kind.to_string(),
+        _ => canon.to_string(),
+    }
+}
+
+/// Stable stand-in for a type kind the canonicalizer does not know, keeping the raw JSON so
+/// identical unknown types still compare equal.
+fn opaque_type(v: &Value) -> Value {
+    serde_json::json!({"kind": "opaque", "raw": v})
+}
+
+fn rpc_type_to_canonical(v: &Value, pass_through: bool) -> Result<Value> {
     if let Some(s) = v.as_str() {
         let out = match s {
             "Bool" => serde_json::json!({"kind": "bool"}),
@@ -67,6 +215,7 @@ pub fn rpc_type_to_canonical_json(v: &Value) -> Result<Value> {
             "U256" => serde_json::json!({"kind": "u256"}),
             "Address" => serde_json::json!({"kind": "address"}),
             "Signer" => serde_json::json!({"kind": "signer"}),
+            _ if pass_through => opaque_type(v),
             other => return Err(anyhow!("unknown RPC primitive type string: {}", other)),
         };
         return Ok(out);
@@ -90,13 +239,13 @@ pub fn rpc_type_to_canonical_json(v: &Value) -> Result<Value> {
         "Address" => serde_json::json!({"kind": "address"}),
         "Signer" => serde_json::json!({"kind": "signer"}),
         "Vector" => {
-            serde_json::json!({"kind": "vector", "type": rpc_type_to_canonical_json(inner)?})
+            serde_json::json!({"kind": "vector", "type": rpc_type_to_canonical(inner, pass_through)?})
         }
         "Reference" => {
-            serde_json::json!({"kind": "ref", "mutable": false, "to": rpc_type_to_canonical_json(inner)?})
+            serde_json::json!({"kind": "ref", "mutable": false, "to": rpc_type_to_canonical(inner, pass_through)?})
         }
         "MutableReference" => {
-            serde_json::json!({"kind": "ref", "mutable": true, "to": rpc_type_to_canonical_json(inner)?})
+            serde_json::json!({"kind": "ref", "mutable": true, "to": rpc_type_to_canonical(inner, pass_through)?})
         }
         "TypeParameter" => {
             let idx = inner
@@ -126,7 +275,7 @@ pub fn rpc_type_to_canonical_json(v: &Value) -> Result<Value> {
                 .ok_or_else(|| anyhow!("Struct missing typeArguments: {}", inner))?;
             let args_canon: Vec<Value> = args
                 .iter()
-                .map(rpc_type_to_canonical_json)
+                .map(|a| rpc_type_to_canonical(a, pass_through))
                 .collect::<Result<_>>()?;
             serde_json::json!({
                 "kind": "datatype",
@@ -136,19 +285,28 @@ pub fn rpc_type_to_canonical_json(v: &Value) -> Result<Value> {
                 "type_args": args_canon,
             })
         }
+        _ if pass_through => opaque_type(v),
         _ => return Err(anyhow!("unknown RPC type tag: {}", k)),
     };
     Ok(out)
 }
 
+/// Bytecode counterpart of `rpc_type_to_canonical_json`, with the same nominal datatype form.
 pub fn bytecode_type_to_canonical_json(v: &Value) -> Result<Value> {
+    bytecode_type_to_canonical(v, false)
+}
+
+fn bytecode_type_to_canonical(v: &Value, pass_through: bool) -> Result<Value> {
     let obj = v
         .as_object()
         .ok_or_else(|| anyhow!("bytecode type is not object: {}", v))?;
-    let kind = obj
-        .get("kind")
-        .and_then(Value::as_str)
-        .ok_or_else(|| anyhow!("bytecode type missing kind: {}", v))?;
+    let Some(kind) = obj.get("kind").and_then(Value::as_str) else {
+        // A kind-less object is most likely a newer encoding (e.g. an RPC-style tag).
+        if pass_through {
+            return Ok(opaque_type(v));
+        }
+        return Err(anyhow!("bytecode type missing kind: {}", v));
+    };
     match kind {
         "datatype" => {
             let addr = obj
@@ -169,7 +327,7 @@ pub fn bytecode_type_to_canonical_json(v: &Value) -> Result<Value> {
                 .ok_or_else(|| anyhow!("bytecode datatype missing type_args: {}", v))?;
             let args_canon: Vec<Value> = args
                 .iter()
-                .map(bytecode_type_to_canonical_json)
+                .map(|a| bytecode_type_to_canonical(a, pass_through))
                 .collect::<Result<_>>()?;
             Ok(serde_json::json!({
                 "kind": "datatype",
@@ -184,7 +342,7 @@ pub fn bytecode_type_to_canonical_json(v: &Value) -> Result<Value> {
                 .get("type")
                 .ok_or_else(|| anyhow!("vector missing type: {}", v))?;
             Ok(
-                serde_json::json!({"kind": "vector", "type": bytecode_type_to_canonical_json(inner)?}),
+                serde_json::json!({"kind": "vector", "type": bytecode_type_to_canonical(inner, pass_through)?}),
             )
         }
         "ref" => {
@@ -196,7 +354,7 @@ pub fn bytecode_type_to_canonical_json(v: &Value) -> Result<Value> {
                 .get("to")
                 .ok_or_else(|| anyhow!("ref missing to: {}", v))?;
             Ok(
-                serde_json::json!({"kind":"ref","mutable":mutable,"to": bytecode_type_to_canonical_json(inner)?}),
+                serde_json::json!({"kind":"ref","mutable":mutable,"to": bytecode_type_to_canonical(inner, pass_through)?}),
             )
         }
         "type_param" => {
@@ -209,10 +367,257 @@ pub fn bytecode_type_to_canonical_json(v: &Value) -> Result<Value> {
         "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "u256" | "address" | "signer" => {
             Ok(serde_json::json!({"kind": kind}))
         }
+        _ if pass_through => Ok(opaque_type(v)),
         _ => Err(anyhow!("unknown bytecode type kind: {}", kind)),
     }
 }
 
+/// Parse lowercase/capitalized ability names (`"store"`, `"Store"`) into an `AbilitySet`.
+pub fn ability_set_from_value(value: &Value) -> AbilitySet {
+    abilities_from_value(value)
+        .iter()
+        .filter_map(|a| match a.as_str() {
+            "copy" => Some(Ability::Copy),
+            "drop" => Some(Ability::Drop),
+            "store" => Some(Ability::Store),
+            "key" => Some(Ability::Key),
+            _ => None,
+        })
+        .fold(AbilitySet::EMPTY, |set, a| set | a)
+}
+
+/// Effective abilities of a canonical field type, resolving datatypes against `struct_decls` (an
+/// interface `modules` object in either schema). A generic instantiation keeps a declared
+/// ability only if every non-phantom type argument has the ability it requires. Returns `None`
+/// when the answer depends on something not in `struct_decls` (a type parameter, or a datatype
+/// declared in another package).
+pub fn effective_field_abilities(field_type: &Value, struct_decls: &Value) -> Option<AbilitySet> {
+    match field_type.get("kind").and_then(Value::as_str)? {
+        "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "u256" | "address" => {
+            Some(AbilitySet::PRIMITIVES)
+        }
+        "signer" => Some(AbilitySet::SIGNER),
+        "ref" => Some(AbilitySet::REFERENCES),
+        "vector" => Some(AbilitySet::VECTOR.intersect(effective_field_abilities(
+            field_type.get("type")?,
+            struct_decls,
+        )?)),
+        "datatype" => {
+            let module_name = field_type.get("module").and_then(Value::as_str)?;
+            let name = field_type.get("name").and_then(Value::as_str)?;
+            let module = struct_decls.get(module_name)?;
+            if let (Some(a), Some(b)) = (
+                module.get("address").and_then(Value::as_str),
+                field_type.get("address").and_then(Value::as_str),
+            ) {
+                if normalize_address_str(a).ok()? != normalize_address_str(b).ok()? {
+                    return None;
+                }
+            }
+            let decl = module
+                .get("structs")
+                .and_then(Value::as_object)?
+                .iter()
+                .find(|(k, _)| bare_struct_name(k) == name)
+                .map(|(_, v)| v)?;
+            let declared = ability_set_from_value(decl.get("abilities")?);
+            let empty = Vec::new();
+            let params = decl
+                .get("type_params")
+                .or_else(|| decl.get("typeParameters"))
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            let args = field_type
+                .get("type_args")
+                .and_then(Value::as_array)
+                .unwrap_or(&empty);
+            let mut arg_abilities = Vec::new();
+            for (i, arg) in args.iter().enumerate() {
+                let phantom = params.get(i).is_some_and(|p| {
+                    p.get("is_phantom")
+                        .or_else(|| p.get("isPhantom"))
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false)
+                });
+                if !phantom {
+                    arg_abilities.push(effective_field_abilities(arg, struct_decls)?);
+                }
+            }
+            Some(
+                declared
+                    .into_iter()
+                    .filter(|a| {
+                        arg_abilities
+                            .iter()
+                            .all(|arg| arg.has_ability(a.requires()))
+                    })
+                    .fold(AbilitySet::EMPTY, |set, a| set | a),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Nested datatypes followed by `estimate_struct_size` before giving up.
+const SIZE_ESTIMATE_MAX_DEPTH: usize = 16;
+
+/// Best-effort BCS size of a struct declaration (either schema) from its field types, resolving
+/// datatypes declared in `struct_decls` (an interface `modules` object) and a few well-known
+/// framework types. Returns `None` for native or generic-dependent layouts and for datatypes
+/// declared elsewhere.
+pub fn estimate_struct_size(
+    struct_value: &Value,
+    source: SchemaSource,
+    struct_decls: &Value,
+) -> Option<SizeEstimate> {
+    struct_size(struct_value, &[], source, struct_decls, 0)
+}
+
+fn struct_size(
+    decl: &Value,
+    type_args: &[Value],
+    source: SchemaSource,
+    struct_decls: &Value,
+    depth: usize,
+) -> Option<SizeEstimate> {
+    if depth > SIZE_ESTIMATE_MAX_DEPTH
+        || decl.get("is_native").and_then(Value::as_bool) == Some(true)
+    {
+        return None;
+    }
+    let mut total = SizeEstimate::fixed(0);
+    for field in decl.get("fields")?.as_array()? {
+        let canon = type_to_canonical_json(source, field.get("type")?).ok()?;
+        let canon = substitute_type_params(&canon, type_args)?;
+        total = total.plus(canonical_size(&canon, source, struct_decls, depth)?);
+    }
+    Some(total)
+}
+
+/// `canon` with every `type_param` node replaced by the matching entry of `type_args`.
+fn substitute_type_params(canon: &Value, type_args: &[Value]) -> Option<Value> {
+    match canon {
+        Value::Object(obj) if obj.get("kind").and_then(Value::as_str) == Some("type_param") => {
+            let idx = obj.get("index").and_then(Value::as_u64)? as usize;
+            type_args.get(idx).cloned()
+        }
+        Value::Object(obj) => obj
+            .iter()
+            .map(|(k, v)| Some((k.clone(), substitute_type_params(v, type_args)?)))
+            .collect::<Option<serde_json::Map<_, _>>>()
+            .map(Value::Object),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| substitute_type_params(v, type_args))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        other => Some(other.clone()),
+    }
+}
+
+fn canonical_size(
+    canon: &Value,
+    source: SchemaSource,
+    struct_decls: &Value,
+    depth: usize,
+) -> Option<SizeEstimate> {
+    let variable = SizeEstimate { min: 1, max: None };
+    match canon.get("kind").and_then(Value::as_str)? {
+        "bool" | "u8" => Some(SizeEstimate::fixed(1)),
+        "u16" => Some(SizeEstimate::fixed(2)),
+        "u32" => Some(SizeEstimate::fixed(4)),
+        "u64" => Some(SizeEstimate::fixed(8)),
+        "u128" => Some(SizeEstimate::fixed(16)),
+        "u256" | "address" => Some(SizeEstimate::fixed(32)),
+        // ULEB128 length prefix, then any number of elements.
+        "vector" => Some(variable),
+        "datatype" => {
+            let module_name = canon.get("module").and_then(Value::as_str)?;
+            let name = canon.get("name").and_then(Value::as_str)?;
+            let args = canon
+                .get("type_args")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            if is_canonical_datatype(canon, "0x2", "object", "UID")
+                || is_canonical_datatype(canon, "0x2", "object", "ID")
+            {
+                return Some(SizeEstimate::fixed(32));
+            }
+            if is_canonical_datatype(canon, "0x2", "balance", "Balance") {
+                return Some(SizeEstimate::fixed(8));
+            }
+            if is_canonical_datatype(canon, "0x1", "string", "String")
+                || is_canonical_datatype(canon, "0x1", "ascii", "String")
+            {
+                return Some(variable);
+            }
+            if is_canonical_datatype(canon, "0x1", "option", "Option") {
+                let inner = canonical_size(args.first()?, source, struct_decls, depth + 1)?;
+                return Some(SizeEstimate {
+                    min: 1,
+                    max: inner.max.map(|m| m + 1),
+                });
+            }
+            let module = struct_decls.get(module_name)?;
+            if let (Some(a), Some(b)) = (
+                module.get("address").and_then(Value::as_str),
+                canon.get("address").and_then(Value::as_str),
+            ) {
+                if normalize_address_str(a).ok()? != normalize_address_str(b).ok()? {
+                    return None;
+                }
+            }
+            let decl = module
+                .get("structs")
+                .and_then(Value::as_object)?
+                .iter()
+                .find(|(k, _)| bare_struct_name(k) == name)
+                .map(|(_, v)| v)?;
+            struct_size(decl, &args, source, struct_decls, depth + 1)
+        }
+        _ => None,
+    }
+}
+
+/// True if `canon` is the canonical form of the datatype `address::module::name` (any type args).
+pub fn is_canonical_datatype(canon: &Value, address: &str, module: &str, name: &str) -> bool {
+    if canon.get("kind").and_then(Value::as_str) != Some("datatype") {
+        return false;
+    }
+    let same_address = match (
+        canon.get("address").and_then(Value::as_str),
+        normalize_address_str(address),
+    ) {
+        (Some(a), Ok(b)) => normalize_address_str(a).is_ok_and(|a| a == b),
+        _ => false,
+    };
+    same_address
+        && canon.get("module").and_then(Value::as_str) == Some(module)
+        && canon.get("name").and_then(Value::as_str) == Some(name)
+}
+
+/// `type_to_canonical_json` honoring `policy`: under `PassThrough`, unknown kinds become an
+/// opaque `{"kind": "opaque", "raw": ...}` node instead of an error.
+pub fn type_to_canonical_json_with(
+    source: SchemaSource,
+    v: &Value,
+    policy: UnknownTypePolicy,
+) -> Result<Value> {
+    let pass_through = policy == UnknownTypePolicy::PassThrough;
+    match source {
+        SchemaSource::Rpc => rpc_type_to_canonical(v, pass_through),
+        SchemaSource::Bytecode => bytecode_type_to_canonical(v, pass_through),
+    }
+}
+
+pub fn type_to_canonical_json(source: SchemaSource, v: &Value) -> Result<Value> {
+    match source {
+        SchemaSource::Rpc => rpc_type_to_canonical_json(v),
+        SchemaSource::Bytecode => bytecode_type_to_canonical_json(v),
+    }
+}
+
 pub fn signature_token_to_json(module: &CompiledModule, tok: &SignatureToken) -> Value {
     match tok {
         SignatureToken::Bool => serde_json::json!({"kind": "bool"}),
@@ -268,10 +673,196 @@ fn module_id_for_datatype_handle(
     }
 }
 
+/// Convert package JSON printed by the `sui` CLI into the bytecode interface form
+/// (`{"modules": ...}`), so local CLI output can be compared directly. Accepts
+/// `sui move build --dump-bytecode-as-base64` output (`modules`: base64 module bytes, plus
+/// `dependencies`) and `sui client object <id> --bcs --json` output for a package (`bcs.moduleMap`,
+/// plus `id`, `version` and `linkageTable`). Declared dependencies are kept as a top-level
+/// `dependencies` array of addresses and the package version as `version`.
+pub fn from_sui_client_json(value: &Value) -> Result<Value> {
+    let package = value
+        .get("bcs")
+        .or_else(|| value.get("content"))
+        .unwrap_or(value);
+    let encoded: Vec<(String, &Value)> =
+        if let Some(map) = package.get("moduleMap").and_then(Value::as_object) {
+            map.iter().map(|(name, v)| (name.clone(), v)).collect()
+        } else if let Some(list) = package.get("modules").and_then(Value::as_array) {
+            list.iter()
+                .enumerate()
+                .map(|(i, v)| (format!("modules[{i}]"), v))
+                .collect()
+        } else if package.get("disassembled").is_some() {
+            return Err(anyhow!(
+                "sui CLI JSON only has disassembled modules; re-run `sui client object` with --bcs"
+            ));
+        } else {
+            return Err(anyhow!(
+                "unrecognized sui CLI JSON: expected bcs.moduleMap or a modules array"
+            ));
+        };
+
+    let mut compiled = Vec::with_capacity(encoded.len());
+    for (name, v) in encoded {
+        let bytes = decode_module_map_entry_bytes(&name, v)?;
+        let module = CompiledModule::deserialize_with_defaults(&bytes)
+            .map_err(|e| anyhow!("deserialize {}: {}", name, e))?;
+        compiled.push(module);
+    }
+    let package_id = package
+        .get("id")
+        .or_else(|| value.get("objectId"))
+        .and_then(Value::as_str)
+        .unwrap_or("0x0");
+    let (_, mut interface) =
+        build_bytecode_interface_value_from_compiled_modules(package_id, &compiled)?;
+
+    let dependencies: Option<Vec<Value>> = match (
+        package.get("dependencies").and_then(Value::as_array),
+        package.get("linkageTable").and_then(Value::as_object),
+    ) {
+        (Some(deps), _) => Some(deps.clone()),
+        (_, Some(linkage)) => Some(linkage.keys().cloned().map(Value::String).collect()),
+        _ => None,
+    };
+    if let Some(dependencies) = dependencies {
+        interface["dependencies"] = Value::Array(dependencies);
+    }
+    if let Some(version) = package.get("version").or_else(|| value.get("version")) {
+        interface["version"] = version.clone();
+    }
+    Ok(interface)
+}
+
+/// Convert an RPC-schema interface (`exposedFunctions`, `typeParameters`, ...) into the bytecode
+/// schema, e.g. to compare two RPC fetches. Types go through their canonical form, which is the
+/// bytecode encoding; a type that does not parse is kept as is, so the comparison still reports
+/// it. Top-level keys other than `modules` and module `address`/`name` are kept.
+pub fn rpc_interface_to_bytecode_schema(rpc: &Value) -> Value {
+    let types = |list: Option<&Value>| -> Value {
+        list.and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|t| rpc_type_to_canonical_json(t).unwrap_or_else(|_| t.clone()))
+            .collect()
+    };
+    let convert_struct = |s: &Value| {
+        let type_params: Vec<Value> = s
+            .get("typeParameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|tp| {
+                serde_json::json!({
+                    "constraints": abilities_from_value(tp.get("constraints").unwrap_or(tp)),
+                    "is_phantom": tp.get("isPhantom").and_then(Value::as_bool).unwrap_or(false),
+                })
+            })
+            .collect();
+        let fields: Vec<Value> = s
+            .get("fields")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|f| {
+                let ty = f.get("type").unwrap_or(&Value::Null);
+                serde_json::json!({
+                    "name": f.get("name").cloned().unwrap_or(Value::Null),
+                    "type": rpc_type_to_canonical_json(ty).unwrap_or_else(|_| ty.clone()),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "abilities": abilities_from_value(s.get("abilities").unwrap_or(&Value::Null)),
+            "type_params": type_params,
+            "is_native": false,
+            "fields": fields,
+        })
+    };
+    let convert_function = |f: &Value| {
+        let type_params: Vec<Value> = f
+            .get("typeParameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|tp| serde_json::json!({"constraints": abilities_from_value(tp)}))
+            .collect();
+        serde_json::json!({
+            "visibility": f
+                .get("visibility")
+                .and_then(rpc_visibility_to_string)
+                .unwrap_or_else(|| "private".to_string()),
+            "is_entry": f.get("isEntry").and_then(Value::as_bool).unwrap_or(false),
+            "is_native": false,
+            "type_params": type_params,
+            "params": types(f.get("parameters")),
+            "returns": types(f.get("return")),
+        })
+    };
+
+    let mut out = rpc.as_object().cloned().unwrap_or_default();
+    let modules: serde_json::Map<String, Value> = rpc
+        .get("modules")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, m)| {
+            let mut module = serde_json::Map::new();
+            for key in ["address", "name"] {
+                if let Some(v) = m.get(key) {
+                    module.insert(key.to_string(), v.clone());
+                }
+            }
+            let structs: serde_json::Map<String, Value> = m
+                .get("structs")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(n, s)| (n.clone(), convert_struct(s)))
+                .collect();
+            let functions: serde_json::Map<String, Value> = m
+                .get("exposedFunctions")
+                .or_else(|| m.get("exposed_functions"))
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(n, f)| (n.clone(), convert_function(f)))
+                .collect();
+            module.insert("structs".to_string(), Value::Object(structs));
+            module.insert("functions".to_string(), Value::Object(functions));
+            (name.clone(), Value::Object(module))
+        })
+        .collect();
+    out.insert("modules".to_string(), Value::Object(modules));
+    Value::Object(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_sui_client_json_object_bcs_fixture() {
+        let value: Value =
+            serde_json::from_str(include_str!("../tests/fixture/sui_client_object_bcs.json"))
+                .unwrap();
+        let adapted = from_sui_client_json(&value).unwrap();
+        let module = &adapted["modules"]["test_module"];
+        assert!(module["functions"].get("simple_func").is_some());
+        assert!(module["structs"].get("SimpleStruct").is_some());
+        assert_eq!(
+            adapted["dependencies"],
+            serde_json::json!([
+                "0x0000000000000000000000000000000000000000000000000000000000000002"
+            ])
+        );
+        assert_eq!(adapted["version"], 3);
+
+        let err = from_sui_client_json(&serde_json::json!({"content": {"disassembled": {}}}))
+            .unwrap_err();
+        assert!(err.to_string().contains("--bcs"));
+    }
+
     #[test]
     fn test_normalize_address_str_pads_to_32_bytes() {
         assert_eq!(
@@ -284,6 +875,87 @@ mod tests {
         );
     }
 
+    fn expected_parsed_interface() -> Value {
+        serde_json::json!({
+            "modules": {
+                "m": {
+                    "structs": {
+                        "S": {"abilities": ["store"], "fields": [{"name": "x", "type": {"kind": "u64"}}]}
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_interface_str_json() {
+        let s = r#"{"modules":{"m":{"structs":{"S":{"abilities":["store"],"fields":[{"name":"x","type":{"kind":"u64"}}]}}}}}"#;
+        assert_eq!(
+            parse_interface_str(s, InputFormat::Json).unwrap(),
+            expected_parsed_interface()
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_parse_interface_str_yaml() {
+        let s = "
+modules:
+  m:
+    structs:
+      S:
+        abilities: [store]
+        fields:
+          - name: x
+            type: {kind: u64}
+";
+        assert_eq!(
+            parse_interface_str(s, InputFormat::Yaml).unwrap(),
+            expected_parsed_interface()
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_parse_interface_str_toml() {
+        let s = r#"
+[modules.m.structs.S]
+abilities = ["store"]
+
+[[modules.m.structs.S.fields]]
+name = "x"
+type = { kind = "u64" }
+"#;
+        assert_eq!(
+            parse_interface_str(s, InputFormat::Toml).unwrap(),
+            expected_parsed_interface()
+        );
+    }
+
+    #[test]
+    fn test_rpc_visibility_to_string_accepts_all_shapes() {
+        for (name, expected) in [
+            ("Public", "public"),
+            ("Private", "private"),
+            ("Friend", "friend"),
+            ("Package", "friend"),
+        ] {
+            let shapes = [
+                serde_json::json!(name),
+                serde_json::json!({ name: null }),
+                serde_json::json!({ "visibility": name }),
+            ];
+            for shape in shapes {
+                assert_eq!(
+                    rpc_visibility_to_string(&shape).as_deref(),
+                    Some(expected),
+                    "{shape}"
+                );
+            }
+        }
+        assert_eq!(rpc_visibility_to_string(&serde_json::json!("Weird")), None);
+    }
+
     #[test]
     fn test_rpc_type_to_canonical_handles_string_primitives() {
         assert_eq!(
@@ -318,4 +990,77 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_inlined_struct_fields_unify_with_nominal_reference() {
+        let inlined = serde_json::json!({
+            "Struct": {
+                "address": "0x2",
+                "module": "balance",
+                "name": "Balance",
+                "typeArguments": [{"TypeParameter": 0}],
+                "fields": [{"name": "value", "type": "U64"}]
+            }
+        });
+        let nominal = serde_json::json!({
+            "kind": "datatype",
+            "address": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "module": "balance",
+            "name": "Balance",
+            "type_args": [{"kind": "type_param", "index": 0}]
+        });
+        assert_eq!(
+            rpc_type_to_canonical_json(&inlined).unwrap(),
+            bytecode_type_to_canonical_json(&nominal).unwrap()
+        );
+
+        let mut byte_inlined = nominal.clone();
+        byte_inlined["fields"] = serde_json::json!([{"name": "value", "type": {"kind": "u64"}}]);
+        assert_eq!(
+            bytecode_type_to_canonical_json(&byte_inlined).unwrap(),
+            bytecode_type_to_canonical_json(&nominal).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_struct_size_resolves_nested_and_variable_fields() {
+        let decls = serde_json::json!({
+            "pool": {
+                "address": "0x1",
+                "structs": {
+                    "Pool": {
+                        "is_native": false,
+                        "fields": [
+                            {"name": "id", "type": {"kind": "datatype", "address": "0x2", "module": "object", "name": "UID", "type_args": []}},
+                            {"name": "fees", "type": {"kind": "datatype", "address": "0x1", "module": "pool", "name": "Fees", "type_args": [{"kind": "u64"}]}},
+                            {"name": "tag", "type": {"kind": "vector", "type": {"kind": "u8"}}}
+                        ]
+                    },
+                    "Fees": {
+                        "is_native": false,
+                        "fields": [{"name": "amount", "type": {"kind": "type_param", "index": 0}}]
+                    }
+                }
+            }
+        });
+        let pool = &decls["pool"]["structs"]["Pool"];
+        assert_eq!(
+            estimate_struct_size(pool, SchemaSource::Bytecode, &decls),
+            Some(SizeEstimate { min: 41, max: None })
+        );
+
+        let mut grown = pool.clone();
+        grown["fields"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({"name": "fee", "type": {"kind": "u128"}}));
+        let grown = estimate_struct_size(&grown, SchemaSource::Bytecode, &decls).unwrap();
+        assert_eq!(grown.min, 41 + 16);
+
+        let generic = &decls["pool"]["structs"]["Fees"];
+        assert_eq!(
+            estimate_struct_size(generic, SchemaSource::Bytecode, &decls),
+            None
+        );
+    }
 }
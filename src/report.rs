@@ -0,0 +1,1194 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{IsTerminal, Write};
+
+use crate::normalization::canonical_type_to_move_string;
+use crate::types::{
+    InterfaceCompareMismatch, InterfaceCompareReport, InterfaceCompareSummary, MismatchKind,
+    MismatchSeverity, ModuleBreakdown,
+};
+
+const SUMMARY_COLUMNS: [&str; 8] = [
+    "modules compared",
+    "missing in bytecode",
+    "extra in bytecode",
+    "structs compared",
+    "struct mismatches",
+    "functions compared",
+    "function mismatches",
+    "mismatches total",
+];
+
+fn summary_values(summary: &InterfaceCompareSummary) -> [usize; 8] {
+    [
+        summary.modules_compared,
+        summary.modules_missing_in_bytecode,
+        summary.modules_extra_in_bytecode,
+        summary.structs_compared,
+        summary.struct_mismatches,
+        summary.functions_compared,
+        summary.function_mismatches,
+        summary.mismatches_total,
+    ]
+}
+
+/// Module a mismatch path belongs to (`modules/<module>/...`).
+pub fn module_of_path(path: &str) -> Option<&str> {
+    path.strip_prefix("modules/")?.split('/').next()
+}
+
+/// Group mismatches by module name (sorted), preserving mismatch order within a module.
+pub fn group_by_module<'a>(
+    mismatches: impl IntoIterator<Item = &'a InterfaceCompareMismatch>,
+) -> BTreeMap<&'a str, Vec<&'a InterfaceCompareMismatch>> {
+    let mut groups: BTreeMap<&str, Vec<&InterfaceCompareMismatch>> = BTreeMap::new();
+    for m in mismatches {
+        groups
+            .entry(module_of_path(&m.path).unwrap_or("<package>"))
+            .or_default()
+            .push(m);
+    }
+    groups
+}
+
+/// Number of mismatches of each kind.
+pub fn kind_histogram(mismatches: &[InterfaceCompareMismatch]) -> BTreeMap<MismatchKind, usize> {
+    let mut hist = BTreeMap::new();
+    for m in mismatches {
+        *hist.entry(m.kind).or_insert(0) += 1;
+    }
+    hist
+}
+
+/// Per-kind change from `old_hist` to `new_hist` (positive: more mismatches of that kind), for
+/// trend tracking across runs. Kinds whose count did not change are omitted.
+pub fn histogram_delta(
+    old_hist: &BTreeMap<MismatchKind, usize>,
+    new_hist: &BTreeMap<MismatchKind, usize>,
+) -> Vec<(MismatchKind, i64)> {
+    let kinds: BTreeSet<MismatchKind> = old_hist.keys().chain(new_hist.keys()).copied().collect();
+    kinds
+        .into_iter()
+        .filter_map(|kind| {
+            let count = |h: &BTreeMap<MismatchKind, usize>| h.get(&kind).copied().unwrap_or(0);
+            let delta = count(new_hist) as i64 - count(old_hist) as i64;
+            (delta != 0).then_some((kind, delta))
+        })
+        .collect()
+}
+
+fn severity_label(m: &InterfaceCompareMismatch) -> String {
+    serde_json::to_value(m.severity)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Mismatches at or above `min_severity`.
+fn visible(
+    mismatches: &[InterfaceCompareMismatch],
+    min_severity: MismatchSeverity,
+) -> Vec<&InterfaceCompareMismatch> {
+    mismatches
+        .iter()
+        .filter(|m| m.severity >= min_severity)
+        .collect()
+}
+
+/// Summary-wide count of findings below `min_severity`, and the note saying they are hidden.
+fn hidden_note(
+    summary: &InterfaceCompareSummary,
+    min_severity: MismatchSeverity,
+) -> (usize, Option<String>) {
+    let levels = [
+        (
+            MismatchSeverity::Warning,
+            summary.warning_mismatches,
+            "warning",
+        ),
+        (MismatchSeverity::Info, summary.info_mismatches, "info"),
+    ];
+    let parts: Vec<(usize, String)> = levels
+        .into_iter()
+        .filter(|(level, count, _)| *level < min_severity && *count > 0)
+        .map(|(_, count, label)| (count, format!("{count} {label}-level")))
+        .collect();
+    if parts.is_empty() {
+        return (0, None);
+    }
+    let hidden = parts.iter().map(|(count, _)| count).sum();
+    let labels: Vec<String> = parts.into_iter().map(|(_, label)| label).collect();
+    (
+        hidden,
+        Some(format!(
+            "hiding {} {}",
+            labels.join(" and "),
+            if hidden == 1 { "item" } else { "items" }
+        )),
+    )
+}
+
+/// Markdown report: summary table, mismatches grouped by module, and (when a per-module
+/// breakdown is supplied) the modules that compared clean. Mismatches below `min_severity`
+/// are left out of the body; the summary table still counts them.
+pub fn render_markdown(
+    summary: &InterfaceCompareSummary,
+    mismatches: &[InterfaceCompareMismatch],
+    breakdown: Option<&ModuleBreakdown>,
+    min_severity: MismatchSeverity,
+) -> String {
+    let mut out = String::from("# Interface comparison\n\n");
+    out.push_str(&format!("| {} |\n", SUMMARY_COLUMNS.join(" | ")));
+    out.push_str(&format!("|{}\n", "---|".repeat(SUMMARY_COLUMNS.len())));
+    let values: Vec<String> = summary_values(summary)
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    out.push_str(&format!("| {} |\n", values.join(" | ")));
+    let (hidden, note) = hidden_note(summary, min_severity);
+    if let Some(note) = note {
+        out.push_str(&format!("\n_{note}_\n"));
+    }
+
+    let mismatches = visible(mismatches, min_severity);
+    if !mismatches.is_empty() {
+        out.push_str("\n## Mismatches\n");
+        let shown_total = summary.mismatches_total.saturating_sub(hidden);
+        if mismatches.len() < shown_total {
+            out.push_str(&format!(
+                "\nShowing {} of {} mismatches.\n",
+                mismatches.len(),
+                shown_total
+            ));
+        }
+        for (module, group) in group_by_module(mismatches.iter().copied()) {
+            out.push_str(&format!("\n### `{module}`\n\n"));
+            for m in group {
+                out.push_str(&format!(
+                    "- **{}** `{}`: {}\n",
+                    severity_label(m),
+                    m.path,
+                    m.reason
+                ));
+            }
+        }
+    }
+
+    if let Some(breakdown) = breakdown {
+        let clean = breakdown.clean_modules();
+        if !clean.is_empty() {
+            out.push_str("\n## ✅ Clean modules\n\n");
+            for module in clean {
+                out.push_str(&format!("- `{module}`\n"));
+            }
+        }
+    }
+    out
+}
+
+fn severity_emoji(severity: MismatchSeverity) -> &'static str {
+    match severity {
+        MismatchSeverity::Error => "❌",
+        MismatchSeverity::Warning => "⚠️",
+        MismatchSeverity::Info => "ℹ️",
+    }
+}
+
+/// GitHub-flavored summary for PR bots and `$GITHUB_STEP_SUMMARY`: a status line, a compact
+/// count table, and one collapsible `<details>` block per severity with mismatches grouped by
+/// module as in `render_markdown`.
+pub fn render_github_summary(
+    summary: &InterfaceCompareSummary,
+    mismatches: &[InterfaceCompareMismatch],
+) -> String {
+    let status = if summary.error_mismatches > 0 {
+        severity_emoji(MismatchSeverity::Error)
+    } else if summary.warning_mismatches > 0 {
+        severity_emoji(MismatchSeverity::Warning)
+    } else {
+        "✅"
+    };
+    let mut out = format!("## {status} Interface comparison\n\n");
+    out.push_str("| modules | structs | functions | mismatches |\n|---|---|---|---|\n");
+    out.push_str(&format!(
+        "| {} | {} | {} | {} |\n",
+        summary.modules_compared,
+        summary.structs_compared,
+        summary.functions_compared,
+        summary.mismatches_total
+    ));
+    let levels = [
+        (MismatchSeverity::Error, summary.error_mismatches, "error"),
+        (
+            MismatchSeverity::Warning,
+            summary.warning_mismatches,
+            "warning",
+        ),
+        (MismatchSeverity::Info, summary.info_mismatches, "info"),
+    ];
+    for (severity, count, label) in levels {
+        let shown: Vec<&InterfaceCompareMismatch> = mismatches
+            .iter()
+            .filter(|m| m.severity == severity)
+            .collect();
+        if count == 0 && shown.is_empty() {
+            continue;
+        }
+        let count = count.max(shown.len());
+        out.push_str(&format!(
+            "\n<details><summary>{} {}</summary>\n\n| module | path | reason |\n|---|---|---|\n",
+            severity_emoji(severity),
+            plural(count, label)
+        ));
+        for (module, group) in group_by_module(shown.iter().copied()) {
+            for m in group {
+                out.push_str(&format!(
+                    "| `{module}` | `{}` | {} |\n",
+                    m.path,
+                    m.reason.replace('|', "\\|")
+                ));
+            }
+        }
+        if shown.len() < count {
+            out.push_str(&format!("\n_{} not shown_\n", count - shown.len()));
+        }
+        out.push_str("\n</details>\n");
+    }
+    out
+}
+
+/// Whether terminal output should be colored: stdout is a terminal and `NO_COLOR` is unset or
+/// empty (<https://no-color.org>).
+pub fn color_from_env() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && std::io::stdout().is_terminal()
+}
+
+fn paint(text: &str, code: &str, use_color: bool) -> String {
+    if use_color {
+        format!("\x1b[{code}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+fn severity_color(severity: MismatchSeverity) -> &'static str {
+    match severity {
+        MismatchSeverity::Error => "31",
+        MismatchSeverity::Warning => "33",
+        MismatchSeverity::Info => "34",
+    }
+}
+
+/// Steps from a canonical type's root to the node a type-mismatch path points at: the longest
+/// trailing run of `type`/`to`/`type_args[j]` segments that can be followed from the root.
+fn diff_steps<'a>(canon: &Value, path: &'a str) -> Vec<&'a str> {
+    let segments: Vec<&str> = path.split('/').collect();
+    let trailing = segments
+        .iter()
+        .rev()
+        .take_while(|s| matches!(**s, "type" | "to") || s.starts_with("type_args["))
+        .count();
+    (0..=trailing)
+        .rev()
+        .map(|n| segments[segments.len() - n..].to_vec())
+        .find(|steps| sub_node(canon, steps).is_some())
+        .unwrap_or_default()
+}
+
+fn type_arg_index(step: &str) -> Option<usize> {
+    step.strip_prefix("type_args[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+fn sub_node<'v>(canon: &'v Value, steps: &[&str]) -> Option<&'v Value> {
+    let Some((step, rest)) = steps.split_first() else {
+        return Some(canon);
+    };
+    let kind = canon.get("kind").and_then(Value::as_str)?;
+    let next = match (*step, kind) {
+        ("type", "vector") => canon.get("type")?,
+        ("to", "ref") => canon.get("to")?,
+        (s, "datatype") => canon.get("type_args")?.get(type_arg_index(s)?)?,
+        _ => return None,
+    };
+    sub_node(next, rest)
+}
+
+/// Move spelling of `canon` with the node at `steps` emphasized (bold underline, or `[..]`
+/// without color).
+fn type_with_highlight(canon: &Value, steps: &[&str], use_color: bool) -> String {
+    const MARK: &str = "\u{1}";
+    let Some(target) = sub_node(canon, steps) else {
+        return canonical_type_to_move_string(canon);
+    };
+    let highlighted = canonical_type_to_move_string(target);
+    let highlighted = if use_color {
+        paint(&highlighted, "1;4", true)
+    } else {
+        format!("[{highlighted}]")
+    };
+    let mut marked = canon.clone();
+    let mut node = &mut marked;
+    for step in steps {
+        node = match *step {
+            "type" | "to" => &mut node[*step],
+            s => &mut node["type_args"][type_arg_index(s).unwrap_or_default()],
+        };
+    }
+    *node = serde_json::json!({ "kind": MARK });
+    canonical_type_to_move_string(&marked).replace(MARK, &highlighted)
+}
+
+fn side_text(whole: Option<&Value>, at: Option<&Value>, path: &str, use_color: bool) -> String {
+    match (whole, at) {
+        (Some(canon), _) => type_with_highlight(canon, &diff_steps(canon, path), use_color),
+        (None, Some(v)) if v.get("kind").is_some() => canonical_type_to_move_string(v),
+        (None, Some(v)) => v.to_string(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Indented terminal summary of a comparison, mismatches grouped by module. Severities are
+/// colored red/yellow/blue when `use_color` is set (see `color_from_env`), and for type
+/// mismatches carrying canonical types the differing sub-node is highlighted in context.
+pub fn render_terminal(report: &InterfaceCompareReport, use_color: bool) -> String {
+    let summary = &report.summary;
+    let mut out = format!(
+        "package {}: {} modules, {} structs, {} functions compared; {}, {}, {}\n",
+        report.package_id,
+        summary.modules_compared,
+        summary.structs_compared,
+        summary.functions_compared,
+        paint(
+            &plural(summary.error_mismatches, "error"),
+            severity_color(MismatchSeverity::Error),
+            use_color && summary.error_mismatches > 0
+        ),
+        paint(
+            &plural(summary.warning_mismatches, "warning"),
+            severity_color(MismatchSeverity::Warning),
+            use_color && summary.warning_mismatches > 0
+        ),
+        paint(
+            &format!("{} info", summary.info_mismatches),
+            severity_color(MismatchSeverity::Info),
+            use_color && summary.info_mismatches > 0
+        ),
+    );
+    for (module, group) in group_by_module(&report.mismatches) {
+        out.push_str(&format!("\n{}\n", paint(module, "1", use_color)));
+        for m in group {
+            let label = format!("{:<7}", severity_label(m));
+            out.push_str(&format!(
+                "  {} {}: {}\n",
+                paint(&label, severity_color(m.severity), use_color),
+                m.path,
+                m.reason
+            ));
+            if m.rpc.is_some() || m.rpc_canonical.is_some() {
+                out.push_str(&format!(
+                    "          rpc:      {}\n",
+                    side_text(m.rpc_canonical.as_ref(), m.rpc.as_ref(), &m.path, use_color)
+                ));
+            }
+            if m.bytecode.is_some() || m.bytecode_canonical.is_some() {
+                out.push_str(&format!(
+                    "          bytecode: {}\n",
+                    side_text(
+                        m.bytecode_canonical.as_ref(),
+                        m.bytecode.as_ref(),
+                        &m.path,
+                        use_color
+                    )
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// One mismatch as a pair of values for a two-column diff view.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SideBySideRow {
+    pub path: String,
+    pub rpc_repr: String,
+    pub bytecode_repr: String,
+    pub severity: MismatchSeverity,
+}
+
+/// Move spelling of a canonical type, compact JSON for any other value, empty when absent.
+fn side_repr(at: Option<&Value>, whole: Option<&Value>) -> String {
+    match at.or(whole) {
+        Some(v) if v.get("kind").is_some() => canonical_type_to_move_string(v),
+        Some(v) => v.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Pair each mismatch's RPC and bytecode values into one row, for side-by-side diff UIs.
+pub fn render_side_by_side(mismatches: &[InterfaceCompareMismatch]) -> Vec<SideBySideRow> {
+    mismatches
+        .iter()
+        .map(|m| SideBySideRow {
+            path: m.path.clone(),
+            rpc_repr: side_repr(m.rpc.as_ref(), m.rpc_canonical.as_ref()),
+            bytecode_repr: side_repr(m.bytecode.as_ref(), m.bytecode_canonical.as_ref()),
+            severity: m.severity,
+        })
+        .collect()
+}
+
+/// Append `value` pretty-printed, every line prefixed with `marker`.
+fn push_diff_lines(out: &mut String, marker: char, value: &Value) {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    for line in pretty.lines() {
+        out.push(marker);
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+/// Plain-text, unified-diff-style report for pasting into reviews: a `---`/`+++` header, a
+/// `#` line with the summary counts, then one `@@ module @@` hunk per module listing each
+/// mismatch's severity, path and reason, followed by its captured values as pretty JSON on `-`
+/// (rpc) and `+` (bytecode) lines. Values only appear when the comparison ran with
+/// `include_values`.
+pub fn render_text_report(
+    summary: &InterfaceCompareSummary,
+    mismatches: &[InterfaceCompareMismatch],
+) -> String {
+    let mut out = format!(
+        "--- rpc\n+++ bytecode\n# {summary} errors={} warnings={} infos={}\n",
+        summary.error_mismatches, summary.warning_mismatches, summary.info_mismatches
+    );
+    if mismatches.len() < summary.mismatches_total {
+        out.push_str(&format!(
+            "# showing {} of {} mismatches\n",
+            mismatches.len(),
+            summary.mismatches_total
+        ));
+    }
+    for (module, group) in group_by_module(mismatches) {
+        out.push_str(&format!("@@ {module} @@\n"));
+        for m in group {
+            out.push_str(&format!(
+                " [{}] {}: {}\n",
+                severity_label(m),
+                m.path,
+                m.reason
+            ));
+            if let Some(rpc) = &m.rpc {
+                push_diff_lines(&mut out, '-', rpc);
+            }
+            if let Some(bytecode) = &m.bytecode {
+                push_diff_lines(&mut out, '+', bytecode);
+            }
+        }
+    }
+    out
+}
+
+/// RFC 4180 cell: quoted (with `"` doubled) when it holds a comma, quote or line break.
+fn csv_cell(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// CSV with a `severity,kind,path,reason,rpc,bytecode` header and one row per mismatch;
+/// `rpc`/`bytecode` hold compact JSON (empty when not captured).
+pub fn render_csv(mismatches: &[InterfaceCompareMismatch]) -> String {
+    let mut out = String::from("severity,kind,path,reason,rpc,bytecode\n");
+    let json = |v: &Option<Value>| v.as_ref().map(Value::to_string).unwrap_or_default();
+    for m in mismatches {
+        let kind = serde_json::to_value(m.kind)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let cells = [
+            severity_label(m),
+            kind,
+            m.path.clone(),
+            m.reason.clone(),
+            json(&m.rpc),
+            json(&m.bytecode),
+        ];
+        let cells: Vec<String> = cells.iter().map(|c| csv_cell(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Lines written between explicit flushes in `write_jsonl`.
+const JSONL_FLUSH_EVERY: usize = 1024;
+
+/// Write mismatches (owned or borrowed) at or above `min_severity` as JSON Lines, one line
+/// per item as `mismatches` yields it. Returns the number of lines written.
+///
+/// This saves building the output string, not the mismatches themselves: the comparator entry
+/// points still return a collected `Vec`, so peak memory is no lower than for `render_csv`.
+pub fn write_jsonl<W, I>(
+    mut writer: W,
+    mismatches: I,
+    min_severity: MismatchSeverity,
+) -> Result<usize>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Borrow<InterfaceCompareMismatch>,
+{
+    let mut written = 0usize;
+    for m in mismatches
+        .into_iter()
+        .filter(|m| m.borrow().severity >= min_severity)
+    {
+        serde_json::to_writer(&mut writer, m.borrow()).context("serialize mismatch")?;
+        writer.write_all(b"\n").context("write mismatch line")?;
+        written += 1;
+        if written.is_multiple_of(JSONL_FLUSH_EVERY) {
+            writer.flush().context("flush mismatch lines")?;
+        }
+    }
+    writer.flush().context("flush mismatch lines")?;
+    Ok(written)
+}
+
+/// Readable form of a mismatch path, e.g. `modules/pool/functions/swap/params[1]` becomes
+/// ``function `pool::swap` parameter 2`` (indices shown 1-based).
+pub fn humanize_path(path: &str) -> String {
+    let mut segments = path.split('/');
+    let mut module: Option<&str> = None;
+    let mut owner: Option<String> = None;
+    let mut rest: Vec<String> = Vec::new();
+    while let Some(seg) = segments.next() {
+        match seg {
+            "modules" if module.is_none() => module = segments.next(),
+            "structs" | "functions" | "constants" if owner.is_none() => {
+                let kind = match seg {
+                    "structs" => "struct",
+                    "functions" => "function",
+                    _ => "constant",
+                };
+                let name = segments.next().unwrap_or("?");
+                owner = Some(match module {
+                    Some(m) => format!("{kind} `{m}::{name}`"),
+                    None => format!("{kind} `{name}`"),
+                });
+            }
+            _ => {
+                let indexed = seg
+                    .strip_suffix(']')
+                    .and_then(|s| s.split_once('['))
+                    .and_then(|(name, i)| Some((name, i.parse::<usize>().ok()?)));
+                rest.push(match indexed {
+                    Some((name, i)) => {
+                        let label = match name {
+                            "fields" => "field",
+                            "params" => "parameter",
+                            "returns" => "return value",
+                            "type_params" => "type parameter",
+                            other => other,
+                        };
+                        format!("{} {}", label, i + 1)
+                    }
+                    None => seg.replace('_', " "),
+                });
+            }
+        }
+    }
+    let head = owner.or_else(|| module.map(|m| format!("module `{m}`")));
+    head.into_iter().chain(rest).collect::<Vec<_>>().join(" ")
+}
+
+/// Maximum number of individual differences `explain` spells out.
+const EXPLAIN_MAX_ITEMS: usize = 5;
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("{n} {word}")
+    } else {
+        format!("{n} {word}s")
+    }
+}
+
+/// Prose summary of a comparison for non-expert readers. Modules with errors come first, and
+/// at most `EXPLAIN_MAX_ITEMS` differences (most severe first) are described individually.
+pub fn explain(
+    summary: &InterfaceCompareSummary,
+    mismatches: &[InterfaceCompareMismatch],
+) -> String {
+    let modules_total = summary.modules_compared
+        + summary.modules_missing_in_bytecode
+        + summary.modules_extra_in_bytecode;
+    if summary.mismatches_total == 0 {
+        return format!(
+            "The on-chain package matches the source: all {}, {} and {} compared identically.",
+            plural(modules_total, "module"),
+            plural(summary.structs_compared, "struct"),
+            plural(summary.functions_compared, "function")
+        );
+    }
+
+    let groups = group_by_module(mismatches);
+    let affected = groups.keys().filter(|m| **m != "<package>").count();
+    let mut sentences = vec![format!(
+        "The on-chain package matches the source for {} of {}.",
+        modules_total.saturating_sub(affected),
+        plural(modules_total, "module")
+    )];
+
+    let mut ordered: Vec<(&str, Vec<&InterfaceCompareMismatch>)> = groups.into_iter().collect();
+    let errors = |group: &[&InterfaceCompareMismatch]| {
+        group
+            .iter()
+            .filter(|m| m.severity == MismatchSeverity::Error)
+            .count()
+    };
+    ordered.sort_by_key(|(_, group)| std::cmp::Reverse(errors(group)));
+
+    let mut budget = EXPLAIN_MAX_ITEMS;
+    let mut omitted = 0usize;
+    for (module, mut group) in ordered {
+        group.sort_by_key(|m| std::cmp::Reverse(m.severity));
+        let breaking = errors(&group);
+        let other = group.len() - breaking;
+        let mut counts = Vec::new();
+        if breaking > 0 {
+            counts.push(plural(breaking, "breaking change"));
+        }
+        if other > 0 {
+            counts.push(plural(other, "minor difference"));
+        }
+        let shown: Vec<String> = group
+            .iter()
+            .take(budget)
+            .map(|m| format!("{} ({})", humanize_path(&m.path), m.reason))
+            .collect();
+        budget -= shown.len();
+        omitted += group.len() - shown.len();
+        let subject = if module == "<package>" {
+            "The package".to_string()
+        } else {
+            format!("The `{module}` module")
+        };
+        let mut sentence = format!("{subject} has {}", counts.join(" and "));
+        if !shown.is_empty() {
+            sentence.push_str(": ");
+            sentence.push_str(&shown.join("; "));
+        }
+        sentence.push('.');
+        sentences.push(sentence);
+    }
+    if omitted > 0 {
+        sentences.push(format!(
+            "{} not described here.",
+            plural(omitted, "further difference")
+        ));
+    }
+    if mismatches.len() < summary.mismatches_total {
+        sentences.push(format!(
+            "Only {} of {} differences were captured.",
+            mismatches.len(),
+            summary.mismatches_total
+        ));
+    }
+    sentences.join(" ")
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// HTML fragment with the same sections (and `min_severity` filtering) as `render_markdown`.
+pub fn render_html(
+    summary: &InterfaceCompareSummary,
+    mismatches: &[InterfaceCompareMismatch],
+    breakdown: Option<&ModuleBreakdown>,
+    min_severity: MismatchSeverity,
+) -> String {
+    let mut out = String::from("<h1>Interface comparison</h1>\n<table>\n<tr>");
+    for col in SUMMARY_COLUMNS {
+        out.push_str(&format!("<th>{col}</th>"));
+    }
+    out.push_str("</tr>\n<tr>");
+    for v in summary_values(summary) {
+        out.push_str(&format!("<td>{v}</td>"));
+    }
+    out.push_str("</tr>\n</table>\n");
+    if let Some(note) = hidden_note(summary, min_severity).1 {
+        out.push_str(&format!("<p><em>{note}</em></p>\n"));
+    }
+
+    let mismatches = visible(mismatches, min_severity);
+    if !mismatches.is_empty() {
+        out.push_str("<h2>Mismatches</h2>\n");
+        for (module, group) in group_by_module(mismatches.iter().copied()) {
+            out.push_str(&format!("<h3>{}</h3>\n<ul>\n", html_escape(module)));
+            for m in group {
+                out.push_str(&format!(
+                    "<li><strong>{}</strong> <code>{}</code>: {}</li>\n",
+                    severity_label(m),
+                    html_escape(&m.path),
+                    html_escape(&m.reason)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+
+    if let Some(breakdown) = breakdown {
+        let clean = breakdown.clean_modules();
+        if !clean.is_empty() {
+            out.push_str("<h2>✅ Clean modules</h2>\n<ul>\n");
+            for module in clean {
+                out.push_str(&format!("<li><code>{}</code></li>\n", html_escape(&module)));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparator::{
+        compare_interface_rpc_vs_bytecode, module_breakdown, InterfaceCompareOptions,
+    };
+    use crate::types::MismatchKind;
+
+    fn two_module_fixture() -> (serde_json::Value, serde_json::Value) {
+        let rpc_fun = serde_json::json!({
+            "visibility": "Public",
+            "isEntry": false,
+            "typeParameters": [],
+            "parameters": ["U64"],
+            "return": []
+        });
+        let rpc = serde_json::json!({
+            "modules": {
+                "clean": {"structs": {}, "exposedFunctions": {"f": rpc_fun}},
+                "pool": {"structs": {}, "exposedFunctions": {"swap": rpc_fun}}
+            }
+        });
+        let fun = |kind: &str| {
+            serde_json::json!({
+                "visibility": "public",
+                "is_entry": false,
+                "type_params": [],
+                "params": [{"kind": kind}],
+                "returns": []
+            })
+        };
+        let bytecode = serde_json::json!({
+            "modules": {
+                "clean": {"structs": {}, "functions": {"f": fun("u64")}},
+                "pool": {"structs": {}, "functions": {"swap": fun("u128")}}
+            }
+        });
+        (rpc, bytecode)
+    }
+
+    #[test]
+    fn test_render_markdown_lists_clean_modules_separately() {
+        let (rpc, bytecode) = two_module_fixture();
+        let opts = InterfaceCompareOptions {
+            max_mismatches: 10,
+            ..Default::default()
+        };
+        let breakdown = module_breakdown("0x1", &rpc, &bytecode, &opts);
+        assert_eq!(breakdown.clean_modules(), vec!["clean".to_string()]);
+
+        let (summary, mismatches) =
+            compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+        let md = render_markdown(
+            &summary,
+            &mismatches,
+            Some(&breakdown),
+            MismatchSeverity::Info,
+        );
+        let (mismatch_section, clean_section) = md.split_once("## ✅ Clean modules").unwrap();
+        assert!(mismatch_section.contains("### `pool`"));
+        assert!(!mismatch_section.contains("`clean`"));
+        assert!(clean_section.contains("- `clean`"));
+
+        let html = render_html(
+            &summary,
+            &mismatches,
+            Some(&breakdown),
+            MismatchSeverity::Info,
+        );
+        assert!(html.contains("<h2>✅ Clean modules</h2>"));
+        assert!(html.contains("<li><code>clean</code></li>"));
+
+        // A type from an undeclared package is only caught against the package's
+        // `dependencies`, so the breakdown must see them as the full comparison does.
+        let (mut rpc, mut bytecode) = two_module_fixture();
+        rpc["modules"]["linked"] = rpc["modules"]["clean"].clone();
+        rpc["modules"]["linked"]["exposedFunctions"]["f"]["parameters"] = serde_json::json!([
+            {"Struct": {"address": "0x99", "module": "coin", "name": "C", "typeArguments": []}}
+        ]);
+        bytecode["modules"]["linked"] = bytecode["modules"]["clean"].clone();
+        bytecode["modules"]["linked"]["functions"]["f"]["params"] = serde_json::json!([
+            {"kind": "datatype", "address": "0x99", "module": "coin", "name": "C", "type_args": []}
+        ]);
+        bytecode["dependencies"] = serde_json::json!(["0x2"]);
+        let breakdown = module_breakdown("0x1", &rpc, &bytecode, &opts);
+        assert_eq!(breakdown.clean_modules(), vec!["clean".to_string()]);
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts);
+        assert!(mismatches
+            .iter()
+            .any(|m| m.kind == MismatchKind::UnavailableTypeReference
+                && m.path.starts_with("modules/linked/")));
+        let md = render_markdown(
+            &summary,
+            &mismatches,
+            Some(&breakdown),
+            MismatchSeverity::Info,
+        );
+        let (_, clean_section) = md.split_once("## ✅ Clean modules").unwrap();
+        assert!(clean_section.contains("- `clean`"));
+        assert!(!clean_section.contains("`linked`"));
+    }
+
+    #[test]
+    fn test_explain_mentions_breaking_change_and_module() {
+        assert_eq!(
+            humanize_path("modules/pool/functions/swap/params[1]"),
+            "function `pool::swap` parameter 2"
+        );
+        let (rpc, bytecode) = two_module_fixture();
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let text = explain(&summary, &mismatches);
+        assert!(text.starts_with("The on-chain package matches the source for 1 of 2 modules."));
+        assert!(text.contains("The `pool` module has 1 breaking change"));
+        assert!(text.contains("function `pool::swap` parameter 1 (param type mismatch)"));
+    }
+
+    #[test]
+    fn test_write_jsonl_writes_one_line_per_mismatch() {
+        let mismatches = (0..2500).map(|i| InterfaceCompareMismatch {
+            path: format!("modules/m/functions/f{i}"),
+            reason: "function missing in bytecode".to_string(),
+            kind: MismatchKind::FunctionMissing,
+            severity: MismatchSeverity::Error,
+            accepted: false,
+            rpc: None,
+            bytecode: None,
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        });
+        let mut buf: Vec<u8> = Vec::new();
+        let written = write_jsonl(&mut buf, mismatches, MismatchSeverity::Info).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(written, 2500);
+        assert_eq!(text.lines().count(), 2500);
+        let last: serde_json::Value = serde_json::from_str(text.lines().last().unwrap()).unwrap();
+        assert_eq!(last["path"], "modules/m/functions/f2499");
+    }
+
+    #[test]
+    fn test_render_min_severity_hides_lower_levels_but_counts_them() {
+        let (rpc, bytecode) = two_module_fixture();
+        let (summary, mut mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let advisory = |path: &str, severity| InterfaceCompareMismatch {
+            path: path.to_string(),
+            reason: "advisory".to_string(),
+            kind: MismatchKind::Other,
+            severity,
+            accepted: false,
+            rpc: None,
+            bytecode: None,
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        };
+        mismatches.push(advisory("modules/clean/imports/x", MismatchSeverity::Info));
+        mismatches.push(advisory("modules/clean/imports/y", MismatchSeverity::Info));
+        mismatches.push(advisory(
+            "modules/pool/functions/swap/tx_context",
+            MismatchSeverity::Warning,
+        ));
+        let summary = InterfaceCompareSummary {
+            mismatches_total: summary.mismatches_total + 3,
+            warning_mismatches: 1,
+            info_mismatches: 2,
+            ..summary
+        };
+
+        let md = render_markdown(&summary, &mismatches, None, MismatchSeverity::Error);
+        assert!(md.contains("hiding 1 warning-level and 2 info-level items"));
+        assert!(md.contains(&format!("| {} |", summary.mismatches_total)));
+        assert!(md.contains("param type mismatch"));
+        assert!(!md.contains("advisory"));
+
+        let html = render_html(&summary, &mismatches, None, MismatchSeverity::Error);
+        assert!(html.contains("hiding 1 warning-level and 2 info-level items"));
+        assert!(!html.contains("advisory"));
+
+        let mut buf: Vec<u8> = Vec::new();
+        let written = write_jsonl(&mut buf, mismatches, MismatchSeverity::Warning).unwrap();
+        assert_eq!(written, summary.error_mismatches + 1);
+    }
+
+    #[test]
+    fn test_render_github_summary_has_details_per_severity() {
+        let (rpc, bytecode) = two_module_fixture();
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        let out = render_github_summary(&summary, &mismatches);
+        assert!(out.starts_with("## ❌ Interface comparison"));
+        assert!(out.contains("<details><summary>❌ 1 error</summary>"));
+        assert!(out.contains("| `pool` | `modules/pool/functions/swap/params[0]` |"));
+        assert_eq!(out.matches("<details>").count(), 1);
+        assert_eq!(out.matches("</details>").count(), 1);
+
+        let summary = InterfaceCompareSummary {
+            mismatches_total: 0,
+            error_mismatches: 0,
+            ..summary
+        };
+        let clean = render_github_summary(&summary, &[]);
+        assert!(clean.starts_with("## ✅ Interface comparison"));
+        assert!(!clean.contains("<details>"));
+    }
+
+    #[test]
+    fn test_render_terminal_highlights_nested_type_diff() {
+        let (mut rpc, mut bytecode) = two_module_fixture();
+        rpc["modules"]["pool"]["exposedFunctions"]["swap"]["parameters"] =
+            serde_json::json!([{"Vector": {"Vector": "U64"}}]);
+        bytecode["modules"]["pool"]["functions"]["swap"]["params"] = serde_json::json!([
+            {"kind": "vector", "type": {"kind": "vector", "type": {"kind": "u128"}}}
+        ]);
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                include_canonical: true,
+                ..Default::default()
+            },
+        );
+        let report = InterfaceCompareReport::new("0x1".to_string(), summary, mismatches);
+
+        let plain = render_terminal(&report, false);
+        assert!(
+            plain.starts_with("package 0x1: 2 modules, 0 structs, 2 functions compared; 1 error")
+        );
+        assert!(plain.contains("\npool\n  error   modules/pool/functions/swap/params[0]/type/type: param type mismatch"));
+        assert!(plain.contains("rpc:      vector<vector<[u64]>>"));
+        assert!(plain.contains("bytecode: vector<vector<[u128]>>"));
+        assert!(!plain.contains('\x1b'));
+
+        let colored = render_terminal(&report, true);
+        assert!(colored.contains("\x1b[31merror  \x1b[0m"));
+        assert!(colored.contains("vector<vector<\x1b[1;4mu128\x1b[0m>>"));
+    }
+
+    /// Minimal RFC 4180 reader for checking `render_csv` output.
+    fn parse_csv(text: &str) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, quoted) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                ('"', _) => quoted = !quoted,
+                (',', false) => row.push(std::mem::take(&mut cell)),
+                ('\n', false) => {
+                    row.push(std::mem::take(&mut cell));
+                    rows.push(std::mem::take(&mut row));
+                }
+                (c, _) => cell.push(c),
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_render_side_by_side_pairs_type_reprs() {
+        let (rpc, bytecode) = two_module_fixture();
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            render_side_by_side(&mismatches),
+            vec![SideBySideRow {
+                path: "modules/pool/functions/swap/params[0]".to_string(),
+                rpc_repr: "u64".to_string(),
+                bytecode_repr: "u128".to_string(),
+                severity: MismatchSeverity::Error,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_text_report_groups_by_module_with_diff_lines() {
+        let (mut rpc, mut bytecode) = two_module_fixture();
+        rpc["modules"]["gone"] = rpc["modules"]["clean"].clone();
+        bytecode["modules"]["pool"]["functions"]["swap"]["visibility"] =
+            serde_json::json!("friend");
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                include_values: true,
+                ..Default::default()
+            },
+        );
+        let text = render_text_report(&summary, &mismatches);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[..2], ["--- rpc", "+++ bytecode"]);
+        assert!(lines[2].starts_with("# modules_compared=2 "));
+        assert!(lines[2].ends_with(&format!(
+            "errors={} warnings=0 infos=0",
+            summary.error_mismatches
+        )));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|l| l.starts_with("@@"))
+                .collect::<Vec<_>>(),
+            vec![&"@@ gone @@", &"@@ pool @@"]
+        );
+        assert!(text.contains(
+            " [error] modules/pool/functions/swap/params[0]: param type mismatch\n\
+             -{\n-  \"kind\": \"u64\"\n-}\n+{\n+  \"kind\": \"u128\"\n+}\n"
+        ));
+        assert!(text.contains("-\"Public\"\n+\"friend\"\n"));
+
+        let (summary, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 1,
+                ..Default::default()
+            },
+        );
+        let text = render_text_report(&summary, &mismatches);
+        assert!(text.contains(&format!("# showing 1 of {}", summary.mismatches_total)));
+        assert!(!text
+            .lines()
+            .any(|l| l.starts_with('-') && !l.starts_with("---")));
+    }
+
+    #[test]
+    fn test_render_csv_escapes_commas_and_quotes() {
+        let mismatch = InterfaceCompareMismatch {
+            path: "modules/m/constants/NAME".to_string(),
+            reason: "constant mismatch".to_string(),
+            kind: MismatchKind::ConstantMismatch,
+            severity: MismatchSeverity::Error,
+            accepted: false,
+            rpc: Some(serde_json::json!({"value": "a,\"b\""})),
+            bytecode: Some(serde_json::json!("line\nbreak")),
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        };
+        let csv = render_csv(&[mismatch]);
+        assert!(csv.contains(r#""{""value"":""a,\""b\""""}""#));
+
+        let rows = parse_csv(&csv);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec!["severity", "kind", "path", "reason", "rpc", "bytecode"]
+        );
+        assert_eq!(rows[1][0], "error");
+        assert_eq!(rows[1][1], "constant_mismatch");
+        let rpc: serde_json::Value = serde_json::from_str(&rows[1][4]).unwrap();
+        assert_eq!(rpc["value"], "a,\"b\"");
+        assert_eq!(rows[1][5], r#""line\nbreak""#);
+    }
+
+    #[test]
+    fn test_histogram_delta_reports_increases_and_decreases() {
+        let old = BTreeMap::from([
+            (MismatchKind::ParamTypeMismatch, 3),
+            (MismatchKind::FunctionMissing, 1),
+            (MismatchKind::AbilitiesMismatch, 2),
+        ]);
+        let new = BTreeMap::from([
+            (MismatchKind::ParamTypeMismatch, 1),
+            (MismatchKind::FunctionMissing, 1),
+            (MismatchKind::AbilitiesMismatch, 5),
+            (MismatchKind::ConstantMismatch, 1),
+        ]);
+        let delta = histogram_delta(&old, &new);
+        assert_eq!(
+            delta,
+            vec![
+                (MismatchKind::AbilitiesMismatch, 3),
+                (MismatchKind::ConstantMismatch, 1),
+                (MismatchKind::ParamTypeMismatch, -2),
+            ]
+        );
+
+        let (rpc, bytecode) = two_module_fixture();
+        let (_, mismatches) = compare_interface_rpc_vs_bytecode(
+            "0x1",
+            &rpc,
+            &bytecode,
+            InterfaceCompareOptions {
+                max_mismatches: 10,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            kind_histogram(&mismatches),
+            BTreeMap::from([(MismatchKind::ParamTypeMismatch, 1)])
+        );
+    }
+}
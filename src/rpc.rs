@@ -52,6 +52,40 @@ pub async fn resolve_package_address_from_package_info(
         .map_err(|e| anyhow!("invalid package_address {}: {}", package_address, e))
 }
 
+/// Object digest (base58) of the package object as currently reported by the RPC.
+pub async fn fetch_package_digest(
+    client: Arc<sui_sdk::SuiClient>,
+    package_id: ObjectID,
+    retry: RetryConfig,
+) -> Result<String> {
+    let options = sui_sdk::rpc_types::SuiObjectDataOptions::new();
+    let resp = with_retries(
+        retry.retries,
+        retry.initial_backoff,
+        retry.max_backoff,
+        || {
+            let client = Arc::clone(&client);
+            let options = options.clone();
+            async move {
+                client
+                    .read_api()
+                    .get_object_with_options(package_id, options)
+                    .await
+                    .with_context(|| format!("fetch object {}", package_id))
+            }
+        },
+    )
+    .await?;
+
+    let value = serde_json::to_value(&resp).context("serialize object response")?;
+    value
+        .get("data")
+        .and_then(|d| d.get("digest"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("object {} missing data.digest", package_id))
+}
+
 pub async fn fetch_bcs_module_names(
     client: Arc<sui_sdk::SuiClient>,
     package_id: ObjectID,
@@ -146,26 +146,14 @@ pub async fn run_single(
                 InterfaceCompareOptions {
                     max_mismatches: args.compare_max_mismatches,
                     include_values: args.emit_compare_report.is_some(),
+                    ..Default::default()
                 },
             );
-            eprintln!(
-                "interface_compare: modules_compared={} modules_missing_in_bytecode={} modules_extra_in_bytecode={} structs_compared={} struct_mismatches={} functions_compared={} function_mismatches={} mismatches_total={}",
-                summary.modules_compared,
-                summary.modules_missing_in_bytecode,
-                summary.modules_extra_in_bytecode,
-                summary.structs_compared,
-                summary.struct_mismatches,
-                summary.functions_compared,
-                summary.function_mismatches,
-                summary.mismatches_total
-            );
+            eprintln!("interface_compare: {}", summary);
 
             if let Some(path) = args.emit_compare_report.as_ref() {
-                let report = InterfaceCompareReport {
-                    package_id: package_oid.to_string(),
-                    summary,
-                    mismatches,
-                };
+                let report =
+                    InterfaceCompareReport::new(package_oid.to_string(), summary, mismatches);
                 let mut report_value =
                     serde_json::to_value(report).context("serialize compare report")?;
                 crate::utils::canonicalize_json_value(&mut report_value);
@@ -1,6 +1,10 @@
-use serde::Serialize;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::utils::canonicalize_json_value;
 
 #[derive(Debug, Serialize)]
 pub struct PackageInterfaceJson {
@@ -76,7 +80,7 @@ pub struct BytecodeModuleJson {
     pub functions: BTreeMap<String, BytecodeFunctionJson>,
 }
 
-#[derive(Debug, Serialize, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Default)]
 pub struct InterfaceCompareSummary {
     pub modules_compared: usize,
     pub modules_missing_in_bytecode: usize,
@@ -86,23 +90,591 @@ pub struct InterfaceCompareSummary {
     pub functions_compared: usize,
     pub function_mismatches: usize,
     pub mismatches_total: usize,
+    /// Missing plus extra modules; `mismatches_total == module_set_mismatches + item_mismatches`.
+    #[serde(default)]
+    pub module_set_mismatches: usize,
+    /// Everything found inside modules present on both sides.
+    #[serde(default)]
+    pub item_mismatches: usize,
+    #[serde(default)]
+    pub error_mismatches: usize,
+    #[serde(default)]
+    pub warning_mismatches: usize,
+    #[serde(default)]
+    pub info_mismatches: usize,
+    /// Mismatches downgraded to `Info` by `accept_paths` (also counted in `info_mismatches`).
+    #[serde(default)]
+    pub accepted_mismatches: usize,
+    /// Mismatches suppressed by `ignore_paths`; they are left out of every other count.
+    #[serde(default)]
+    pub ignored_mismatches: usize,
+    /// False when the walk was cancelled before every module was compared.
+    #[serde(default = "default_true")]
+    pub complete: bool,
+    /// Field/param/return type positions compared, and how many of them differed.
+    #[serde(default)]
+    pub type_sites_compared: usize,
+    #[serde(default)]
+    pub type_sites_mismatched: usize,
+    /// Structs and functions declared on one side only.
+    #[serde(default)]
+    pub structs_missing_in_bytecode: usize,
+    #[serde(default)]
+    pub structs_extra_in_bytecode: usize,
+    #[serde(default)]
+    pub functions_missing_in_bytecode: usize,
+    #[serde(default)]
+    pub functions_extra_in_bytecode: usize,
+    /// Compared structs and functions with at least one `Error` finding, each counted once.
+    #[serde(default)]
+    pub structs_with_errors: usize,
+    #[serde(default)]
+    pub functions_with_errors: usize,
 }
 
-#[derive(Debug, Serialize)]
+impl InterfaceCompareSummary {
+    /// Process exit code for CI gating: `0` when nothing at or above `fail_on` was found,
+    /// otherwise `1` (errors), `2` (warnings) or `3` (infos) for the most severe level present.
+    pub fn exit_code(&self, fail_on: MismatchSeverity) -> i32 {
+        if self.error_mismatches > 0 {
+            1
+        } else if self.warning_mismatches > 0 && fail_on <= MismatchSeverity::Warning {
+            2
+        } else if self.info_mismatches > 0 && fail_on <= MismatchSeverity::Info {
+            3
+        } else {
+            0
+        }
+    }
+}
+
+/// Headline form, also used for the CLI `interface_compare:` log line.
+impl fmt::Display for InterfaceCompareSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "modules_compared={} modules_missing_in_bytecode={} modules_extra_in_bytecode={} structs_compared={} struct_mismatches={} functions_compared={} function_mismatches={} mismatches_total={}",
+            self.modules_compared,
+            self.modules_missing_in_bytecode,
+            self.modules_extra_in_bytecode,
+            self.structs_compared,
+            self.struct_mismatches,
+            self.functions_compared,
+            self.function_mismatches,
+            self.mismatches_total
+        )
+    }
+}
+
+/// `InterfaceCompareSummary` of a bytecode-as-reference comparison, with module-set counts named
+/// from the bytecode's point of view.
+#[derive(Debug, Serialize, Copy, Clone)]
+pub struct BytecodeVsRpcSummary {
+    pub modules_compared: usize,
+    pub modules_missing_in_rpc: usize,
+    pub modules_extra_in_rpc: usize,
+    pub structs_compared: usize,
+    pub struct_mismatches: usize,
+    pub functions_compared: usize,
+    pub function_mismatches: usize,
+    pub mismatches_total: usize,
+    pub module_set_mismatches: usize,
+    pub item_mismatches: usize,
+    pub error_mismatches: usize,
+    pub warning_mismatches: usize,
+    pub info_mismatches: usize,
+    pub accepted_mismatches: usize,
+    pub ignored_mismatches: usize,
+    pub complete: bool,
+    pub type_sites_compared: usize,
+    pub type_sites_mismatched: usize,
+    pub structs_missing_in_rpc: usize,
+    pub structs_extra_in_rpc: usize,
+    pub functions_missing_in_rpc: usize,
+    pub functions_extra_in_rpc: usize,
+    pub structs_with_errors: usize,
+    pub functions_with_errors: usize,
+}
+
+impl From<InterfaceCompareSummary> for BytecodeVsRpcSummary {
+    fn from(s: InterfaceCompareSummary) -> Self {
+        Self {
+            modules_compared: s.modules_compared,
+            modules_missing_in_rpc: s.modules_extra_in_bytecode,
+            modules_extra_in_rpc: s.modules_missing_in_bytecode,
+            structs_compared: s.structs_compared,
+            struct_mismatches: s.struct_mismatches,
+            functions_compared: s.functions_compared,
+            function_mismatches: s.function_mismatches,
+            mismatches_total: s.mismatches_total,
+            module_set_mismatches: s.module_set_mismatches,
+            item_mismatches: s.item_mismatches,
+            error_mismatches: s.error_mismatches,
+            warning_mismatches: s.warning_mismatches,
+            info_mismatches: s.info_mismatches,
+            accepted_mismatches: s.accepted_mismatches,
+            ignored_mismatches: s.ignored_mismatches,
+            complete: s.complete,
+            type_sites_compared: s.type_sites_compared,
+            type_sites_mismatched: s.type_sites_mismatched,
+            structs_missing_in_rpc: s.structs_extra_in_bytecode,
+            structs_extra_in_rpc: s.structs_missing_in_bytecode,
+            functions_missing_in_rpc: s.functions_extra_in_bytecode,
+            functions_extra_in_rpc: s.functions_missing_in_bytecode,
+            structs_with_errors: s.structs_with_errors,
+            functions_with_errors: s.functions_with_errors,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How much a mismatch matters. Ordered so `a >= b` means "at least as severe".
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchSeverity {
+    Info,
+    Warning,
+    /// Also the severity of mismatches in reports written before severities existed.
+    #[default]
+    Error,
+}
+
+/// What a mismatch is about, independent of the human-readable `reason`.
+#[derive(
+    Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    ModuleMissing,
+    ModuleExtra,
+    ModuleNameMismatch,
+    /// Names matched only ignoring case (`case_insensitive_names`).
+    NameCasing,
+    StructMissing,
+    /// A struct only the bytecode side declares.
+    StructExtra,
+    AbilitiesMismatch,
+    /// A `key` struct gained `store`, so anyone holding it can `public_transfer` it.
+    PublicTransferGained,
+    /// A `key` struct lost `store`, so holders can no longer `public_transfer` it.
+    PublicTransferLost,
+    DynamicFieldStoreLost,
+    StructSizeChanged,
+    /// A struct gained or lost `copy` (`Warning`; also reported by the abilities diff).
+    CopyabilityChanged,
+    /// A struct with no abilities (a "hot potato") gained `drop`, `store` or `key`.
+    HotPotatoChanged,
+    TypeParamArityMismatch,
+    /// Type params went from zero to some, or from some to zero.
+    GenericityChanged,
+    /// Module-wide declared type param totals are far apart (coarse `Info` signal).
+    TypeParamDisparity,
+    ConstraintAdded,
+    ConstraintRemoved,
+    PhantomChanged,
+    DuplicateFieldName,
+    AccessorFieldsChanged,
+    FieldCountMismatch,
+    FieldNameMismatch,
+    FieldTypeMismatch,
+    /// Field-by-name comparison (`match_fields_by_name`) only.
+    FieldMissing,
+    FieldExtra,
+    FieldOrderChanged,
+    /// A type on the RPC side could not be parsed, so its site was not compared.
+    RpcTypeParseError,
+    /// A type on the bytecode side could not be parsed, so its site was not compared.
+    BytecodeTypeParseError,
+    ConstantMissing,
+    ConstantExtra,
+    ConstantMismatch,
+    /// A module import present on one side only (`compare_imports`, `Info`).
+    ImportChanged,
+    FunctionMissing,
+    FunctionExtra,
+    VisibilityMismatch,
+    EntryMismatch,
+    CallabilityChanged,
+    PassingModeChanged,
+    TxContextChanged,
+    DeprecationChanged,
+    /// A parameter of `init` changed (reported instead of a param type mismatch).
+    InitSignatureChanged,
+    /// The bytecode `init` is not one Sui would run at publish; checked on the bytecode side
+    /// alone, since RPC never lists `init`.
+    InitSignatureInvalid,
+    /// An `entry` function parameter of a type Sui rejects in entry functions.
+    InvalidEntryParam,
+    InvalidAcquires,
+    InternalTypeExposed,
+    PackageVersionMismatch,
+    UnavailableTypeReference,
+    HotPotatoReturnChanged,
+    PossibleCollision,
+    ParamCountMismatch,
+    ParamTypeMismatch,
+    ParamNameMismatch,
+    ReturnCountMismatch,
+    ReturnTypeMismatch,
+    /// Also the kind of mismatches in reports written before kinds existed.
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InterfaceCompareMismatch {
     pub path: String,
     pub reason: String,
+    #[serde(default)]
+    pub kind: MismatchKind,
+    #[serde(default)]
+    pub severity: MismatchSeverity,
+    /// True when the path matched an `accept_paths` pattern (a documented, tolerated difference).
+    #[serde(default)]
+    pub accepted: bool,
     pub rpc: Option<Value>,
     pub bytecode: Option<Value>,
+    /// Full canonical types for type mismatches (`include_canonical`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_canonical: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode_canonical: Option<Value>,
+}
+
+impl fmt::Display for MismatchSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MismatchSeverity::Info => "INFO",
+            MismatchSeverity::Warning => "WARNING",
+            MismatchSeverity::Error => "ERROR",
+        })
+    }
+}
+
+/// `[ERROR] modules/pool/structs/Pool/fields[0]/type: field type mismatch`
+impl fmt::Display for InterfaceCompareMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.path, self.reason)?;
+        if self.accepted {
+            f.write_str(" (accepted)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Which extractor produced an interface JSON value (selects key names and type shapes).
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaSource {
+    Rpc,
+    Bytecode,
+}
+
+/// Estimated BCS-serialized size of a value in bytes; `max` is `None` when unbounded (vectors,
+/// strings).
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+pub struct SizeEstimate {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl SizeEstimate {
+    pub fn fixed(n: usize) -> Self {
+        SizeEstimate {
+            min: n,
+            max: Some(n),
+        }
+    }
+
+    pub fn plus(self, other: SizeEstimate) -> Self {
+        SizeEstimate {
+            min: self.min + other.min,
+            max: self.max.zip(other.max).map(|(a, b)| a + b),
+        }
+    }
+}
+
+/// What the comparator does with a type kind its canonicalizers do not recognize.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownTypePolicy {
+    /// Report a parse-error mismatch with `Error` severity.
+    #[default]
+    Error,
+    /// Report the same parse-error mismatch with `Warning` severity.
+    Warn,
+    /// Canonicalize the unknown node to an opaque form holding its raw JSON and compare that.
+    PassThrough,
+}
+
+/// Serialization format of an interface document handed to `normalization::parse_interface_str`.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InputFormat {
+    Json,
+    /// Requires the `yaml` feature.
+    Yaml,
+    /// Requires the `toml` feature.
+    Toml,
+}
+
+/// How off-chain callers can reach a function, derived from visibility + `entry`.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionCallability {
+    /// `public entry`: callable from PTBs and other packages.
+    Entrypoint,
+    /// `public` (non-entry): callable from PTBs and other packages, not as a transaction entry.
+    Public,
+    /// `entry` with friend/private visibility: callable only as a transaction entry.
+    EntryOnly,
+    /// Not reachable from outside the package.
+    Internal,
+}
+
+/// How a parameter receives its argument, derived from the top level of its type.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PassingMode {
+    /// Moved into the call; an object argument is consumed.
+    ByValue,
+    /// `&T`: borrowed immutably.
+    ByRef,
+    /// `&mut T`: borrowed mutably.
+    ByMutRef,
+}
+
+/// Function-level outcome of a comparison, for deciding which bindings to regenerate.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionStatus {
+    Compatible,
+    Changed,
+    /// In the RPC interface only.
+    Removed,
+    /// In the bytecode interface only.
+    Added,
+}
+
+/// One mismatch rolled up under a `FunctionVerdict`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct FunctionChange {
+    pub path: String,
+    pub reason: String,
+    pub kind: MismatchKind,
+    pub severity: MismatchSeverity,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct FunctionVerdict {
+    pub module: String,
+    pub name: String,
+    pub status: FunctionStatus,
+    pub changes: Vec<FunctionChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationIssue {
+    pub path: String,
+    pub reason: String,
+    pub value: Option<Value>,
 }
 
+/// Cheap count-only pre-check between two interfaces (no type canonicalization).
 #[derive(Debug, Serialize)]
+pub struct ShapeCheck {
+    pub rpc: SanityCounts,
+    pub bytecode: SanityCounts,
+    pub modules_match: bool,
+    pub structs_match: bool,
+    pub functions_match: bool,
+}
+
+/// Serialized (compact JSON) byte length of each interface. `percent_diff` is the absolute
+/// difference relative to the larger side (0 when both are empty).
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct SizeDiff {
+    pub rpc_bytes: usize,
+    pub bytecode_bytes: usize,
+    pub percent_diff: f64,
+}
+
+/// Comparison counts for one module present on both sides.
+#[derive(Debug, Serialize, Clone)]
+pub struct ModuleCompareStats {
+    pub module: String,
+    pub structs_compared: usize,
+    pub functions_compared: usize,
+    pub mismatches: usize,
+}
+
+/// Per-module view of a comparison (intersecting modules only, sorted by name).
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ModuleBreakdown {
+    pub modules: Vec<ModuleCompareStats>,
+}
+
+impl ModuleBreakdown {
+    /// Intersecting modules that compared with zero mismatches.
+    pub fn clean_modules(&self) -> Vec<String> {
+        self.modules
+            .iter()
+            .filter(|m| m.mismatches == 0)
+            .map(|m| m.module.clone())
+            .collect()
+    }
+}
+
+/// Version of the serialized `InterfaceCompareReport` layout; bump when fields change meaning.
+pub const COMPARE_REPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InterfaceCompareReport {
+    /// `0` for reports written before the field existed.
+    #[serde(default)]
+    pub schema_version: u32,
     pub package_id: String,
+    /// When the comparison ran, if the caller recorded it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at_unix_seconds: Option<u64>,
     pub summary: InterfaceCompareSummary,
     pub mismatches: Vec<InterfaceCompareMismatch>,
 }
 
+impl InterfaceCompareReport {
+    pub fn new(
+        package_id: String,
+        summary: InterfaceCompareSummary,
+        mismatches: Vec<InterfaceCompareMismatch>,
+    ) -> Self {
+        Self {
+            schema_version: COMPARE_REPORT_SCHEMA_VERSION,
+            package_id,
+            generated_at_unix_seconds: None,
+            summary,
+            mismatches,
+        }
+    }
+
+    /// The report as one self-describing JSON object with every map key-sorted, for embedding
+    /// in larger documents. `from_json_str` reads it back.
+    pub fn to_json_value(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        canonicalize_json_value(&mut value);
+        value
+    }
+
+    /// Parse a stored report. Older layouts are accepted (missing fields take their defaults);
+    /// reports from a newer, unknown schema version are rejected.
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        let report: Self = serde_json::from_str(s).context("parse compare report")?;
+        if report.schema_version > COMPARE_REPORT_SCHEMA_VERSION {
+            return Err(anyhow!(
+                "compare report schema_version {} is newer than supported version {}",
+                report.schema_version,
+                COMPARE_REPORT_SCHEMA_VERSION
+            ));
+        }
+        Ok(report)
+    }
+
+    /// Union the mismatches of several `(source, report)` comparisons of the same package, e.g.
+    /// one per RPC provider. Mismatches are keyed by `(path, kind)` and only the samples kept in
+    /// each report take part. Reports that kept fewer samples than `summary.mismatches_total` are
+    /// listed in `truncated_sources`: a mismatch missing from one of them proves nothing, so it
+    /// does not make the mismatch `inconsistent`.
+    pub fn merge(results: &[(String, InterfaceCompareReport)]) -> MergedCompareReport {
+        let mut merged: BTreeMap<(String, MismatchKind), MergedMismatch> = BTreeMap::new();
+        for (source, report) in results {
+            for m in &report.mismatches {
+                let entry =
+                    merged
+                        .entry((m.path.clone(), m.kind))
+                        .or_insert_with(|| MergedMismatch {
+                            path: m.path.clone(),
+                            reason: m.reason.clone(),
+                            kind: m.kind,
+                            severity: m.severity,
+                            sources: Vec::new(),
+                            inconsistent: false,
+                        });
+                entry.severity = entry.severity.max(m.severity);
+                if !entry.sources.contains(source) {
+                    entry.sources.push(source.clone());
+                }
+            }
+        }
+
+        let sources: Vec<String> = results.iter().map(|(s, _)| s.clone()).collect();
+        let truncated_sources: Vec<String> = results
+            .iter()
+            .filter(|(_, r)| r.mismatches.len() < r.summary.mismatches_total)
+            .map(|(s, _)| s.clone())
+            .collect();
+        let mut mismatches: Vec<MergedMismatch> = merged.into_values().collect();
+        for m in &mut mismatches {
+            m.inconsistent = sources
+                .iter()
+                .any(|s| !m.sources.contains(s) && !truncated_sources.contains(s));
+        }
+        MergedCompareReport {
+            inconsistent: mismatches.iter().filter(|m| m.inconsistent).count(),
+            sources,
+            truncated_sources,
+            mismatches,
+        }
+    }
+}
+
+/// A mismatch from `InterfaceCompareReport::merge`, with the sources that reported it.
+#[derive(Debug, Serialize, Clone)]
+pub struct MergedMismatch {
+    pub path: String,
+    pub reason: String,
+    pub kind: MismatchKind,
+    /// Highest severity any source reported.
+    pub severity: MismatchSeverity,
+    pub sources: Vec<String>,
+    /// Reported by some sources but not others, i.e. the providers disagree.
+    pub inconsistent: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergedCompareReport {
+    pub sources: Vec<String>,
+    /// Sources whose report was capped by `max_mismatches`.
+    pub truncated_sources: Vec<String>,
+    /// Number of mismatches flagged `inconsistent`.
+    pub inconsistent: usize,
+    pub mismatches: Vec<MergedMismatch>,
+}
+
+/// A package publication recorded in a `Move.lock` `[env.<name>]` section.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct LockPublication {
+    pub env: String,
+    pub package_id: String,
+}
+
+/// One package of a batch comparison: either a report or the error that stopped it.
+#[derive(Debug, Serialize)]
+pub struct BatchCompareRow {
+    pub env: String,
+    pub package_id: String,
+    pub report: Option<InterfaceCompareReport>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct BatchCompareReport {
+    pub packages_compared: usize,
+    pub packages_failed: usize,
+    pub rows: Vec<BatchCompareRow>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BatchSummaryRow {
     pub input_id: String,
@@ -269,3 +841,237 @@ pub struct CorpusSummaryStats {
     pub interface_mismatches_total: usize,
     pub problems: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_mismatch_and_summary() {
+        let mismatch = InterfaceCompareMismatch {
+            path: "modules/pool/structs/Pool/fields[0]/type".to_string(),
+            reason: "field type mismatch".to_string(),
+            kind: MismatchKind::FieldTypeMismatch,
+            severity: MismatchSeverity::Error,
+            accepted: false,
+            rpc: None,
+            bytecode: None,
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        };
+        assert_eq!(
+            mismatch.to_string(),
+            "[ERROR] modules/pool/structs/Pool/fields[0]/type: field type mismatch"
+        );
+
+        let summary = InterfaceCompareSummary {
+            modules_compared: 2,
+            modules_missing_in_bytecode: 0,
+            modules_extra_in_bytecode: 1,
+            structs_compared: 3,
+            struct_mismatches: 1,
+            functions_compared: 4,
+            function_mismatches: 0,
+            mismatches_total: 2,
+            module_set_mismatches: 1,
+            item_mismatches: 1,
+            error_mismatches: 2,
+            warning_mismatches: 0,
+            info_mismatches: 0,
+            accepted_mismatches: 0,
+            ignored_mismatches: 0,
+            complete: true,
+            type_sites_compared: 0,
+            type_sites_mismatched: 0,
+            structs_missing_in_bytecode: 0,
+            structs_extra_in_bytecode: 0,
+            functions_missing_in_bytecode: 0,
+            functions_extra_in_bytecode: 0,
+            structs_with_errors: 1,
+            functions_with_errors: 0,
+        };
+        assert_eq!(
+            summary.to_string(),
+            "modules_compared=2 modules_missing_in_bytecode=0 modules_extra_in_bytecode=1 structs_compared=3 struct_mismatches=1 functions_compared=4 function_mismatches=0 mismatches_total=2"
+        );
+    }
+
+    #[test]
+    fn test_compare_report_round_trips_schema_version() {
+        let summary: InterfaceCompareSummary = serde_json::from_value(serde_json::json!({
+            "modules_compared": 1,
+            "modules_missing_in_bytecode": 0,
+            "modules_extra_in_bytecode": 0,
+            "structs_compared": 0,
+            "struct_mismatches": 0,
+            "functions_compared": 1,
+            "function_mismatches": 1,
+            "mismatches_total": 1
+        }))
+        .unwrap();
+        assert!(summary.complete);
+
+        let report = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            summary,
+            vec![InterfaceCompareMismatch {
+                path: "modules/m/functions/f/visibility".to_string(),
+                reason: "visibility mismatch".to_string(),
+                kind: MismatchKind::VisibilityMismatch,
+                severity: MismatchSeverity::Error,
+                accepted: false,
+                rpc: None,
+                bytecode: None,
+                rpc_canonical: None,
+                bytecode_canonical: None,
+            }],
+        );
+        let text = serde_json::to_string(&report).unwrap();
+        assert!(text.contains("\"schema_version\":1"));
+        let read = InterfaceCompareReport::from_json_str(&text).unwrap();
+        assert_eq!(read.schema_version, COMPARE_REPORT_SCHEMA_VERSION);
+        assert_eq!(read.mismatches[0].reason, "visibility mismatch");
+
+        let mut legacy: Value = serde_json::from_str(&text).unwrap();
+        legacy.as_object_mut().unwrap().remove("schema_version");
+        let read = InterfaceCompareReport::from_json_str(&legacy.to_string()).unwrap();
+        assert_eq!(read.schema_version, 0);
+
+        let mut future: Value = serde_json::from_str(&text).unwrap();
+        future["schema_version"] = Value::from(COMPARE_REPORT_SCHEMA_VERSION + 1);
+        let err = InterfaceCompareReport::from_json_str(&future.to_string()).unwrap_err();
+        assert!(err.to_string().contains("newer than supported"));
+    }
+
+    #[test]
+    fn test_compare_report_to_json_value_is_sorted_and_round_trips() {
+        let summary: InterfaceCompareSummary = serde_json::from_value(serde_json::json!({
+            "modules_compared": 1,
+            "modules_missing_in_bytecode": 0,
+            "modules_extra_in_bytecode": 0,
+            "structs_compared": 0,
+            "struct_mismatches": 0,
+            "functions_compared": 0,
+            "function_mismatches": 1,
+            "mismatches_total": 1
+        }))
+        .unwrap();
+        let mut report = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            summary,
+            vec![mismatch(
+                "modules/m/functions/f",
+                MismatchKind::FunctionMissing,
+            )],
+        );
+        report.generated_at_unix_seconds = Some(1_767_225_600);
+
+        let value = report.to_json_value();
+        fn assert_sorted(v: &Value) {
+            match v {
+                Value::Object(map) => {
+                    let keys: Vec<&String> = map.keys().collect();
+                    let mut sorted = keys.clone();
+                    sorted.sort();
+                    assert_eq!(keys, sorted);
+                    map.values().for_each(assert_sorted);
+                }
+                Value::Array(items) => items.iter().for_each(assert_sorted),
+                _ => {}
+            }
+        }
+        assert_sorted(&value);
+        assert_eq!(value["schema_version"], COMPARE_REPORT_SCHEMA_VERSION);
+        assert_eq!(value["package_id"], "0x1");
+
+        let read = InterfaceCompareReport::from_json_str(&value.to_string()).unwrap();
+        assert_eq!(read.to_json_value(), value);
+        assert_eq!(read.generated_at_unix_seconds, Some(1_767_225_600));
+    }
+
+    fn mismatch(path: &str, kind: MismatchKind) -> InterfaceCompareMismatch {
+        InterfaceCompareMismatch {
+            path: path.to_string(),
+            reason: "mismatch".to_string(),
+            kind,
+            severity: MismatchSeverity::Error,
+            accepted: false,
+            rpc: None,
+            bytecode: None,
+            rpc_canonical: None,
+            bytecode_canonical: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_records_provenance_and_inconsistency() {
+        let summary = || -> InterfaceCompareSummary {
+            serde_json::from_value(serde_json::json!({
+                "modules_compared": 1,
+                "modules_missing_in_bytecode": 0,
+                "modules_extra_in_bytecode": 0,
+                "structs_compared": 0,
+                "struct_mismatches": 0,
+                "functions_compared": 1,
+                "function_mismatches": 1,
+                "mismatches_total": 1
+            }))
+            .unwrap()
+        };
+        let visibility = "modules/m/functions/f/visibility";
+        let entry = "modules/m/functions/f/is_entry";
+        let a = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            summary(),
+            vec![mismatch(visibility, MismatchKind::VisibilityMismatch)],
+        );
+        let b = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            summary(),
+            vec![
+                mismatch(visibility, MismatchKind::VisibilityMismatch),
+                mismatch(entry, MismatchKind::EntryMismatch),
+            ],
+        );
+
+        let merged = InterfaceCompareReport::merge(&[
+            ("provider-a".to_string(), a),
+            ("provider-b".to_string(), b),
+        ]);
+        assert_eq!(merged.mismatches.len(), 2);
+        assert_eq!(merged.inconsistent, 1);
+        let by_path = |p: &str| merged.mismatches.iter().find(|m| m.path == p).unwrap();
+        assert_eq!(
+            by_path(visibility).sources,
+            vec!["provider-a", "provider-b"]
+        );
+        assert!(!by_path(visibility).inconsistent);
+        assert_eq!(by_path(entry).sources, vec!["provider-b"]);
+        assert!(by_path(entry).inconsistent);
+        assert!(merged.truncated_sources.is_empty());
+
+        // provider-a found both but kept one sample: its silence on `entry` is not a disagreement.
+        let capped = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            InterfaceCompareSummary {
+                mismatches_total: 2,
+                ..summary()
+            },
+            vec![mismatch(visibility, MismatchKind::VisibilityMismatch)],
+        );
+        let b = InterfaceCompareReport::new(
+            "0x1".to_string(),
+            summary(),
+            vec![
+                mismatch(visibility, MismatchKind::VisibilityMismatch),
+                mismatch(entry, MismatchKind::EntryMismatch),
+            ],
+        );
+        let merged = InterfaceCompareReport::merge(&[
+            ("provider-a".to_string(), capped),
+            ("provider-b".to_string(), b),
+        ]);
+        assert_eq!(merged.truncated_sources, vec!["provider-a"]);
+        assert_eq!(merged.inconsistent, 0);
+    }
+}
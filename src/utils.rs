@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::fs::{self, File};
+#[cfg(feature = "rpc")]
 use std::future::Future;
 use std::io::{self, BufWriter, Write};
 use std::path::{Path, PathBuf};
@@ -58,6 +59,7 @@ pub fn should_retry_error(error: &anyhow::Error) -> bool {
         || s.to_ascii_lowercase().contains("transport")
 }
 
+#[cfg(feature = "rpc")]
 pub async fn with_retries<T, F, Fut>(
     retries: usize,
     initial_backoff: Duration,
@@ -86,15 +88,38 @@ where
     }
 }
 
+/// Object keys whose array values are sets in both interface schemas: ability lists
+/// (`abilities`) and type param constraint lists (`constraints`).
+pub const ORDER_INSENSITIVE_KEYS: &[&str] = &["abilities", "constraints"];
+
+/// Sort object keys recursively. Array order is always preserved: type argument, field, param
+/// and return lists are positional. Use `canonicalize_json_value_unordered` to also sort arrays
+/// that are sets.
 pub fn canonicalize_json_value(value: &mut Value) {
+    canonicalize_impl(value, &[]);
+}
+
+/// `canonicalize_json_value`, additionally sorting (by their canonical JSON text) the elements
+/// of arrays held directly under any key in `unordered_keys`, e.g. `ORDER_INSENSITIVE_KEYS`.
+/// Arrays under other keys, such as `type_args`/`typeArguments`, keep their order.
+pub fn canonicalize_json_value_unordered(value: &mut Value, unordered_keys: &[&str]) {
+    canonicalize_impl(value, unordered_keys);
+}
+
+fn canonicalize_impl(value: &mut Value, unordered_keys: &[&str]) {
     match value {
         Value::Object(map) => {
             let old_map = std::mem::take(map);
             let mut entries: Vec<(String, Value)> = old_map.into_iter().collect();
             entries.sort_by(|(a, _), (b, _)| a.cmp(b));
 
-            for (_, v) in entries.iter_mut() {
-                canonicalize_json_value(v);
+            for (k, v) in entries.iter_mut() {
+                canonicalize_impl(v, unordered_keys);
+                if let Value::Array(values) = v {
+                    if unordered_keys.contains(&k.as_str()) {
+                        values.sort_by_cached_key(Value::to_string);
+                    }
+                }
             }
 
             for (k, v) in entries {
@@ -103,13 +128,48 @@ pub fn canonicalize_json_value(value: &mut Value) {
         }
         Value::Array(values) => {
             for v in values.iter_mut() {
-                canonicalize_json_value(v);
+                canonicalize_impl(v, unordered_keys);
             }
         }
         _ => {}
     }
 }
 
+/// Match a `/`-separated path against a pattern where `*` matches within one segment, `**`
+/// matches across segments, and `?` matches one non-`/` character. A pattern also matches any
+/// path it is a segment-aligned prefix of (`modules/m` matches `modules/m/structs/S`).
+pub fn path_matches_pattern(pattern: &str, path: &str) -> bool {
+    if glob_match(pattern.as_bytes(), path.as_bytes()) {
+        return true;
+    }
+    path.match_indices('/')
+        .any(|(i, _)| glob_match(pattern.as_bytes(), &path.as_bytes()[..i]))
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            (0..=text.len()).any(|i| glob_match(&pattern[2..], &text[i..]))
+        }
+        Some(b'*') => {
+            for i in 0..=text.len() {
+                if glob_match(&pattern[1..], &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => {
+            text.first().is_some_and(|c| *c != b'/') && glob_match(&pattern[1..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
 pub fn bytes_to_hex_prefixed(bytes: &[u8]) -> String {
     let mut out = String::with_capacity(2 + bytes.len() * 2);
     out.push_str("0x");
@@ -236,6 +296,53 @@ mod tests {
         assert_eq!(bytes_to_hex_prefixed(&[]), "0x");
     }
 
+    #[test]
+    fn test_canonicalize_json_value_sorts_only_flagged_arrays() {
+        let input = serde_json::json!({
+            "type_args": [{"kind": "u64"}, {"kind": "bool"}],
+            "abilities": ["store", "copy"],
+            "type_params": [{"constraints": ["store", "drop"]}],
+            "rpc": {"abilities": {"abilities": ["Store", "Copy"]}}
+        });
+
+        let mut plain = input.clone();
+        canonicalize_json_value(&mut plain);
+        assert_eq!(plain["abilities"], serde_json::json!(["store", "copy"]));
+        assert_eq!(
+            plain.as_object().unwrap().keys().collect::<Vec<_>>(),
+            vec!["abilities", "rpc", "type_args", "type_params"]
+        );
+
+        let mut unordered = input;
+        canonicalize_json_value_unordered(&mut unordered, ORDER_INSENSITIVE_KEYS);
+        assert_eq!(unordered["abilities"], serde_json::json!(["copy", "store"]));
+        assert_eq!(
+            unordered["type_params"][0]["constraints"],
+            serde_json::json!(["drop", "store"])
+        );
+        assert_eq!(
+            unordered["rpc"]["abilities"]["abilities"],
+            serde_json::json!(["Copy", "Store"])
+        );
+        assert_eq!(
+            unordered["type_args"],
+            serde_json::json!([{"kind": "u64"}, {"kind": "bool"}])
+        );
+    }
+
+    #[test]
+    fn test_path_matches_pattern() {
+        let path = "modules/pool/functions/swap/visibility";
+        assert!(path_matches_pattern(
+            "modules/*/functions/*/visibility",
+            path
+        ));
+        assert!(path_matches_pattern("modules/pool", path));
+        assert!(path_matches_pattern("**/visibility", path));
+        assert!(!path_matches_pattern("modules/*/visibility", path));
+        assert!(!path_matches_pattern("modules/po", path));
+    }
+
     #[test]
     fn test_fnv1a64() {
         // Known test vectors for FNV-1a 64-bit
@@ -0,0 +1,320 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "rpc")]
+use crate::args::RetryConfig;
+#[cfg(feature = "rpc")]
+use crate::bytecode::{
+    build_bytecode_interface_value_from_compiled_modules, read_local_compiled_modules,
+};
+use crate::comparator::{compare_interface_rpc_vs_bytecode, InterfaceCompareOptions};
+#[cfg(feature = "rpc")]
+use crate::fetch::fetch_many;
+use crate::types::{BatchCompareReport, BatchCompareRow, InterfaceCompareReport, LockPublication};
+
+#[cfg(feature = "rpc")]
+const LOCK_FETCH_CONCURRENCY: usize = 4;
+
+/// Value of a `key = "value"` line, or `None` for any other line.
+fn quoted_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let (k, v) = line.split_once('=')?;
+    if k.trim() != key {
+        return None;
+    }
+    Some(v.trim().trim_matches('"'))
+}
+
+/// Published package IDs from `Move.lock` `[env.<name>]` sections, in file order.
+///
+/// Uses `latest-published-id` (the ID upgraded code lives at) and falls back to
+/// `original-published-id` for envs that were never upgraded.
+pub fn published_ids_from_lock(contents: &str) -> Vec<LockPublication> {
+    let mut out: Vec<LockPublication> = Vec::new();
+    let mut env: Option<(String, Option<String>, Option<String>)> = None;
+    let mut flush = |env: Option<(String, Option<String>, Option<String>)>| {
+        if let Some((name, original, latest)) = env {
+            if let Some(package_id) = latest.or(original) {
+                out.push(LockPublication {
+                    env: name,
+                    package_id,
+                });
+            }
+        }
+    };
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            flush(env.take());
+            env = line
+                .strip_prefix("[env.")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|name| (name.trim_matches('"').to_string(), None, None));
+            continue;
+        }
+        let Some((_, original, latest)) = env.as_mut() else {
+            continue;
+        };
+        if let Some(v) = quoted_value(line, "original-published-id") {
+            *original = Some(v.to_string());
+        } else if let Some(v) = quoted_value(line, "latest-published-id") {
+            *latest = Some(v.to_string());
+        }
+    }
+    flush(env.take());
+    out
+}
+
+/// `[package] name` from a `Move.toml`.
+fn package_name_from_manifest(contents: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some(v) = quoted_value(line, "name") {
+                return Some(v.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Compare each fetched RPC interface against its local bytecode interface. Entries whose fetch
+/// (or bytecode load) failed keep their error; the rest of the batch is unaffected.
+pub fn compare_many<B>(
+    fetched: Vec<(String, Result<Value>)>,
+    load_bytecode: B,
+    opts: &InterfaceCompareOptions,
+) -> Vec<(String, Result<InterfaceCompareReport>)>
+where
+    B: Fn(&str) -> Result<Value>,
+{
+    fetched
+        .into_iter()
+        .map(|(package_id, rpc)| {
+            let compared = rpc.context("fetch rpc interface").and_then(|rpc| {
+                let bytecode = load_bytecode(&package_id)?;
+                let (summary, mismatches) =
+                    compare_interface_rpc_vs_bytecode(&package_id, &rpc, &bytecode, opts.clone());
+                Ok(InterfaceCompareReport::new(
+                    package_id.clone(),
+                    summary,
+                    mismatches,
+                ))
+            });
+            (package_id, compared)
+        })
+        .collect()
+}
+
+/// `compare_many` over the publications of a lock file; `fetched` is in publication order.
+pub fn compare_publications<B>(
+    publications: &[LockPublication],
+    fetched: Vec<(String, Result<Value>)>,
+    load_bytecode: B,
+    opts: &InterfaceCompareOptions,
+) -> BatchCompareReport
+where
+    B: Fn(&str) -> Result<Value>,
+{
+    let mut report = BatchCompareReport::default();
+    let compared = compare_many(fetched, load_bytecode, opts);
+    for (publication, (package_id, compared)) in publications.iter().zip(compared) {
+        let (report_row, error) = match compared {
+            Ok(r) => {
+                report.packages_compared += 1;
+                (Some(r), None)
+            }
+            Err(e) => {
+                report.packages_failed += 1;
+                (None, Some(format!("{e:#}")))
+            }
+        };
+        report.rows.push(BatchCompareRow {
+            env: publication.env.clone(),
+            package_id,
+            report: report_row,
+            error,
+        });
+    }
+    report
+}
+
+/// Directories of the packages in the workspace rooted at `root`: `root` itself and each
+/// immediate subdirectory, when they have a `Move.toml`. Sorted by path.
+fn workspace_package_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![root.to_path_buf()];
+    for entry in fs::read_dir(root).with_context(|| format!("read {}", root.display()))? {
+        let path = entry
+            .with_context(|| format!("list {}", root.display()))?
+            .path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.retain(|dir| dir.join("Move.toml").is_file());
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Every workspace package published on `env`, with its build directory
+/// (`<package dir>/build/<package name>`). Each package's own `Move.lock` is read; its
+/// `[env.*]` sections are the same package on different chains, so only `env` is used and
+/// packages never published there are left out.
+pub fn workspace_publications(
+    workspace_root: &Path,
+    env: &str,
+) -> Result<Vec<(LockPublication, PathBuf)>> {
+    let mut out = Vec::new();
+    for dir in workspace_package_dirs(workspace_root)? {
+        let lock_path = dir.join("Move.lock");
+        if !lock_path.is_file() {
+            continue;
+        }
+        let lock = fs::read_to_string(&lock_path)
+            .with_context(|| format!("read {}", lock_path.display()))?;
+        let Some(publication) = published_ids_from_lock(&lock)
+            .into_iter()
+            .find(|p| p.env == env)
+        else {
+            continue;
+        };
+        let manifest_path = dir.join("Move.toml");
+        let manifest = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("read {}", manifest_path.display()))?;
+        let name = package_name_from_manifest(&manifest)
+            .ok_or_else(|| anyhow!("no [package] name in {}", manifest_path.display()))?;
+        out.push((publication, dir.join("build").join(name)));
+    }
+    Ok(out)
+}
+
+/// Compare each package of the workspace rooted at `workspace_root` that is published on `env`
+/// (see `workspace_publications`) against its local build, fetching the published interfaces
+/// from `rpc_endpoint` (each retried per `retry`). Fetch and build errors are reported per
+/// package.
+#[cfg(feature = "rpc")]
+pub async fn compare_workspace(
+    workspace_root: &Path,
+    env: &str,
+    rpc_endpoint: &str,
+    retry: RetryConfig,
+    opts: &InterfaceCompareOptions,
+) -> Result<BatchCompareReport> {
+    let packages = workspace_publications(workspace_root, env)?;
+    let publications: Vec<LockPublication> = packages.iter().map(|(p, _)| p.clone()).collect();
+
+    let ids: Vec<String> = publications.iter().map(|p| p.package_id.clone()).collect();
+    let fetched = fetch_many(rpc_endpoint, &ids, LOCK_FETCH_CONCURRENCY, retry).await;
+    let load_bytecode = |package_id: &str| {
+        let (_, build_dir) = packages
+            .iter()
+            .find(|(p, _)| p.package_id == package_id)
+            .ok_or_else(|| anyhow!("no workspace package published as {package_id}"))?;
+        let compiled = read_local_compiled_modules(build_dir)?;
+        build_bytecode_interface_value_from_compiled_modules(package_id, &compiled)
+            .map(|(_, value)| value)
+    };
+    Ok(compare_publications(
+        &publications,
+        fetched,
+        load_bytecode,
+        opts,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_ids_from_lock_fixture() {
+        let lock = include_str!("../tests/fixture_workspace/Move.lock");
+        assert_eq!(
+            published_ids_from_lock(lock),
+            vec![
+                LockPublication {
+                    env: "mainnet".to_string(),
+                    package_id:
+                        "0x0c7ae833c220aa73a3643a0d508afa4ac5d50d97312ea4584e35f9eb21b9df12"
+                            .to_string(),
+                },
+                LockPublication {
+                    env: "testnet".to_string(),
+                    package_id:
+                        "0x00000000000000000000000000000000000000000000000000000000000000a1"
+                            .to_string(),
+                },
+            ]
+        );
+        assert!(published_ids_from_lock(include_str!("../tests/fixture/Move.lock")).is_empty());
+    }
+
+    #[test]
+    fn test_workspace_publications_selects_env_per_package() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixture_workspace");
+        let mainnet = workspace_publications(&root, "mainnet").unwrap();
+        let found: Vec<(&str, PathBuf)> = mainnet
+            .iter()
+            .map(|(p, build)| {
+                (
+                    p.package_id.as_str(),
+                    build.strip_prefix(&root).unwrap().to_path_buf(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "0x0c7ae833c220aa73a3643a0d508afa4ac5d50d97312ea4584e35f9eb21b9df12",
+                    PathBuf::from("build/fixture_workspace")
+                ),
+                (
+                    "0x00000000000000000000000000000000000000000000000000000000000000b2",
+                    PathBuf::from("oracle/build/oracle")
+                ),
+            ]
+        );
+
+        // The oracle package was never published on testnet.
+        let testnet = workspace_publications(&root, "testnet").unwrap();
+        assert_eq!(testnet.len(), 1);
+        assert_eq!(
+            testnet[0].0.package_id,
+            "0x00000000000000000000000000000000000000000000000000000000000000a1"
+        );
+        assert!(workspace_publications(&root, "devnet").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compare_publications_isolates_fetch_errors() {
+        let publications =
+            published_ids_from_lock(include_str!("../tests/fixture_workspace/Move.lock"));
+        let empty = serde_json::json!({"modules": {}});
+        let fetched = vec![
+            (publications[0].package_id.clone(), Ok(empty.clone())),
+            (
+                publications[1].package_id.clone(),
+                Err(anyhow!("rate limited")),
+            ),
+        ];
+        let report = compare_publications(
+            &publications,
+            fetched,
+            |_: &str| Ok(empty.clone()),
+            &InterfaceCompareOptions::default(),
+        );
+
+        assert_eq!(report.packages_compared, 1);
+        assert_eq!(report.packages_failed, 1);
+        assert!(report.rows[0].report.is_some());
+        assert!(report.rows[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("rate limited"));
+    }
+}
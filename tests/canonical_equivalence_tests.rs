@@ -0,0 +1,163 @@
+//! Both canonicalizers must agree: a type rendered in the RPC (normalized-module) shape and in the
+//! bytecode extractor shape canonicalizes to the same value.
+
+use serde_json::Value;
+use sui_move_interface_extractor::normalization::{
+    bytecode_type_to_canonical_json, rpc_type_to_canonical_json,
+};
+use sui_move_interface_extractor::utils::canonicalize_json_value;
+
+/// Types as they appear in real Sui framework and DeFi signatures.
+const TYPES: &[&str] = &[
+    "bool",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "u256",
+    "address",
+    "signer",
+    "vector<u8>",
+    "vector<vector<u64>>",
+    "vector<address>",
+    "T0",
+    "&T1",
+    "&mut vector<T0>",
+    "0x1::string::String",
+    "0x1::ascii::String",
+    "0x1::option::Option<u64>",
+    "0x1::option::Option<0x2::object::ID>",
+    "0x1::type_name::TypeName",
+    "0x2::object::UID",
+    "0x2::object::ID",
+    "&mut 0x2::object::UID",
+    "0x2::coin::Coin<0x2::sui::SUI>",
+    "&mut 0x2::coin::Coin<T0>",
+    "0x2::coin::TreasuryCap<T0>",
+    "&0x2::coin::CoinMetadata<T0>",
+    "0x2::balance::Balance<T1>",
+    "0x2::balance::Supply<0x2::sui::SUI>",
+    "&0x2::clock::Clock",
+    "&mut 0x2::tx_context::TxContext",
+    "0x2::table::Table<address, 0x2::balance::Balance<T0>>",
+    "0x2::bag::Bag",
+    "0x2::object_table::ObjectTable<0x2::object::ID, T0>",
+    "0x2::dynamic_field::Field<0x1::string::String, vector<u8>>",
+    "0x2::vec_map::VecMap<u64, 0x1::option::Option<address>>",
+    "0x2::linked_table::LinkedTable<0x1::type_name::TypeName, u128>",
+    "0x2::transfer_policy::TransferPolicy<T0>",
+    "0x2::kiosk::KioskOwnerCap",
+    "0x3::staking_pool::StakedSui",
+    "0xdee9::clob_v2::Pool<T0, T1>",
+];
+
+/// Split `A, B<C, D>` at top-level commas.
+fn split_args(s: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let (mut depth, mut start) = (0usize, 0usize);
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                out.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(s[start..].trim());
+    out
+}
+
+/// Render a Move type string as `(rpc, bytecode)` JSON. RPC keeps the short address the node
+/// reports (`0x2`); bytecode uses the full 32-byte hex the extractor writes.
+fn render(ty: &str) -> (Value, Value) {
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("&mut ") {
+        let (r, b) = render(inner);
+        return (
+            serde_json::json!({"MutableReference": r}),
+            serde_json::json!({"kind": "ref", "mutable": true, "to": b}),
+        );
+    }
+    if let Some(inner) = ty.strip_prefix('&') {
+        let (r, b) = render(inner);
+        return (
+            serde_json::json!({"Reference": r}),
+            serde_json::json!({"kind": "ref", "mutable": false, "to": b}),
+        );
+    }
+    if let Some(inner) = ty.strip_prefix("vector<").and_then(|t| t.strip_suffix('>')) {
+        let (r, b) = render(inner);
+        return (
+            serde_json::json!({"Vector": r}),
+            serde_json::json!({"kind": "vector", "type": b}),
+        );
+    }
+    if let Some(index) = ty.strip_prefix('T').and_then(|i| i.parse::<u64>().ok()) {
+        return (
+            serde_json::json!({"TypeParameter": index}),
+            serde_json::json!({"kind": "type_param", "index": index}),
+        );
+    }
+    if !ty.contains("::") {
+        let mut rpc = ty.to_string();
+        rpc[..1].make_ascii_uppercase();
+        return (Value::String(rpc), serde_json::json!({"kind": ty}));
+    }
+
+    let (path, args) = match ty.split_once('<') {
+        Some((path, rest)) => (path, split_args(rest.strip_suffix('>').unwrap())),
+        None => (ty, Vec::new()),
+    };
+    let mut parts = path.split("::");
+    let (address, module, name) = (
+        parts.next().unwrap(),
+        parts.next().unwrap(),
+        parts.next().unwrap(),
+    );
+    let full_address = format!("0x{:0>64}", address.trim_start_matches("0x"));
+    let (rpc_args, byte_args): (Vec<Value>, Vec<Value>) = args.into_iter().map(render).unzip();
+    (
+        serde_json::json!({"Struct": {
+            "address": address,
+            "module": module,
+            "name": name,
+            "typeArguments": rpc_args
+        }}),
+        serde_json::json!({
+            "kind": "datatype",
+            "address": full_address,
+            "module": module,
+            "name": name,
+            "type_args": byte_args
+        }),
+    )
+}
+
+#[test]
+fn test_rpc_and_bytecode_canonical_forms_agree() {
+    assert!(TYPES.len() >= 30);
+    for ty in TYPES {
+        let (rpc, bytecode) = render(ty);
+        let mut r = rpc_type_to_canonical_json(&rpc)
+            .unwrap_or_else(|e| panic!("rpc canonicalization of {ty} failed: {e:#}"));
+        let mut b = bytecode_type_to_canonical_json(&bytecode)
+            .unwrap_or_else(|e| panic!("bytecode canonicalization of {ty} failed: {e:#}"));
+        canonicalize_json_value(&mut r);
+        canonicalize_json_value(&mut b);
+        assert_eq!(r, b, "canonical forms differ for {ty}");
+    }
+}
+
+#[test]
+fn test_canonical_forms_agree_for_uppercase_rpc_addresses() {
+    let (mut rpc, bytecode) = render("0x2::coin::Coin<0xdee9::clob_v2::Pool<T0, T1>>");
+    rpc["Struct"]["typeArguments"][0]["Struct"]["address"] = Value::from("0xDEE9");
+    assert_eq!(
+        rpc_type_to_canonical_json(&rpc).unwrap(),
+        bytecode_type_to_canonical_json(&bytecode).unwrap()
+    );
+}
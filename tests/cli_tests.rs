@@ -1,3 +1,6 @@
+// The CLI binary needs the `rpc` feature.
+#![cfg(feature = "rpc")]
+
 use assert_cmd::Command;
 use std::path::PathBuf;
 use tempfile::TempDir;
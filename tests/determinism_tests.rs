@@ -0,0 +1,114 @@
+use serde_json::Value;
+use sui_move_interface_extractor::comparator::{
+    compare_interface_rpc_vs_bytecode, InterfaceCompareOptions,
+};
+
+/// Copy of `v` with every object's keys inserted in reverse order (observable because the
+/// tests enable serde_json's `preserve_order`).
+fn reversed_keys(v: &Value) -> Value {
+    match v {
+        Value::Object(obj) => {
+            let mut out = serde_json::Map::new();
+            for (k, v) in obj.iter().rev() {
+                out.insert(k.clone(), reversed_keys(v));
+            }
+            Value::Object(out)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(reversed_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+fn interfaces() -> (Value, Value) {
+    let rpc_fun = |param: &str| {
+        serde_json::json!({
+            "visibility": "Public",
+            "isEntry": false,
+            "typeParameters": [],
+            "parameters": [param],
+            "return": []
+        })
+    };
+    let byte_fun = |param: &str| {
+        serde_json::json!({
+            "visibility": "public",
+            "is_entry": false,
+            "type_params": [],
+            "params": [{"kind": param}],
+            "returns": []
+        })
+    };
+    let rpc = serde_json::json!({
+        "modules": {
+            "zeta": {
+                "structs": {
+                    "Pool": {
+                        "abilities": {"abilities": ["Key", "Store"]},
+                        "typeParameters": [],
+                        "fields": [{"name": "a", "type": "U64"}, {"name": "b", "type": "Bool"}]
+                    },
+                    "Cap": {"abilities": {"abilities": ["Key"]}, "typeParameters": [], "fields": []}
+                },
+                "exposedFunctions": {"swap": rpc_fun("U64"), "add": rpc_fun("U8"), "mint": rpc_fun("Bool")}
+            },
+            "alpha": {"structs": {}, "exposedFunctions": {"f": rpc_fun("U16")}},
+            "gone": {"structs": {}, "exposedFunctions": {}}
+        }
+    });
+    let bytecode = serde_json::json!({
+        "modules": {
+            "zeta": {
+                "structs": {
+                    "Pool": {
+                        "abilities": ["key"],
+                        "type_params": [],
+                        "is_native": false,
+                        "fields": [{"name": "a", "type": {"kind": "u128"}}, {"name": "b", "type": {"kind": "u8"}}]
+                    },
+                    "Cap": {"abilities": ["key", "store"], "type_params": [], "is_native": false, "fields": []}
+                },
+                "functions": {"swap": byte_fun("u128"), "add": byte_fun("u16"), "burn": byte_fun("u64")}
+            },
+            "alpha": {"structs": {}, "functions": {"f": byte_fun("u32")}},
+            "extra": {"structs": {}, "functions": {}}
+        }
+    });
+    (rpc, bytecode)
+}
+
+#[test]
+fn test_compare_output_is_independent_of_key_order() {
+    let (rpc, bytecode) = interfaces();
+    let opts = InterfaceCompareOptions {
+        max_mismatches: 100,
+        include_values: true,
+        report_extra_functions: true,
+        ..Default::default()
+    };
+    let (reversed_rpc, reversed_bytecode) = (reversed_keys(&rpc), reversed_keys(&bytecode));
+    assert_ne!(
+        serde_json::to_string(&rpc).unwrap(),
+        serde_json::to_string(&reversed_rpc).unwrap()
+    );
+    assert_ne!(
+        serde_json::to_string(&bytecode).unwrap(),
+        serde_json::to_string(&reversed_bytecode).unwrap()
+    );
+
+    let (summary, mismatches) =
+        compare_interface_rpc_vs_bytecode("0x1", &rpc, &bytecode, opts.clone());
+    let (reordered_summary, reordered) =
+        compare_interface_rpc_vs_bytecode("0x1", &reversed_rpc, &reversed_bytecode, opts);
+
+    assert!(mismatches.len() > 5);
+    // Mismatches carry the input values, whose key order follows the input; compare them as
+    // values so only the order of the mismatches themselves counts.
+    assert_eq!(
+        serde_json::to_value(&mismatches).unwrap(),
+        serde_json::to_value(&reordered).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&summary).unwrap(),
+        serde_json::to_string(&reordered_summary).unwrap()
+    );
+}